@@ -0,0 +1,49 @@
+use asn1::{Decode, Encode, Error, ObjectIdentifier, Parser, RawValue, Tag};
+
+/// AlgorithmIdentifier ::= SEQUENCE {
+///     algorithm  OBJECT IDENTIFIER,
+///     parameters ANY DEFINED BY algorithm OPTIONAL }
+#[derive(Debug, PartialEq)]
+struct AlgorithmIdentifier {
+    algorithm: ObjectIdentifier,
+    parameters: Option<RawValue>,
+}
+
+impl Encode for AlgorithmIdentifier {
+    fn tag(&self) -> Tag {
+        Tag::SEQUENCE
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.algorithm.encode(out)?;
+        if let Some(v) = &self.parameters {
+            v.encode(out)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for AlgorithmIdentifier {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::SEQUENCE
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            algorithm: p.read()?,
+            parameters: p.read_optional()?,
+        })
+    }
+}
+
+fn main() {
+    // sha256WithRSAEncryption with NULL parameters.
+    let der = b"\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b\x05\x00";
+
+    let (alg, rest) = asn1::unmarshal::<AlgorithmIdentifier>(der).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!("1.2.840.113549.1.1.11", alg.algorithm.to_string());
+    assert_eq!(Some(Tag::NULL), alg.parameters.as_ref().map(|v| v.tag));
+
+    assert_eq!(der.to_vec(), asn1::marshal(&alg).unwrap());
+}
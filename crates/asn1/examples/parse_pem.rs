@@ -0,0 +1,14 @@
+use asn1::ObjectIdentifier;
+
+fn main() {
+    let data = b"-----BEGIN EC PARAMETERS-----
+BggqhkjOPQMBBw==
+-----END EC PARAMETERS-----
+";
+
+    let (block, _) = pem::decode(data).unwrap();
+    assert_eq!("EC PARAMETERS", block.type_);
+
+    let curve: ObjectIdentifier = asn1::parse_pem(&block).unwrap();
+    assert_eq!("1.2.840.10045.3.1.7", curve.to_string());
+}
@@ -0,0 +1,92 @@
+use crate::{Decode, Encode, Error, Parser, Tag};
+
+/// `BitString` is an ASN.1 BIT STRING, a sequence of bits packed into bytes from the most
+/// significant bit of the first byte.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitString {
+    /// The bits, with the unused bits of the last byte zero.
+    pub bytes: Vec<u8>,
+    /// The number of bits.
+    pub bit_length: usize,
+}
+
+impl BitString {
+    /// Returns a bit string of all bits of `bytes`.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        let bit_length = bytes.len() * 8;
+        Self { bytes, bit_length }
+    }
+
+    /// Returns the bit at index `i`, or false if `i` is out of range.
+    pub fn at(&self, i: usize) -> bool {
+        if i >= self.bit_length {
+            return false;
+        }
+        (self.bytes[i / 8] >> (7 - i % 8)) & 1 == 1
+    }
+
+    /// Returns the bits shifted right so that the padding is at the start of the first byte
+    /// rather than at the end of the last one.
+    pub fn right_align(&self) -> Vec<u8> {
+        let shift = (8 - self.bit_length % 8) % 8;
+        if shift == 0 || self.bytes.is_empty() {
+            return self.bytes.clone();
+        }
+
+        let mut out = Vec::with_capacity(self.bytes.len());
+        out.push(self.bytes[0] >> shift);
+        for w in self.bytes.windows(2) {
+            out.push((w[0] << (8 - shift)) | (w[1] >> shift));
+        }
+        out
+    }
+}
+
+impl Encode for BitString {
+    fn tag(&self) -> Tag {
+        Tag::BIT_STRING
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        if (self.bit_length + 7) / 8 != self.bytes.len() {
+            return Err(Error::Structural(
+                "bit length doesn't match bytes".to_string(),
+            ));
+        }
+
+        let padding = (8 - self.bit_length % 8) % 8;
+        out.push(padding as u8);
+        out.extend_from_slice(&self.bytes);
+        if let Some(last) = out.last_mut().filter(|_| padding > 0) {
+            *last &= 0xff << padding;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for BitString {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::BIT_STRING
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        let (padding, bytes) = match p.content() {
+            [padding, bytes @ ..] => (*padding as usize, bytes),
+            [] => return Err(Error::Syntax("zero length BIT STRING")),
+        };
+        if padding > 7
+            || (bytes.is_empty() && padding > 0)
+            || matches!(bytes.last(), Some(&b) if b & ((1 << padding) - 1) != 0)
+        {
+            return Err(Error::Syntax("invalid padding bits in BIT STRING"));
+        }
+
+        Ok(Self {
+            bytes: bytes.to_vec(),
+            bit_length: bytes.len() * 8 - padding,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
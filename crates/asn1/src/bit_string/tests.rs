@@ -0,0 +1,78 @@
+use crate::{BitString, Error};
+
+#[test]
+fn marshal() {
+    let test_vector: Vec<(BitString, &[u8])> = vec![
+        (BitString::default(), &[0x03, 0x01, 0x00]),
+        (BitString::new(vec![0xaa]), &[0x03, 0x02, 0x00, 0xaa]),
+        (
+            BitString {
+                bytes: vec![0xab, 0xcf],
+                bit_length: 12,
+            },
+            &[0x03, 0x03, 0x04, 0xab, 0xc0],
+        ),
+    ];
+
+    for (i, (v, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(expect, crate::marshal(&v).unwrap(), "#{}", i);
+    }
+
+    let v = BitString {
+        bytes: vec![0xab],
+        bit_length: 12,
+    };
+    assert!(matches!(crate::marshal(&v), Err(Error::Structural(_))));
+}
+
+#[test]
+fn unmarshal() {
+    let (v, _) = crate::unmarshal::<BitString>(&[0x03, 0x03, 0x04, 0xab, 0xc0]).unwrap();
+    assert_eq!(12, v.bit_length);
+    assert_eq!(vec![0xab, 0xc0], v.bytes);
+
+    let invalid: Vec<&[u8]> = vec![
+        &[0x03, 0x01, 0x01],
+        &[0x03, 0x02, 0x08, 0x00],
+        &[0x03, 0x02, 0x01, 0x01],
+    ];
+    for (i, data) in invalid.into_iter().enumerate() {
+        let err = crate::unmarshal::<BitString>(data).unwrap_err();
+        assert_eq!(
+            Error::Syntax("invalid padding bits in BIT STRING"),
+            err,
+            "#{}",
+            i
+        );
+    }
+    assert_eq!(
+        Error::Syntax("zero length BIT STRING"),
+        crate::unmarshal::<BitString>(&[0x03, 0x00]).unwrap_err()
+    );
+}
+
+#[test]
+fn at() {
+    let v = BitString {
+        bytes: vec![0x82, 0x40],
+        bit_length: 10,
+    };
+
+    let bits: Vec<bool> = (0..12).map(|i| v.at(i)).collect();
+    let expect = [
+        true, false, false, false, false, false, true, false, false, true, false, false,
+    ];
+    assert_eq!(expect.to_vec(), bits);
+}
+
+#[test]
+fn right_align() {
+    let v = BitString {
+        bytes: vec![0xab, 0xc0],
+        bit_length: 12,
+    };
+    assert_eq!(vec![0x0a, 0xbc], v.right_align());
+
+    let v = BitString::new(vec![0x12, 0x34]);
+    assert_eq!(vec![0x12, 0x34], v.right_align());
+}
@@ -0,0 +1,158 @@
+use crate::{Decode, Encode, Error, Parser, Tag};
+
+/// `SequenceOf` is an ASN.1 SEQUENCE OF, elements of the same type in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SequenceOf<T>(pub Vec<T>);
+
+/// `SetOf` is an ASN.1 SET OF, elements of the same type which DER sorts by their encodings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SetOf<T>(pub Vec<T>);
+
+/// `Explicit` tags `T` explicitly with the context-specific tag `N`: the element of `T` is
+/// wrapped into a constructed element with tag `N`.
+///
+/// # Example
+/// ```
+/// use asn1::Explicit;
+///
+/// let der = asn1::marshal(&Explicit::<_, 0>(2u8)).unwrap();
+/// assert_eq!(vec![0xa0, 0x03, 0x02, 0x01, 0x02], der);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Explicit<T, const N: u32>(pub T);
+
+/// `Implicit` tags `T` implicitly with the context-specific tag `N`: the tag of `T` is
+/// replaced by `N`.
+///
+/// # Example
+/// ```
+/// use asn1::Implicit;
+///
+/// let der = asn1::marshal(&Implicit::<_, 1>(2u8)).unwrap();
+/// assert_eq!(vec![0x81, 0x01, 0x02], der);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Implicit<T, const N: u32>(pub T);
+
+impl<T> Encode for SequenceOf<T>
+where
+    T: Encode,
+{
+    fn tag(&self) -> Tag {
+        Tag::SEQUENCE
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        for v in self.0.iter() {
+            v.encode(out)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Decode for SequenceOf<T>
+where
+    T: Decode,
+{
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::SEQUENCE
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        let mut out = vec![];
+        while !p.is_empty() {
+            out.push(p.read()?);
+        }
+        Ok(Self(out))
+    }
+}
+
+impl<T> Encode for SetOf<T>
+where
+    T: Encode,
+{
+    fn tag(&self) -> Tag {
+        Tag::SET
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let mut elems = self
+            .0
+            .iter()
+            .map(crate::marshal)
+            .collect::<Result<Vec<_>, _>>()?;
+        elems.sort();
+        for v in elems {
+            out.extend(v);
+        }
+        Ok(())
+    }
+}
+
+impl<T> Decode for SetOf<T>
+where
+    T: Decode,
+{
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::SET
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        SequenceOf::decode_content(p).map(|v| Self(v.0))
+    }
+}
+
+impl<T, const N: u32> Encode for Explicit<T, N>
+where
+    T: Encode,
+{
+    fn tag(&self) -> Tag {
+        Tag::context_specific(N, true)
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.encode(out)
+    }
+}
+
+impl<T, const N: u32> Decode for Explicit<T, N>
+where
+    T: Decode,
+{
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::context_specific(N, true)
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        p.read().map(Self)
+    }
+}
+
+impl<T, const N: u32> Encode for Implicit<T, N>
+where
+    T: Encode,
+{
+    fn tag(&self) -> Tag {
+        Tag::context_specific(N, self.0.tag().constructed)
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.encode_content(out)
+    }
+}
+
+impl<T, const N: u32> Decode for Implicit<T, N>
+where
+    T: Decode,
+{
+    fn matches(tag: Tag) -> bool {
+        tag.is(Tag::context_specific(N, false))
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        T::decode_content(p).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests;
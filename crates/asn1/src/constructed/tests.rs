@@ -0,0 +1,46 @@
+use crate::{Error, Explicit, Implicit, SequenceOf, SetOf};
+
+#[test]
+fn sequence_of() {
+    let v = SequenceOf(vec![3u8, 1, 2]);
+    let der = crate::marshal(&v).unwrap();
+    assert_eq!(
+        vec![0x30, 0x09, 0x02, 0x01, 0x03, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02],
+        der
+    );
+    assert_eq!(v, crate::unmarshal(&der).unwrap().0);
+}
+
+#[test]
+fn set_of_is_sorted() {
+    let v = SetOf(vec![256u16, 3, 1]);
+    let der = crate::marshal(&v).unwrap();
+    assert_eq!(
+        vec![0x31, 0x0a, 0x02, 0x01, 0x01, 0x02, 0x01, 0x03, 0x02, 0x02, 0x01, 0x00],
+        der
+    );
+    assert_eq!(SetOf(vec![1u16, 3, 256]), crate::unmarshal(&der).unwrap().0);
+}
+
+#[test]
+fn explicit() {
+    let der = [0xa2, 0x05, 0x30, 0x03, 0x02, 0x01, 0x07];
+    let (v, _) = crate::unmarshal::<Explicit<SequenceOf<u8>, 2>>(&der).unwrap();
+    assert_eq!(vec![7], v.0 .0);
+    assert_eq!(der.to_vec(), crate::marshal(&v).unwrap());
+
+    let err = crate::unmarshal::<Explicit<SequenceOf<u8>, 1>>(&der).unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+}
+
+#[test]
+fn implicit() {
+    let der = [0xa2, 0x03, 0x02, 0x01, 0x07];
+    let (v, _) = crate::unmarshal::<Implicit<SequenceOf<u8>, 2>>(&der).unwrap();
+    assert_eq!(vec![7], v.0 .0);
+    assert_eq!(der.to_vec(), crate::marshal(&v).unwrap());
+
+    let (v, _) = crate::unmarshal::<Implicit<u8, 0>>(&[0x80, 0x01, 0x07]).unwrap();
+    assert_eq!(7, v.0);
+    assert_eq!(vec![0x80, 0x01, 0x07], crate::marshal(&v).unwrap());
+}
@@ -0,0 +1,11 @@
+/// Possible errors during marshaling and unmarshaling.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The data isn't valid ASN.1, or not valid DER when DER is required.
+    #[error("asn1: syntax error: {0}")]
+    Syntax(&'static str),
+    /// The data is valid ASN.1 but doesn't fit the Rust type, or a Rust value can't be
+    /// marshaled.
+    #[error("asn1: structure error: {0}")]
+    Structural(String),
+}
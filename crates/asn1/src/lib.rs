@@ -0,0 +1,57 @@
+//! Implementation of ASN.1 data structures encoded in DER or BER, as defined in ITU-T
+//! Rec X.690, after Go's `encoding/asn1`.
+//!
+//! Rust values map to ASN.1 elements through the [Encode] and [Decode] traits, which are
+//! implemented for
+//! - BOOLEAN: [bool];
+//! - INTEGER: integer primitives and [BigInt];
+//! - ENUMERATED: [Enumerated];
+//! - BIT STRING: [BitString];
+//! - OCTET STRING: `Vec<u8>`;
+//! - NULL: [Null];
+//! - OBJECT IDENTIFIER: [ObjectIdentifier];
+//! - UTCTime and GeneralizedTime: [DateTime], [UtcTime] and [GeneralizedTime];
+//! - string types: [String], [PrintableString], [Ia5String], [NumericString] and
+//!   [Utf8String];
+//! - SEQUENCE OF and SET OF: [SequenceOf] and [SetOf];
+//! - explicitly and implicitly tagged values: [Explicit] and [Implicit];
+//! - elements of any type: [RawValue].
+//!
+//! Structs implement them by hand to map to SEQUENCEs, encoding and parsing fields in order.
+//!
+//! See also [A Layman's Guide to a Subset of ASN.1, BER, and DER](
+//! http://luca.ntop.org/Teaching/Appunti/asn1.html).
+//!
+//! # Example
+//! ```
+#![doc = include_str!("../examples/algorithm_identifier.rs")]
+//! ```
+
+#![allow(clippy::needless_doctest_main)]
+
+mod bit_string;
+mod constructed;
+mod errors;
+mod marshal;
+mod oid;
+mod parser;
+mod primitive;
+mod string;
+mod tag;
+mod time;
+
+pub use bit_string::*;
+pub use constructed::*;
+pub use errors::*;
+pub use marshal::*;
+pub use oid::*;
+pub use parser::*;
+pub use primitive::*;
+pub use string::*;
+pub use tag::{
+    Class, Tag, TAG_BIT_STRING, TAG_BMP_STRING, TAG_BOOLEAN, TAG_ENUM, TAG_GENERALIZED_TIME,
+    TAG_GENERAL_STRING, TAG_IA5_STRING, TAG_INTEGER, TAG_NULL, TAG_NUMERIC_STRING,
+    TAG_OCTET_STRING, TAG_OID, TAG_PRINTABLE_STRING, TAG_SEQUENCE, TAG_SET, TAG_T61_STRING,
+    TAG_UTC_TIME, TAG_UTF8_STRING,
+};
+pub use time::*;
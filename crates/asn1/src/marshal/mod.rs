@@ -0,0 +1,86 @@
+use crate::tag::Header;
+use crate::{Error, Tag};
+
+/// An `Encode` type knows how to serialize itself as a DER encoded ASN.1 element.
+///
+/// Structs map to SEQUENCEs by encoding their fields in order into the contents. OPTIONAL
+/// fields are left out when absent.
+///
+/// # Example
+/// ```
+/// use asn1::{Encode, Error, Tag};
+///
+/// struct Point {
+///     x: i64,
+///     y: Option<i64>,
+/// }
+///
+/// impl Encode for Point {
+///     fn tag(&self) -> Tag {
+///         Tag::SEQUENCE
+///     }
+///
+///     fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+///         self.x.encode(out)?;
+///         if let Some(y) = &self.y {
+///             y.encode(out)?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let got = asn1::marshal(&Point { x: 5, y: None }).unwrap();
+/// assert_eq!(vec![0x30, 0x03, 0x02, 0x01, 0x05], got);
+/// ```
+pub trait Encode {
+    /// Returns the tag of the element.
+    fn tag(&self) -> Tag;
+
+    /// Appends the contents of the element to `out`.
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error>;
+
+    /// Appends the whole element to `out`: its header, then its contents.
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let mut content = vec![];
+        self.encode_content(&mut content)?;
+        Header::write(out, self.tag(), content.len());
+        out.extend_from_slice(&content);
+        Ok(())
+    }
+}
+
+/// Returns the DER encoding of `v`.
+pub fn marshal<T>(v: &T) -> Result<Vec<u8>, Error>
+where
+    T: Encode + ?Sized,
+{
+    let mut out = vec![];
+    v.encode(&mut out)?;
+    Ok(out)
+}
+
+impl<T> Encode for &T
+where
+    T: Encode + ?Sized,
+{
+    fn tag(&self) -> Tag {
+        (**self).tag()
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        (**self).encode_content(out)
+    }
+}
+
+impl<T> Encode for Box<T>
+where
+    T: Encode + ?Sized,
+{
+    fn tag(&self) -> Tag {
+        (**self).tag()
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        (**self).encode_content(out)
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::tag::write_base128;
+use crate::{Decode, Encode, Error, Parser, Tag};
+
+/// `ObjectIdentifier` is an ASN.1 OBJECT IDENTIFIER, a sequence of non-negative arcs.
+///
+/// # Example
+/// ```
+/// use asn1::ObjectIdentifier;
+///
+/// let oid: ObjectIdentifier = "1.2.840.113549.1.1.11".parse().unwrap();
+/// assert_eq!(&[1, 2, 840, 113549, 1, 1, 11], oid.arcs());
+///
+/// let der = asn1::marshal(&oid).unwrap();
+/// assert_eq!(b"\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b", der.as_slice());
+/// assert_eq!(oid, asn1::unmarshal(&der).unwrap().0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectIdentifier(Vec<u64>);
+
+impl ObjectIdentifier {
+    /// Returns the identifier made of `arcs`, failing if there are less than 2 of them or the
+    /// first 2 can't be encoded.
+    pub fn new(arcs: &[u64]) -> Result<Self, Error> {
+        match arcs {
+            [first, second, ..] if *first < 2 && *second < 40 => {}
+            [2, second, ..] if *second <= u64::MAX - 80 => {}
+            _ => return Err(Error::Structural("invalid object identifier".to_string())),
+        }
+        Ok(Self(arcs.to_vec()))
+    }
+
+    /// Returns the arcs of the identifier.
+    pub fn arcs(&self) -> &[u64] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ObjectIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, v) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{}", v)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ObjectIdentifier {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let arcs = s
+            .split('.')
+            .map(|v| v.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::Structural(format!("invalid object identifier '{}'", s)))?;
+        Self::new(&arcs)
+    }
+}
+
+impl Encode for ObjectIdentifier {
+    fn tag(&self) -> Tag {
+        Tag::OID
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        if Self::new(&self.0).is_err() {
+            return Err(Error::Structural("invalid object identifier".to_string()));
+        }
+
+        write_base128(out, self.0[0] * 40 + self.0[1]);
+        for &v in self.0[2..].iter() {
+            write_base128(out, v);
+        }
+        Ok(())
+    }
+}
+
+impl Decode for ObjectIdentifier {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::OID
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        let mut b = p.content();
+        if b.is_empty() {
+            return Err(Error::Syntax("zero length OBJECT IDENTIFIER"));
+        }
+
+        let mut arcs = Vec::with_capacity(b.len() + 1);
+        while !b.is_empty() {
+            let (v, rest) = parse_base128(b)?;
+            if arcs.is_empty() {
+                // The first byte packs the first two arcs as 40 * first + second.
+                let first = (v / 40).min(2);
+                arcs.push(first);
+                arcs.push(v - first * 40);
+            } else {
+                arcs.push(v);
+            }
+            b = rest;
+        }
+
+        Ok(Self(arcs))
+    }
+}
+
+fn parse_base128(b: &[u8]) -> Result<(u64, &[u8]), Error> {
+    let mut v = 0u64;
+    for (i, &c) in b.iter().enumerate() {
+        if i == 0 && c == 0x80 {
+            return Err(Error::Syntax("integer is not minimally encoded"));
+        }
+        if v >> 57 != 0 {
+            return Err(Error::Structural("base 128 integer too large".to_string()));
+        }
+        v = (v << 7) | (c & 0x7f) as u64;
+        if c & 0x80 == 0 {
+            return Ok((v, &b[i + 1..]));
+        }
+    }
+
+    Err(Error::Syntax("truncated base 128 integer"))
+}
+
+#[cfg(test)]
+mod tests;
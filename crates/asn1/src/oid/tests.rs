@@ -0,0 +1,57 @@
+use crate::{Error, ObjectIdentifier};
+
+#[test]
+fn marshal_unmarshal() {
+    let test_vector: Vec<(&str, &[u8])> = vec![
+        ("1.2.840.10045.2.1", b"\x06\x07\x2a\x86\x48\xce\x3d\x02\x01"),
+        ("2.5.4.3", b"\x06\x03\x55\x04\x03"),
+        ("0.39", b"\x06\x01\x27"),
+        ("2.999.3", b"\x06\x03\x88\x37\x03"),
+        (
+            "1.2.18446744073709551615",
+            b"\x06\x0b\x2a\x81\xff\xff\xff\xff\xff\xff\xff\xff\x7f",
+        ),
+    ];
+
+    for (i, (s, expect)) in test_vector.into_iter().enumerate() {
+        let oid: ObjectIdentifier = s.parse().unwrap();
+        assert_eq!(s, oid.to_string(), "#{} display", i);
+        assert_eq!(expect, crate::marshal(&oid).unwrap(), "#{} marshal", i);
+        assert_eq!(oid, crate::unmarshal(expect).unwrap().0, "#{} unmarshal", i);
+    }
+}
+
+#[test]
+fn new_invalid() {
+    let invalid: Vec<&[u64]> = vec![&[], &[1], &[1, 40], &[3, 1]];
+    for (i, arcs) in invalid.into_iter().enumerate() {
+        assert!(ObjectIdentifier::new(arcs).is_err(), "#{}", i);
+    }
+
+    assert!("1.2.x".parse::<ObjectIdentifier>().is_err());
+    assert!("".parse::<ObjectIdentifier>().is_err());
+}
+
+#[test]
+fn unmarshal_invalid() {
+    let test_vector: Vec<(&[u8], Error)> = vec![
+        (b"\x06\x00", Error::Syntax("zero length OBJECT IDENTIFIER")),
+        (
+            b"\x06\x02\x2a\x86",
+            Error::Syntax("truncated base 128 integer"),
+        ),
+        (
+            b"\x06\x03\x2a\x80\x01",
+            Error::Syntax("integer is not minimally encoded"),
+        ),
+        (
+            b"\x06\x0b\x2a\x82\x80\x80\x80\x80\x80\x80\x80\x80\x00",
+            Error::Structural("base 128 integer too large".to_string()),
+        ),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        let err = crate::unmarshal::<ObjectIdentifier>(data).unwrap_err();
+        assert_eq!(expect, err, "#{}", i);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::tag::Header;
+use crate::{Error, Tag};
+
+/// Maximum number of elements nested into one another, which bounds the recursion of parsing
+/// and the work of finding where indefinite lengths end.
+const MAX_NESTING_DEPTH: usize = 100;
+
+/// A `Decode` type knows how to parse itself from the contents of an ASN.1 element.
+///
+/// Structs map to SEQUENCEs by reading their fields in order from the [Parser] given to
+/// [Decode::decode_content]. Elements left after the last field are ignored, since X.509 has
+/// grown by appending fields.
+///
+/// # Example
+/// ```
+/// use asn1::{Decode, Error, Parser, Tag};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Point {
+///     x: i64,
+///     y: Option<i64>,
+/// }
+///
+/// impl Decode for Point {
+///     fn matches(tag: Tag) -> bool {
+///         tag == Tag::SEQUENCE
+///     }
+///
+///     fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+///         Ok(Self {
+///             x: p.read()?,
+///             y: p.read_optional()?,
+///         })
+///     }
+/// }
+///
+/// let (p, rest) = asn1::unmarshal::<Point>(&[0x30, 0x03, 0x02, 0x01, 0x05]).unwrap();
+/// assert_eq!(Point { x: 5, y: None }, p);
+/// assert!(rest.is_empty());
+/// ```
+pub trait Decode: Sized {
+    /// Tells whether an element tagged `tag` can be decoded as `Self`, which decides whether
+    /// an OPTIONAL field is present.
+    fn matches(tag: Tag) -> bool;
+
+    /// Parses `Self` from the contents of an element, which `p` iterates over.
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error>;
+}
+
+/// `Parser` reads ASN.1 elements in sequence from the contents of a constructed element or
+/// from the top level of some data.
+///
+/// It enforces DER unless created by [Parser::new_ber], in which case indefinite lengths,
+/// non-minimal encodings and constructed strings are accepted too. Either way, elements may
+/// nest at most 100 levels deep.
+#[derive(Clone, Debug)]
+pub struct Parser<'a> {
+    data: &'a [u8],
+    ber: bool,
+    tag: Option<Tag>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Returns a parser reading DER encoded elements from `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            ber: false,
+            tag: None,
+            depth: 0,
+        }
+    }
+
+    /// Returns a parser reading BER encoded elements from `data`.
+    pub fn new_ber(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            ber: true,
+            tag: None,
+            depth: 0,
+        }
+    }
+
+    /// Tells whether BER is accepted rather than DER only.
+    pub fn is_ber(&self) -> bool {
+        self.ber
+    }
+
+    /// Tells whether all data has been read.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the data not read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the tag of the element whose contents are parsed, or `None` at the top level.
+    pub fn tag(&self) -> Option<Tag> {
+        self.tag
+    }
+
+    /// Returns the tag of the next element without reading it.
+    pub fn peek_tag(&self) -> Option<Tag> {
+        Header::parse(self.data, self.ber).ok().map(|(h, _)| h.tag)
+    }
+
+    /// Takes all data not read yet, which is how primitive values get their contents.
+    pub fn content(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.data)
+    }
+
+    /// Reads the next element as `T`. Nothing is consumed on failure.
+    pub fn read<T>(&mut self) -> Result<T, Error>
+    where
+        T: Decode,
+    {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(nested_too_deeply());
+        }
+
+        let (tag, content, rest) = self.next_element()?;
+        if !T::matches(tag) {
+            return Err(Error::Structural(format!(
+                "unexpected tag {} for {}",
+                tag,
+                std::any::type_name::<T>()
+            )));
+        }
+
+        let v = T::decode_content(&mut Parser {
+            data: content,
+            ber: self.ber,
+            tag: Some(tag),
+            depth: self.depth + 1,
+        })?;
+        self.data = rest;
+
+        Ok(v)
+    }
+
+    /// Reads the next element as `T` if its tag matches, which is how OPTIONAL and DEFAULT
+    /// fields are parsed.
+    pub fn read_optional<T>(&mut self) -> Result<Option<T>, Error>
+    where
+        T: Decode,
+    {
+        match self.peek_tag() {
+            Some(tag) if T::matches(tag) => self.read().map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Splits off the next element into its tag, contents and the data after it.
+    fn next_element(&self) -> Result<(Tag, &'a [u8], &'a [u8]), Error> {
+        let (header, n) = Header::parse(self.data, self.ber)?;
+        let data = &self.data[n..];
+        match header.len {
+            Some(len) if len <= data.len() => Ok((header.tag, &data[..len], &data[len..])),
+            Some(_) => Err(Error::Syntax("data truncated")),
+            None => {
+                let len = indefinite_len(data, MAX_NESTING_DEPTH - self.depth)?;
+                Ok((header.tag, &data[..len], &data[len + 2..]))
+            }
+        }
+    }
+}
+
+/// Returns the length of contents of indefinite length, which end right before the first
+/// end-of-contents marker at their level, with at most `max_depth` levels of indefinite
+/// lengths nested in them.
+///
+/// The elements are skipped in a single pass, keeping track of the indefinite lengths open.
+fn indefinite_len(data: &[u8], max_depth: usize) -> Result<usize, Error> {
+    let (mut pos, mut depth) = (0, 0);
+    loop {
+        let rest = &data[pos..];
+        if rest.starts_with(&[0, 0]) {
+            if depth == 0 {
+                return Ok(pos);
+            }
+            depth -= 1;
+            pos += 2;
+            continue;
+        }
+        if rest.is_empty() {
+            return Err(Error::Syntax("missing end-of-contents"));
+        }
+
+        let (header, n) = Header::parse(rest, true)?;
+        pos += n;
+        match header.len {
+            Some(len) if len <= data.len() - pos => pos += len,
+            Some(_) => return Err(Error::Syntax("data truncated")),
+            None if depth + 1 >= max_depth => return Err(nested_too_deeply()),
+            None => depth += 1,
+        }
+    }
+}
+
+fn nested_too_deeply() -> Error {
+    Error::Structural(format!(
+        "elements nested more than {} levels deep",
+        MAX_NESTING_DEPTH
+    ))
+}
+
+/// Parses a DER encoded `T` from `data`, returning it along with the data after it.
+pub fn unmarshal<T>(data: &[u8]) -> Result<(T, &[u8]), Error>
+where
+    T: Decode,
+{
+    let mut p = Parser::new(data);
+    p.read().map(|v| (v, p.remaining()))
+}
+
+/// Parses a BER encoded `T` from `data`, returning it along with the data after it.
+pub fn unmarshal_ber<T>(data: &[u8]) -> Result<(T, &[u8]), Error>
+where
+    T: Decode,
+{
+    let mut p = Parser::new_ber(data);
+    p.read().map(|v| (v, p.remaining()))
+}
+
+/// Parses the contents of a PEM block, which must be exactly one DER encoded `T`.
+///
+/// The type of the block isn't checked.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/parse_pem.rs")]
+/// ```
+pub fn parse_pem<T>(block: &pem::Block) -> Result<T, Error>
+where
+    T: Decode,
+{
+    match unmarshal(&block.bytes)? {
+        (v, []) => Ok(v),
+        _ => Err(Error::Syntax("trailing data")),
+    }
+}
+
+#[cfg(test)]
+mod tests;
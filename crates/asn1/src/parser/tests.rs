@@ -0,0 +1,78 @@
+use crate::{Error, Parser, RawValue, SequenceOf, Tag};
+
+#[test]
+fn read_optional() {
+    let data = [0x02, 0x01, 0x05, 0x01, 0x01, 0xff];
+    let mut p = Parser::new(&data);
+
+    assert_eq!(None, p.read_optional::<bool>().unwrap());
+    assert_eq!(Some(Tag::INTEGER), p.peek_tag());
+    assert_eq!(Some(5u8), p.read_optional().unwrap());
+    assert_eq!(Some(true), p.read_optional().unwrap());
+    assert!(p.is_empty());
+    assert_eq!(None, p.read_optional::<bool>().unwrap());
+}
+
+#[test]
+fn read_mismatch_consumes_nothing() {
+    let data = [0x02, 0x01, 0x05];
+    let mut p = Parser::new(&data);
+
+    let err = p.read::<bool>().unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+    assert_eq!(&data, p.remaining());
+}
+
+#[test]
+fn truncated() {
+    let err = crate::unmarshal::<u8>(&[0x02, 0x02, 0x05]).unwrap_err();
+    assert_eq!(Error::Syntax("data truncated"), err);
+}
+
+#[test]
+fn unmarshal_returns_rest() {
+    let (v, rest) = crate::unmarshal::<u8>(&[0x02, 0x01, 0x05, 0xaa]).unwrap();
+    assert_eq!(5, v);
+    assert_eq!(&[0xaa], rest);
+}
+
+#[test]
+fn indefinite_length() {
+    // SEQUENCE (indefinite) { INTEGER 1, SEQUENCE (indefinite) { INTEGER 2 } } and a trailer.
+    let data = [
+        0x30, 0x80, 0x02, 0x01, 0x01, 0x30, 0x80, 0x02, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0xaa,
+    ];
+
+    let (v, rest) = crate::unmarshal_ber::<SequenceOf<RawValue>>(&data).unwrap();
+    assert_eq!(&[0xaa], rest);
+    assert_eq!(2, v.0.len());
+    assert_eq!(vec![0x01], v.0[0].content);
+    assert_eq!(Tag::SEQUENCE, v.0[1].tag);
+    assert_eq!(vec![0x02, 0x01, 0x02], v.0[1].content);
+
+    let err = crate::unmarshal::<SequenceOf<RawValue>>(&data).unwrap_err();
+    assert_eq!(Error::Syntax("indefinite length found (not DER)"), err);
+
+    let err = crate::unmarshal_ber::<SequenceOf<RawValue>>(&data[..12]).unwrap_err();
+    assert_eq!(Error::Syntax("missing end-of-contents"), err);
+}
+
+#[test]
+fn indefinite_length_nested_too_deeply() {
+    // Enough nested SEQUENCEs of indefinite length to overflow the stack if recursed into.
+    let mut data = [0x30, 0x80].repeat(200_000);
+    data.extend_from_slice(&[0x00, 0x00].repeat(200_000));
+
+    let err = crate::unmarshal_ber::<RawValue>(&data).unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+    let err = crate::unmarshal_ber::<SequenceOf<RawValue>>(&data).unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+
+    // Within the limit.
+    let n = super::MAX_NESTING_DEPTH - 1;
+    let mut data = [0x30, 0x80].repeat(n);
+    data.extend_from_slice(&[0x00, 0x00].repeat(n));
+    let (v, rest) = crate::unmarshal_ber::<RawValue>(&data).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(data.len() - 4, v.content.len());
+}
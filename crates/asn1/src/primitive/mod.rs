@@ -0,0 +1,308 @@
+use crate::{Decode, Encode, Error, Parser, Tag};
+
+/// `Null` is the ASN.1 NULL value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Null;
+
+/// `Enumerated` is an ASN.1 ENUMERATED value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Enumerated(pub i64);
+
+/// `BigInt` is an ASN.1 INTEGER of any size, as used for RSA moduli or certificate serial
+/// numbers, kept as its minimal big-endian two's complement bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigInt(Vec<u8>);
+
+/// `RawValue` is an element of any type left undecoded.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawValue {
+    pub tag: Tag,
+    pub content: Vec<u8>,
+}
+
+impl BigInt {
+    /// Returns the integer of big-endian two's complement bytes `b`, with an empty slice
+    /// meaning zero.
+    pub fn from_signed_bytes_be(b: &[u8]) -> Self {
+        let mut v = if b.is_empty() { vec![0] } else { b.to_vec() };
+        let n = redundant_prefix_len(&v);
+        v.drain(..n);
+        Self(v)
+    }
+
+    /// Returns the non-negative integer of big-endian bytes `b`.
+    pub fn from_unsigned_bytes_be(b: &[u8]) -> Self {
+        let mut v = Vec::with_capacity(b.len() + 1);
+        v.push(0);
+        v.extend_from_slice(b);
+        Self::from_signed_bytes_be(&v)
+    }
+
+    /// Returns the minimal big-endian two's complement bytes of the integer.
+    pub fn signed_bytes_be(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the minimal big-endian bytes of the integer, or `None` if it's negative.
+    pub fn unsigned_bytes_be(&self) -> Option<&[u8]> {
+        match self.0.as_slice() {
+            _ if self.is_negative() => None,
+            [0, rest @ ..] if !rest.is_empty() => Some(rest),
+            v => Some(v),
+        }
+    }
+
+    /// Tells whether the integer is negative.
+    pub fn is_negative(&self) -> bool {
+        self.0[0] & 0x80 != 0
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(v: i64) -> Self {
+        Self::from_signed_bytes_be(&v.to_be_bytes())
+    }
+}
+
+impl Encode for bool {
+    fn tag(&self) -> Tag {
+        Tag::BOOLEAN
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.push(if *self { 0xff } else { 0x00 });
+        Ok(())
+    }
+}
+
+impl Decode for bool {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::BOOLEAN
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        let ber = p.is_ber();
+        match p.content() {
+            [0x00] => Ok(false),
+            [0xff] => Ok(true),
+            [_] if ber => Ok(true),
+            _ => Err(Error::Syntax("invalid boolean")),
+        }
+    }
+}
+
+impl Encode for Null {
+    fn tag(&self) -> Tag {
+        Tag::NULL
+    }
+
+    fn encode_content(&self, _out: &mut Vec<u8>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl Decode for Null {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::NULL
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        match p.content() {
+            [] => Ok(Self),
+            _ => Err(Error::Syntax("invalid NULL")),
+        }
+    }
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),+) => {
+        $(
+            impl Encode for $t {
+                fn tag(&self) -> Tag {
+                    Tag::INTEGER
+                }
+
+                fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+                    #[allow(unused_comparisons)]
+                    let negative = *self < 0;
+                    let mut v = Vec::with_capacity(17);
+                    if negative {
+                        v.push(0xff);
+                        v.extend_from_slice(&(*self as i128).to_be_bytes());
+                    } else {
+                        v.push(0);
+                        v.extend_from_slice(&(*self as u128).to_be_bytes());
+                    }
+                    let n = redundant_prefix_len(&v);
+                    out.extend_from_slice(&v[n..]);
+                    Ok(())
+                }
+            }
+
+            impl Decode for $t {
+                fn matches(tag: Tag) -> bool {
+                    tag == Tag::INTEGER
+                }
+
+                fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+                    let b = check_integer(p.content())?;
+                    let too_large = || {
+                        Error::Structural(format!("integer too large for {}", stringify!($t)))
+                    };
+                    if b[0] & 0x80 != 0 {
+                        if b.len() > 16 {
+                            return Err(too_large());
+                        }
+                        let mut word = [0xffu8; 16];
+                        word[16 - b.len()..].copy_from_slice(b);
+                        <$t>::try_from(i128::from_be_bytes(word)).map_err(|_| too_large())
+                    } else {
+                        let b = b.strip_prefix(&[0]).unwrap_or(b);
+                        if b.len() > 16 {
+                            return Err(too_large());
+                        }
+                        let mut word = [0u8; 16];
+                        word[16 - b.len()..].copy_from_slice(b);
+                        <$t>::try_from(u128::from_be_bytes(word)).map_err(|_| too_large())
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Encode for BigInt {
+    fn tag(&self) -> Tag {
+        Tag::INTEGER
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.extend_from_slice(&self.0);
+        Ok(())
+    }
+}
+
+impl Decode for BigInt {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::INTEGER
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        check_integer(p.content()).map(|b| Self(b.to_vec()))
+    }
+}
+
+impl Encode for Enumerated {
+    fn tag(&self) -> Tag {
+        Tag::ENUM
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.encode_content(out)
+    }
+}
+
+impl Decode for Enumerated {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::ENUM
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        i64::decode_content(p).map(Self)
+    }
+}
+
+/// Byte slices are OCTET STRINGs.
+impl Encode for [u8] {
+    fn tag(&self) -> Tag {
+        Tag::OCTET_STRING
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.extend_from_slice(self);
+        Ok(())
+    }
+}
+
+impl Encode for Vec<u8> {
+    fn tag(&self) -> Tag {
+        Tag::OCTET_STRING
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.as_slice().encode_content(out)
+    }
+}
+
+/// Constructed OCTET STRINGs, the concatenation of nested ones, are accepted in BER.
+impl Decode for Vec<u8> {
+    fn matches(tag: Tag) -> bool {
+        tag.is(Tag::OCTET_STRING)
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        if !matches!(p.tag(), Some(t) if t.constructed) {
+            return Ok(p.content().to_vec());
+        }
+        if !p.is_ber() {
+            return Err(Error::Syntax("constructed string (not DER)"));
+        }
+
+        let mut out = vec![];
+        while !p.is_empty() {
+            out.extend(p.read::<Vec<u8>>()?);
+        }
+        Ok(out)
+    }
+}
+
+impl Encode for RawValue {
+    fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.extend_from_slice(&self.content);
+        Ok(())
+    }
+}
+
+impl Decode for RawValue {
+    fn matches(_tag: Tag) -> bool {
+        true
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        let tag = p.tag().expect("tag of contents");
+        Ok(Self {
+            tag,
+            content: p.content().to_vec(),
+        })
+    }
+}
+
+/// Checks the contents of an INTEGER is non-empty and minimally encoded.
+fn check_integer(b: &[u8]) -> Result<&[u8], Error> {
+    if b.is_empty() {
+        return Err(Error::Structural("empty integer".to_string()));
+    }
+    if redundant_prefix_len(b) > 0 {
+        return Err(Error::Structural(
+            "integer not minimally-encoded".to_string(),
+        ));
+    }
+    Ok(b)
+}
+
+/// Returns the number of leading bytes of a two's complement integer that can be dropped
+/// without changing its value.
+fn redundant_prefix_len(b: &[u8]) -> usize {
+    b.windows(2)
+        .take_while(|w| (w[0] == 0x00 && w[1] & 0x80 == 0) || (w[0] == 0xff && w[1] & 0x80 != 0))
+        .count()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,158 @@
+use crate::{BigInt, Enumerated, Error, Null, RawValue, Tag};
+
+#[test]
+fn integers() {
+    let test_vector: Vec<(i64, &[u8])> = vec![
+        (0, &[0x02, 0x01, 0x00]),
+        (1, &[0x02, 0x01, 0x01]),
+        (127, &[0x02, 0x01, 0x7f]),
+        (128, &[0x02, 0x02, 0x00, 0x80]),
+        (256, &[0x02, 0x02, 0x01, 0x00]),
+        (-1, &[0x02, 0x01, 0xff]),
+        (-128, &[0x02, 0x01, 0x80]),
+        (-129, &[0x02, 0x02, 0xff, 0x7f]),
+        (
+            i64::MIN,
+            &[0x02, 0x08, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+    ];
+
+    for (i, (v, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(expect, crate::marshal(&v).unwrap(), "#{} marshal", i);
+        assert_eq!(v, crate::unmarshal::<i64>(expect).unwrap().0, "#{}", i);
+        assert_eq!(
+            BigInt::from(v),
+            crate::unmarshal::<BigInt>(expect).unwrap().0,
+            "#{} BigInt",
+            i
+        );
+    }
+}
+
+#[test]
+fn unsigned_integers() {
+    let der = crate::marshal(&u64::MAX).unwrap();
+    assert_eq!(
+        vec![0x02, 0x09, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        der
+    );
+    assert_eq!(u64::MAX, crate::unmarshal::<u64>(&der).unwrap().0);
+
+    let err = crate::unmarshal::<u8>(&[0x02, 0x02, 0x01, 0x00]).unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+    let err = crate::unmarshal::<u8>(&[0x02, 0x01, 0xff]).unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+}
+
+#[test]
+fn integer_not_minimal() {
+    for data in [
+        &[0x02, 0x00][..],
+        &[0x02, 0x02, 0x00, 0x7f],
+        &[0x02, 0x02, 0xff, 0x80],
+    ] {
+        assert!(crate::unmarshal::<i64>(data).is_err(), "{:02x?}", data);
+        assert!(crate::unmarshal::<BigInt>(data).is_err(), "{:02x?}", data);
+    }
+}
+
+#[test]
+fn big_int() {
+    let v = BigInt::from_unsigned_bytes_be(&[0x00, 0x80, 0x01]);
+    assert_eq!(&[0x00, 0x80, 0x01], v.signed_bytes_be());
+    assert_eq!(Some(&[0x80, 0x01][..]), v.unsigned_bytes_be());
+    assert!(!v.is_negative());
+
+    let v = BigInt::from_signed_bytes_be(&[0xff, 0xff, 0x80]);
+    assert_eq!(&[0x80], v.signed_bytes_be());
+    assert_eq!(None, v.unsigned_bytes_be());
+    assert!(v.is_negative());
+
+    assert_eq!(BigInt::from(0), BigInt::from_signed_bytes_be(&[]));
+    assert_eq!(Some(&[0][..]), BigInt::from(0).unsigned_bytes_be());
+}
+
+#[test]
+fn booleans() {
+    assert_eq!(vec![0x01, 0x01, 0xff], crate::marshal(&true).unwrap());
+    assert_eq!(vec![0x01, 0x01, 0x00], crate::marshal(&false).unwrap());
+
+    let data = [0x01, 0x01, 0x01];
+    assert_eq!(
+        Error::Syntax("invalid boolean"),
+        crate::unmarshal::<bool>(&data).unwrap_err()
+    );
+    assert!(crate::unmarshal_ber::<bool>(&data).unwrap().0);
+}
+
+#[test]
+fn null_and_enumerated() {
+    assert_eq!(vec![0x05, 0x00], crate::marshal(&Null).unwrap());
+    assert_eq!(
+        Error::Syntax("invalid NULL"),
+        crate::unmarshal::<Null>(&[0x05, 0x01, 0x00]).unwrap_err()
+    );
+
+    let der = crate::marshal(&Enumerated(3)).unwrap();
+    assert_eq!(vec![0x0a, 0x01, 0x03], der);
+    assert_eq!(Enumerated(3), crate::unmarshal(&der).unwrap().0);
+}
+
+#[test]
+fn octet_strings() {
+    let der = crate::marshal(&b"hi".to_vec()).unwrap();
+    assert_eq!(vec![0x04, 0x02, b'h', b'i'], der);
+
+    // A constructed OCTET STRING of "h" and "i".
+    let data = [0x24, 0x80, 0x04, 0x01, b'h', 0x04, 0x01, b'i', 0x00, 0x00];
+    assert_eq!(
+        b"hi".to_vec(),
+        crate::unmarshal_ber::<Vec<u8>>(&data).unwrap().0
+    );
+
+    let data = [0x24, 0x06, 0x04, 0x01, b'h', 0x04, 0x01, b'i'];
+    assert_eq!(
+        Error::Syntax("constructed string (not DER)"),
+        crate::unmarshal::<Vec<u8>>(&data).unwrap_err()
+    );
+}
+
+#[test]
+fn octet_strings_nested_too_deeply() {
+    // Constructed OCTET STRINGs of definite length, each wrapping the next one.
+    let nested = |n: usize| {
+        let mut data = vec![0x04, 0x01, b'x'];
+        for _ in 0..n {
+            let mut outer = vec![0x24, 0x84];
+            outer.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            outer.append(&mut data);
+            data = outer;
+        }
+        data
+    };
+
+    let data = nested(10);
+    assert_eq!(
+        b"x".to_vec(),
+        crate::unmarshal_ber::<Vec<u8>>(&data).unwrap().0
+    );
+
+    let data = nested(1000);
+    let err = crate::unmarshal_ber::<Vec<u8>>(&data).unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+
+    // Indefinite lengths.
+    let mut data = [0x24, 0x80].repeat(200_000);
+    data.extend_from_slice(&[0x00, 0x00].repeat(200_000));
+    let err = crate::unmarshal_ber::<Vec<u8>>(&data).unwrap_err();
+    assert!(matches!(err, Error::Structural(_)), "{:?}", err);
+}
+
+#[test]
+fn raw_value() {
+    let data = [0xa1, 0x03, 0x02, 0x01, 0x07];
+    let (v, _) = crate::unmarshal::<RawValue>(&data).unwrap();
+    assert_eq!(Tag::context_specific(1, true), v.tag);
+    assert_eq!(vec![0x02, 0x01, 0x07], v.content);
+    assert_eq!(data.to_vec(), crate::marshal(&v).unwrap());
+}
@@ -0,0 +1,173 @@
+use crate::{
+    Decode, Encode, Error, Parser, Tag, TAG_BMP_STRING, TAG_GENERAL_STRING, TAG_IA5_STRING,
+    TAG_NUMERIC_STRING, TAG_PRINTABLE_STRING, TAG_T61_STRING, TAG_UTF8_STRING,
+};
+
+/// `PrintableString` is a string always marshaled as an ASN.1 PrintableString.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PrintableString(pub String);
+
+/// `Ia5String` is a string always marshaled as an ASN.1 IA5String, which is ASCII.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ia5String(pub String);
+
+/// `NumericString` is a string always marshaled as an ASN.1 NumericString, made of digits and
+/// spaces.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NumericString(pub String);
+
+/// `Utf8String` is a string always marshaled as an ASN.1 UTF8String.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Utf8String(pub String);
+
+/// Strings are marshaled as PrintableStrings if possible and UTF8Strings otherwise. Any
+/// string type is accepted when unmarshaling.
+impl Encode for str {
+    fn tag(&self) -> Tag {
+        if self.bytes().all(is_printable) {
+            Tag::universal(TAG_PRINTABLE_STRING, false)
+        } else {
+            Tag::universal(TAG_UTF8_STRING, false)
+        }
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        out.extend_from_slice(self.as_bytes());
+        Ok(())
+    }
+}
+
+impl Encode for String {
+    fn tag(&self) -> Tag {
+        self.as_str().tag()
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.as_str().encode_content(out)
+    }
+}
+
+impl Decode for String {
+    fn matches(tag: Tag) -> bool {
+        tag.class == crate::Class::Universal
+            && !tag.constructed
+            && matches!(
+                tag.number,
+                TAG_UTF8_STRING
+                    | TAG_NUMERIC_STRING
+                    | TAG_PRINTABLE_STRING
+                    | TAG_T61_STRING
+                    | TAG_IA5_STRING
+                    | TAG_GENERAL_STRING
+                    | TAG_BMP_STRING
+            )
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        let b = p.content();
+        match p.tag().map_or(TAG_UTF8_STRING, |t| t.number) {
+            TAG_NUMERIC_STRING => {
+                parse_ascii(b, is_numeric, "NumericString contains invalid character")
+            }
+            TAG_PRINTABLE_STRING => parse_ascii(
+                b,
+                is_printable_lenient,
+                "PrintableString contains invalid character",
+            ),
+            TAG_IA5_STRING => {
+                parse_ascii(b, |c| c.is_ascii(), "IA5String contains invalid character")
+            }
+            // T61String and GeneralString are taken as Latin-1, as their character sets can
+            // change mid-string.
+            TAG_T61_STRING | TAG_GENERAL_STRING => Ok(b.iter().map(|&c| c as char).collect()),
+            TAG_BMP_STRING => parse_bmp(b),
+            _ => String::from_utf8(b.to_vec()).map_err(|_| Error::Syntax("invalid UTF-8 string")),
+        }
+    }
+}
+
+macro_rules! impl_string {
+    ($t:ident, $tag:ident, $valid:expr, $name:literal) => {
+        impl Encode for $t {
+            fn tag(&self) -> Tag {
+                Tag::universal($tag, false)
+            }
+
+            fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+                if !self.0.bytes().all($valid) {
+                    return Err(Error::Structural(format!(
+                        "{} contains invalid character",
+                        $name
+                    )));
+                }
+                out.extend_from_slice(self.0.as_bytes());
+                Ok(())
+            }
+        }
+
+        impl Decode for $t {
+            fn matches(tag: Tag) -> bool {
+                tag == Tag::universal($tag, false)
+            }
+
+            fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+                String::decode_content(p).map(Self)
+            }
+        }
+    };
+}
+
+impl_string!(
+    PrintableString,
+    TAG_PRINTABLE_STRING,
+    is_printable,
+    "PrintableString"
+);
+impl_string!(Ia5String, TAG_IA5_STRING, |c: u8| c.is_ascii(), "IA5String");
+impl_string!(
+    NumericString,
+    TAG_NUMERIC_STRING,
+    is_numeric,
+    "NumericString"
+);
+impl_string!(Utf8String, TAG_UTF8_STRING, |_| true, "UTF8String");
+
+fn parse_ascii(b: &[u8], valid: fn(u8) -> bool, err: &'static str) -> Result<String, Error> {
+    if !b.iter().all(|&c| valid(c)) {
+        return Err(Error::Syntax(err));
+    }
+    Ok(String::from_utf8(b.to_vec()).expect("ASCII"))
+}
+
+fn parse_bmp(b: &[u8]) -> Result<String, Error> {
+    if b.len() % 2 != 0 {
+        return Err(Error::Syntax("invalid BMPString"));
+    }
+    let mut units: Vec<u16> = b
+        .chunks(2)
+        .map(|c| u16::from_be_bytes([c[0], c[1]]))
+        .collect();
+    // Strip the terminator if present.
+    if units.last() == Some(&0) {
+        units.pop();
+    }
+    String::from_utf16(&units).map_err(|_| Error::Syntax("invalid BMPString"))
+}
+
+/// Tells whether `c` is in the PrintableString character set.
+fn is_printable(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b" '()+,-./:=?".contains(&c)
+}
+
+/// Like [is_printable], also accepting '*' and '&' which are commonly, if mistakenly, found in
+/// certificates.
+fn is_printable_lenient(c: u8) -> bool {
+    is_printable(c) || c == b'*' || c == b'&'
+}
+
+fn is_numeric(c: u8) -> bool {
+    c.is_ascii_digit() || c == b' '
+}
+
+#[cfg(test)]
+mod tests;
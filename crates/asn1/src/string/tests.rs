@@ -0,0 +1,77 @@
+use crate::{Error, Ia5String, NumericString, PrintableString, Utf8String};
+
+#[test]
+fn marshal_string() {
+    let test_vector: Vec<(&str, &[u8])> = vec![
+        ("test", b"\x13\x04test"),
+        ("Gophers, Inc.", b"\x13\x0dGophers, Inc."),
+        ("a@b", b"\x0c\x03a@b"),
+        ("caf\u{e9}", b"\x0c\x05caf\xc3\xa9"),
+    ];
+
+    for (i, (s, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(expect, crate::marshal(s).unwrap(), "#{}", i);
+        assert_eq!(s, crate::unmarshal::<String>(expect).unwrap().0, "#{}", i);
+    }
+}
+
+#[test]
+fn unmarshal_string() {
+    let test_vector: Vec<(&[u8], &str)> = vec![
+        (b"\x12\x0312 ", "12 "),
+        (b"\x13\x03*.a", "*.a"),
+        (b"\x14\x04caf\xe9", "caf\u{e9}"),
+        (b"\x16\x03a@b", "a@b"),
+        (b"\x1e\x06\x00h\x00i\x00\x00", "hi"),
+        (b"\x1e\x04\xd8\x3d\xde\x00", "\u{1f600}"),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(
+            expect,
+            crate::unmarshal::<String>(data).unwrap().0,
+            "#{}",
+            i
+        );
+    }
+}
+
+#[test]
+fn unmarshal_string_invalid() {
+    let test_vector: Vec<(&[u8], &str)> = vec![
+        (b"\x12\x01a", "NumericString contains invalid character"),
+        (b"\x13\x01@", "PrintableString contains invalid character"),
+        (b"\x16\x01\x80", "IA5String contains invalid character"),
+        (b"\x0c\x01\xff", "invalid UTF-8 string"),
+        (b"\x1e\x01\x00", "invalid BMPString"),
+        (b"\x1e\x02\xd8\x3d", "invalid BMPString"),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        let err = crate::unmarshal::<String>(data).unwrap_err();
+        assert_eq!(Error::Syntax(expect), err, "#{}", i);
+    }
+}
+
+#[test]
+fn typed_strings() {
+    let der = crate::marshal(&Utf8String("test".to_string())).unwrap();
+    assert_eq!(b"\x0c\x04test".to_vec(), der);
+    assert_eq!("test", crate::unmarshal::<Utf8String>(&der).unwrap().0 .0);
+    assert!(crate::unmarshal::<PrintableString>(&der).is_err());
+
+    let der = crate::marshal(&Ia5String("a@b".to_string())).unwrap();
+    assert_eq!(b"\x16\x03a@b".to_vec(), der);
+
+    let der = crate::marshal(&NumericString("0 1".to_string())).unwrap();
+    assert_eq!(b"\x12\x030 1".to_vec(), der);
+
+    let invalid = [
+        crate::marshal(&PrintableString("a@b".to_string())),
+        crate::marshal(&Ia5String("caf\u{e9}".to_string())),
+        crate::marshal(&NumericString("1a".to_string())),
+    ];
+    for (i, v) in invalid.iter().enumerate() {
+        assert!(matches!(v, Err(Error::Structural(_))), "#{}", i);
+    }
+}
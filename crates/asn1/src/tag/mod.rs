@@ -0,0 +1,218 @@
+use std::fmt;
+
+use crate::Error;
+
+/// Universal tag numbers, as defined in X.680.
+pub const TAG_BOOLEAN: u32 = 1;
+pub const TAG_INTEGER: u32 = 2;
+pub const TAG_BIT_STRING: u32 = 3;
+pub const TAG_OCTET_STRING: u32 = 4;
+pub const TAG_NULL: u32 = 5;
+pub const TAG_OID: u32 = 6;
+pub const TAG_ENUM: u32 = 10;
+pub const TAG_UTF8_STRING: u32 = 12;
+pub const TAG_SEQUENCE: u32 = 16;
+pub const TAG_SET: u32 = 17;
+pub const TAG_NUMERIC_STRING: u32 = 18;
+pub const TAG_PRINTABLE_STRING: u32 = 19;
+pub const TAG_T61_STRING: u32 = 20;
+pub const TAG_IA5_STRING: u32 = 22;
+pub const TAG_UTC_TIME: u32 = 23;
+pub const TAG_GENERALIZED_TIME: u32 = 24;
+pub const TAG_GENERAL_STRING: u32 = 27;
+pub const TAG_BMP_STRING: u32 = 30;
+
+/// The class of a tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Class {
+    Universal = 0,
+    Application = 1,
+    ContextSpecific = 2,
+    Private = 3,
+}
+
+/// `Tag` identifies the type of an ASN.1 element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tag {
+    pub class: Class,
+    /// Whether the contents are a sequence of elements rather than the bytes of a value.
+    pub constructed: bool,
+    pub number: u32,
+}
+
+/// The header of an element as parsed: its tag and the length of its contents, if definite.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Header {
+    pub(crate) tag: Tag,
+    pub(crate) len: Option<usize>,
+}
+
+impl Tag {
+    pub const BOOLEAN: Self = Self::universal(TAG_BOOLEAN, false);
+    pub const INTEGER: Self = Self::universal(TAG_INTEGER, false);
+    pub const BIT_STRING: Self = Self::universal(TAG_BIT_STRING, false);
+    pub const OCTET_STRING: Self = Self::universal(TAG_OCTET_STRING, false);
+    pub const NULL: Self = Self::universal(TAG_NULL, false);
+    pub const OID: Self = Self::universal(TAG_OID, false);
+    pub const ENUM: Self = Self::universal(TAG_ENUM, false);
+    pub const SEQUENCE: Self = Self::universal(TAG_SEQUENCE, true);
+    pub const SET: Self = Self::universal(TAG_SET, true);
+
+    /// Returns a tag of the universal class.
+    pub const fn universal(number: u32, constructed: bool) -> Self {
+        Self {
+            class: Class::Universal,
+            constructed,
+            number,
+        }
+    }
+
+    /// Returns a tag of the context-specific class, as used for tagging fields.
+    pub const fn context_specific(number: u32, constructed: bool) -> Self {
+        Self {
+            class: Class::ContextSpecific,
+            constructed,
+            number,
+        }
+    }
+
+    /// Tells whether `self` has the same class and number as `other`, constructed or not.
+    pub fn is(&self, other: Tag) -> bool {
+        self.class == other.class && self.number == other.number
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let class = match self.class {
+            Class::Universal => "UNIVERSAL",
+            Class::Application => "APPLICATION",
+            Class::ContextSpecific => "CONTEXT",
+            Class::Private => "PRIVATE",
+        };
+        write!(f, "[{} {}]", class, self.number)?;
+        if self.constructed {
+            f.write_str(" constructed")?;
+        }
+        Ok(())
+    }
+}
+
+impl Header {
+    /// Parses the header at the start of `data`, returning it along with its length in bytes.
+    /// Indefinite and non-minimal lengths are only allowed if `ber` is set.
+    pub(crate) fn parse(data: &[u8], ber: bool) -> Result<(Self, usize), Error> {
+        let mut offset = 0;
+        let mut next = || {
+            let b = data.get(offset).copied();
+            offset += 1;
+            b.ok_or(Error::Syntax("data truncated"))
+        };
+
+        let b = next()?;
+        let class = match b >> 6 {
+            0 => Class::Universal,
+            1 => Class::Application,
+            2 => Class::ContextSpecific,
+            _ => Class::Private,
+        };
+        let constructed = b & 0x20 != 0;
+        let mut number = (b & 0x1f) as u32;
+        if number == 0x1f {
+            // High-tag-number form, in base 128.
+            number = 0;
+            loop {
+                let b = next()?;
+                if number == 0 && b == 0x80 {
+                    return Err(Error::Syntax("integer is not minimally encoded"));
+                }
+                if number >= 1 << 24 {
+                    return Err(Error::Syntax("base 128 integer too large"));
+                }
+                number = (number << 7) | (b & 0x7f) as u32;
+                if b & 0x80 == 0 {
+                    break;
+                }
+            }
+            if number < 0x1f {
+                return Err(Error::Syntax("non-minimal tag"));
+            }
+        }
+        let tag = Tag {
+            class,
+            constructed,
+            number,
+        };
+
+        let b = next()?;
+        let len = if b & 0x80 == 0 {
+            Some(b as usize)
+        } else if b == 0x80 {
+            if !ber {
+                return Err(Error::Syntax("indefinite length found (not DER)"));
+            }
+            if !constructed {
+                return Err(Error::Syntax("indefinite length of primitive element"));
+            }
+            None
+        } else {
+            let n = (b & 0x7f) as usize;
+            let mut len = 0usize;
+            for i in 0..n {
+                let b = next()?;
+                if len >= 1 << 23 {
+                    return Err(Error::Syntax("length too large"));
+                }
+                if i == 0 && b == 0 && !ber {
+                    return Err(Error::Syntax("superfluous leading zeros in length"));
+                }
+                len = (len << 8) | b as usize;
+            }
+            if len < 0x80 && !ber {
+                return Err(Error::Syntax("non-minimal length"));
+            }
+            Some(len)
+        };
+
+        Ok((Self { tag, len }, offset))
+    }
+
+    /// Appends the DER encoding of a header to `out`.
+    pub(crate) fn write(out: &mut Vec<u8>, tag: Tag, len: usize) {
+        let mut b = (tag.class as u8) << 6;
+        if tag.constructed {
+            b |= 0x20;
+        }
+        if tag.number < 0x1f {
+            out.push(b | tag.number as u8);
+        } else {
+            out.push(b | 0x1f);
+            write_base128(out, tag.number as u64);
+        }
+
+        if len < 0x80 {
+            out.push(len as u8);
+        } else {
+            let n = (usize::BITS - len.leading_zeros() + 7) / 8;
+            out.push(0x80 | n as u8);
+            for i in (0..n).rev() {
+                out.push((len >> (8 * i)) as u8);
+            }
+        }
+    }
+}
+
+/// Appends `v` in base 128 with the high bit of every byte but the last one set.
+pub(crate) fn write_base128(out: &mut Vec<u8>, v: u64) {
+    let n = ((64 - v.leading_zeros()).max(1) + 6) / 7;
+    for i in (0..n).rev() {
+        let mut b = ((v >> (7 * i)) & 0x7f) as u8;
+        if i != 0 {
+            b |= 0x80;
+        }
+        out.push(b);
+    }
+}
+
+#[cfg(test)]
+mod tests;
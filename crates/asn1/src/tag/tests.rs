@@ -0,0 +1,119 @@
+use crate::tag::Header;
+use crate::{Class, Error, Tag};
+
+#[test]
+fn parse() {
+    struct Case {
+        data: &'static [u8],
+        tag: Tag,
+        len: Option<usize>,
+        n: usize,
+    }
+
+    let test_vector = [
+        Case {
+            data: &[0x02, 0x01],
+            tag: Tag::INTEGER,
+            len: Some(1),
+            n: 2,
+        },
+        Case {
+            data: &[0x30, 0x81, 0x80],
+            tag: Tag::SEQUENCE,
+            len: Some(0x80),
+            n: 3,
+        },
+        Case {
+            data: &[0xa3, 0x82, 0x01, 0x00],
+            tag: Tag::context_specific(3, true),
+            len: Some(0x100),
+            n: 4,
+        },
+        Case {
+            data: &[0x5f, 0x81, 0x00, 0x00],
+            tag: Tag {
+                class: Class::Application,
+                constructed: false,
+                number: 0x80,
+            },
+            len: Some(0),
+            n: 4,
+        },
+        Case {
+            data: &[0xdf, 0x1f, 0x00],
+            tag: Tag {
+                class: Class::Private,
+                constructed: false,
+                number: 0x1f,
+            },
+            len: Some(0),
+            n: 3,
+        },
+    ];
+
+    for (i, c) in test_vector.iter().enumerate() {
+        let (h, n) = Header::parse(c.data, false).unwrap();
+        assert_eq!(c.tag, h.tag, "#{} tag", i);
+        assert_eq!(c.len, h.len, "#{} len", i);
+        assert_eq!(c.n, n, "#{} n", i);
+
+        let mut out = vec![];
+        Header::write(&mut out, h.tag, h.len.unwrap());
+        assert_eq!(c.data, out.as_slice(), "#{} write", i);
+    }
+}
+
+#[test]
+fn parse_der_errors() {
+    let test_vector: Vec<(&[u8], &str)> = vec![
+        (&[], "data truncated"),
+        (&[0x02], "data truncated"),
+        (&[0x02, 0x81], "data truncated"),
+        (&[0x30, 0x80], "indefinite length found (not DER)"),
+        (&[0x02, 0x81, 0x01], "non-minimal length"),
+        (
+            &[0x02, 0x82, 0x00, 0x80],
+            "superfluous leading zeros in length",
+        ),
+        (&[0x1f, 0x1e, 0x00], "non-minimal tag"),
+        (
+            &[0x1f, 0x80, 0x01, 0x00],
+            "integer is not minimally encoded",
+        ),
+        (
+            &[0x1f, 0x88, 0x80, 0x80, 0x80, 0x00, 0x00],
+            "base 128 integer too large",
+        ),
+        (
+            &[0x04, 0x85, 0x01, 0x00, 0x00, 0x00, 0x00],
+            "length too large",
+        ),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        let err = Header::parse(data, false).unwrap_err();
+        assert_eq!(Error::Syntax(expect), err, "#{}", i);
+    }
+}
+
+#[test]
+fn parse_ber() {
+    let (h, n) = Header::parse(&[0x30, 0x80], true).unwrap();
+    assert_eq!((Tag::SEQUENCE, None, 2), (h.tag, h.len, n));
+
+    let (h, _) = Header::parse(&[0x02, 0x82, 0x00, 0x01], true).unwrap();
+    assert_eq!(Some(1), h.len);
+
+    let err = Header::parse(&[0x04, 0x80], true).unwrap_err();
+    assert_eq!(Error::Syntax("indefinite length of primitive element"), err);
+}
+
+#[test]
+fn display() {
+    assert_eq!("[UNIVERSAL 2]", Tag::INTEGER.to_string());
+    assert_eq!("[UNIVERSAL 16] constructed", Tag::SEQUENCE.to_string());
+    assert_eq!(
+        "[CONTEXT 0] constructed",
+        Tag::context_specific(0, true).to_string()
+    );
+}
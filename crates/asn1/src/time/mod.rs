@@ -0,0 +1,341 @@
+use std::fmt::Write;
+
+use crate::{Decode, Encode, Error, Parser, Tag, TAG_GENERALIZED_TIME, TAG_UTC_TIME};
+
+/// `DateTime` is a calendar date and time of day with an offset from UTC.
+///
+/// It's marshaled as a UTCTime for years 1950 through 2049 and as a GeneralizedTime otherwise,
+/// as X.509 requires. Either is accepted when unmarshaling. [UtcTime] and [GeneralizedTime]
+/// pin down the type.
+///
+/// # Example
+/// ```
+/// use asn1::DateTime;
+///
+/// let t = DateTime::from_unix(1_000_000_000);
+/// let der = asn1::marshal(&t).unwrap();
+/// assert_eq!(b"\x17\x0d010909014640Z", der.as_slice());
+///
+/// let (got, _) = asn1::unmarshal::<DateTime>(&der).unwrap();
+/// assert_eq!(1_000_000_000, got.unix());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    pub year: i32,
+    /// Month of the year, from 1.
+    pub month: u8,
+    /// Day of the month, from 1.
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+    /// Offset from UTC in minutes, positive east of Greenwich.
+    pub offset_minutes: i16,
+}
+
+/// `UtcTime` is a [DateTime] always marshaled as an ASN.1 UTCTime, with seconds and in UTC
+/// if its offset is zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UtcTime(pub DateTime);
+
+/// `GeneralizedTime` is a [DateTime] always marshaled as an ASN.1 GeneralizedTime.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GeneralizedTime(pub DateTime);
+
+impl DateTime {
+    /// Returns the UTC time `secs` seconds after the Unix epoch.
+    pub fn from_unix(secs: i64) -> Self {
+        let (days, secs) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year,
+            month,
+            day,
+            hour: (secs / 3600) as u8,
+            minute: (secs % 3600 / 60) as u8,
+            second: (secs % 60) as u8,
+            nanosecond: 0,
+            offset_minutes: 0,
+        }
+    }
+
+    /// Returns the number of seconds since the Unix epoch, ignoring nanoseconds.
+    pub fn unix(&self) -> i64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        days * 86400 + self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64
+            - self.offset_minutes as i64 * 60
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let valid = (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+            && self.nanosecond < 1_000_000_000
+            && self.offset_minutes.unsigned_abs() < 24 * 60;
+        if !valid {
+            return Err(Error::Structural("invalid time".to_string()));
+        }
+        Ok(())
+    }
+
+    fn write_utc_time(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.validate()?;
+        if !(1950..2050).contains(&self.year) {
+            return Err(Error::Structural(
+                "cannot represent time as UTCTime".to_string(),
+            ));
+        }
+
+        let mut s = String::new();
+        let _ = write!(
+            s,
+            "{:02}{:02}{:02}{:02}{:02}{:02}",
+            self.year % 100,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second
+        );
+        self.write_offset(&mut s);
+        out.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+
+    fn write_generalized_time(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.validate()?;
+        if !(0..10000).contains(&self.year) {
+            return Err(Error::Structural(
+                "cannot represent time as GeneralizedTime".to_string(),
+            ));
+        }
+
+        let mut s = String::new();
+        let _ = write!(
+            s,
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        );
+        if self.nanosecond > 0 {
+            let frac = format!("{:09}", self.nanosecond);
+            let _ = write!(s, ".{}", frac.trim_end_matches('0'));
+        }
+        self.write_offset(&mut s);
+        out.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+
+    fn write_offset(&self, s: &mut String) {
+        if self.offset_minutes == 0 {
+            s.push('Z');
+            return;
+        }
+        let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+        let m = self.offset_minutes.unsigned_abs();
+        let _ = write!(s, "{}{:02}{:02}", sign, m / 60, m % 60);
+    }
+
+    /// Parses a UTCTime, "YYMMDDhhmm[ss]" followed by "Z" or an offset "±hhmm".
+    fn parse_utc_time(b: &[u8]) -> Result<Self, Error> {
+        let mut s = Scanner(b);
+        let yy = s.digits(2)? as i32;
+        let mut t = Self {
+            year: if yy < 50 { 2000 + yy } else { 1900 + yy },
+            month: s.digits(2)? as u8,
+            day: s.digits(2)? as u8,
+            hour: s.digits(2)? as u8,
+            minute: s.digits(2)? as u8,
+            ..Default::default()
+        };
+        if matches!(s.0.first(), Some(c) if c.is_ascii_digit()) {
+            t.second = s.digits(2)? as u8;
+        }
+        t.offset_minutes = s.offset()?;
+        t.finish(s)
+    }
+
+    /// Parses a GeneralizedTime, "YYYYMMDDhhmmss[.fff]" followed by "Z" or an offset
+    /// "±hhmm".
+    fn parse_generalized_time(b: &[u8]) -> Result<Self, Error> {
+        let mut s = Scanner(b);
+        let mut t = Self {
+            year: s.digits(4)? as i32,
+            month: s.digits(2)? as u8,
+            day: s.digits(2)? as u8,
+            hour: s.digits(2)? as u8,
+            minute: s.digits(2)? as u8,
+            second: s.digits(2)? as u8,
+            ..Default::default()
+        };
+        if let Some(rest) = s.0.strip_prefix(b".") {
+            let n = rest.iter().take_while(|c| c.is_ascii_digit()).count();
+            if n == 0 || n > 9 || rest[n - 1] == b'0' {
+                return Err(Error::Syntax("invalid GeneralizedTime fraction"));
+            }
+            s.0 = rest;
+            t.nanosecond = s.digits(n)? * 10u32.pow(9 - n as u32);
+        }
+        t.offset_minutes = s.offset()?;
+        t.finish(s)
+    }
+
+    fn finish(self, s: Scanner) -> Result<Self, Error> {
+        if !s.0.is_empty() {
+            return Err(Error::Syntax("trailing data in time"));
+        }
+        self.validate()
+            .map_err(|_| Error::Syntax("invalid time"))
+            .map(|_| self)
+    }
+}
+
+impl Encode for DateTime {
+    fn tag(&self) -> Tag {
+        if (1950..2050).contains(&self.year) {
+            Tag::universal(TAG_UTC_TIME, false)
+        } else {
+            Tag::universal(TAG_GENERALIZED_TIME, false)
+        }
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        if (1950..2050).contains(&self.year) {
+            self.write_utc_time(out)
+        } else {
+            self.write_generalized_time(out)
+        }
+    }
+}
+
+impl Decode for DateTime {
+    fn matches(tag: Tag) -> bool {
+        UtcTime::matches(tag) || GeneralizedTime::matches(tag)
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        if matches!(p.tag(), Some(t) if t.number == TAG_UTC_TIME) {
+            Self::parse_utc_time(p.content())
+        } else {
+            Self::parse_generalized_time(p.content())
+        }
+    }
+}
+
+impl Encode for UtcTime {
+    fn tag(&self) -> Tag {
+        Tag::universal(TAG_UTC_TIME, false)
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_utc_time(out)
+    }
+}
+
+impl Decode for UtcTime {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::universal(TAG_UTC_TIME, false)
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        DateTime::parse_utc_time(p.content()).map(Self)
+    }
+}
+
+impl Encode for GeneralizedTime {
+    fn tag(&self) -> Tag {
+        Tag::universal(TAG_GENERALIZED_TIME, false)
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.0.write_generalized_time(out)
+    }
+}
+
+impl Decode for GeneralizedTime {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::universal(TAG_GENERALIZED_TIME, false)
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        DateTime::parse_generalized_time(p.content()).map(Self)
+    }
+}
+
+struct Scanner<'a>(&'a [u8]);
+
+impl<'a> Scanner<'a> {
+    fn digits(&mut self, n: usize) -> Result<u32, Error> {
+        if self.0.len() < n || !self.0[..n].iter().all(u8::is_ascii_digit) {
+            return Err(Error::Syntax("invalid time"));
+        }
+        let v = self.0[..n]
+            .iter()
+            .fold(0, |acc, c| acc * 10 + (c - b'0') as u32);
+        self.0 = &self.0[n..];
+        Ok(v)
+    }
+
+    fn offset(&mut self) -> Result<i16, Error> {
+        let sign = match self.0.first() {
+            Some(b'Z') => {
+                self.0 = &self.0[1..];
+                return Ok(0);
+            }
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return Err(Error::Syntax("invalid time zone")),
+        };
+        self.0 = &self.0[1..];
+        let (h, m) = (self.digits(2)?, self.digits(2)?);
+        if h >= 24 || m >= 60 {
+            return Err(Error::Syntax("invalid time zone"));
+        }
+        Ok(sign * (h * 60 + m) as i16)
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since 1970-01-01 of a date in the proleptic Gregorian calendar,
+/// after <http://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let y = year as i64 - (month <= 2) as i64;
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The inverse of [days_from_civil].
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,201 @@
+use crate::{DateTime, Error, GeneralizedTime, UtcTime};
+
+#[test]
+fn unmarshal_utc_time() {
+    let test_vector: Vec<(&[u8], DateTime)> = vec![
+        (
+            b"910506164540-0700",
+            DateTime {
+                year: 1991,
+                month: 5,
+                day: 6,
+                hour: 16,
+                minute: 45,
+                second: 40,
+                offset_minutes: -7 * 60,
+                ..Default::default()
+            },
+        ),
+        (
+            b"910506234540Z",
+            DateTime {
+                year: 1991,
+                month: 5,
+                day: 6,
+                hour: 23,
+                minute: 45,
+                second: 40,
+                ..Default::default()
+            },
+        ),
+        (
+            b"4912312359Z",
+            DateTime {
+                year: 2049,
+                month: 12,
+                day: 31,
+                hour: 23,
+                minute: 59,
+                ..Default::default()
+            },
+        ),
+        (
+            b"000229000000+0130",
+            DateTime {
+                year: 2000,
+                month: 2,
+                day: 29,
+                offset_minutes: 90,
+                ..Default::default()
+            },
+        ),
+    ];
+
+    for (i, (s, expect)) in test_vector.into_iter().enumerate() {
+        let mut data = vec![0x17, s.len() as u8];
+        data.extend_from_slice(s);
+
+        let (got, _) = crate::unmarshal::<UtcTime>(&data).unwrap();
+        assert_eq!(expect, got.0, "#{}", i);
+        let (got, _) = crate::unmarshal::<DateTime>(&data).unwrap();
+        assert_eq!(expect, got, "#{}", i);
+    }
+}
+
+#[test]
+fn unmarshal_generalized_time() {
+    let test_vector: Vec<(&[u8], DateTime)> = vec![
+        (
+            b"20100102030405Z",
+            DateTime {
+                year: 2010,
+                month: 1,
+                day: 2,
+                hour: 3,
+                minute: 4,
+                second: 5,
+                ..Default::default()
+            },
+        ),
+        (
+            b"20100102030405.25-0130",
+            DateTime {
+                year: 2010,
+                month: 1,
+                day: 2,
+                hour: 3,
+                minute: 4,
+                second: 5,
+                nanosecond: 250_000_000,
+                offset_minutes: -90,
+            },
+        ),
+    ];
+
+    for (i, (s, expect)) in test_vector.into_iter().enumerate() {
+        let mut data = vec![0x18, s.len() as u8];
+        data.extend_from_slice(s);
+
+        let (got, _) = crate::unmarshal::<GeneralizedTime>(&data).unwrap();
+        assert_eq!(expect, got.0, "#{}", i);
+        assert_eq!(data, crate::marshal(&got).unwrap(), "#{} marshal", i);
+    }
+}
+
+#[test]
+fn unmarshal_invalid() {
+    let test_vector: Vec<(&[u8], Error)> = vec![
+        (b"\x17\x0b9105062345Y", Error::Syntax("invalid time zone")),
+        (b"\x17\x0d910231234540Z", Error::Syntax("invalid time")),
+        (
+            b"\x17\x0e910506234540Zx",
+            Error::Syntax("trailing data in time"),
+        ),
+        (b"\x17\x0991050623Z", Error::Syntax("invalid time")),
+        (
+            b"\x18\x1220100102030405.10Z",
+            Error::Syntax("invalid GeneralizedTime fraction"),
+        ),
+        (
+            b"\x18\x1020100102030405.Z",
+            Error::Syntax("invalid GeneralizedTime fraction"),
+        ),
+        (b"\x18\x0f20100102030405+", Error::Syntax("invalid time")),
+        (
+            b"\x18\x1320100102030405+2400",
+            Error::Syntax("invalid time zone"),
+        ),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        let err = crate::unmarshal::<DateTime>(data).unwrap_err();
+        assert_eq!(expect, err, "#{}", i);
+    }
+}
+
+#[test]
+fn marshal() {
+    let test_vector: Vec<(DateTime, &[u8])> = vec![
+        (DateTime::from_unix(0), b"\x17\x0d700101000000Z"),
+        (DateTime::from_unix(2524608000), b"\x18\x0f20500101000000Z"),
+        (DateTime::from_unix(-631152001), b"\x18\x0f19491231235959Z"),
+        (
+            DateTime {
+                offset_minutes: 5 * 60 + 30,
+                ..DateTime::from_unix(0)
+            },
+            b"\x17\x11700101000000+0530",
+        ),
+    ];
+
+    for (i, (t, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(expect, crate::marshal(&t).unwrap(), "#{}", i);
+    }
+
+    let t = DateTime::from_unix(2524608000);
+    assert!(crate::marshal(&UtcTime(t)).is_err());
+    assert_eq!(
+        b"\x18\x0f19700101000000Z".to_vec(),
+        crate::marshal(&GeneralizedTime(DateTime::from_unix(0))).unwrap()
+    );
+
+    for t in [
+        DateTime {
+            month: 13,
+            ..DateTime::from_unix(0)
+        },
+        DateTime {
+            offset_minutes: 24 * 60,
+            ..DateTime::from_unix(0)
+        },
+        DateTime {
+            offset_minutes: i16::MIN,
+            ..DateTime::from_unix(0)
+        },
+    ] {
+        let err = crate::marshal(&t).unwrap_err();
+        assert_eq!(
+            Error::Structural("invalid time".to_string()),
+            err,
+            "{:?}",
+            t
+        );
+    }
+}
+
+#[test]
+fn unix() {
+    for secs in [0, 951782400, -631152001, 2524608000, 253402300799] {
+        assert_eq!(secs, DateTime::from_unix(secs).unix(), "{}", secs);
+    }
+
+    let t = DateTime {
+        year: 1970,
+        month: 1,
+        day: 1,
+        hour: 1,
+        offset_minutes: 60,
+        ..Default::default()
+    };
+    assert_eq!(0, t.unix());
+}
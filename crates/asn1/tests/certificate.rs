@@ -0,0 +1,269 @@
+use asn1::{
+    BigInt, BitString, DateTime, Decode, Encode, Error, Explicit, Implicit, ObjectIdentifier,
+    Parser, RawValue, SequenceOf, SetOf, Tag, Utf8String,
+};
+
+const CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBmzCCAUKgAwIBAgIFAQIDBAUwCgYIKoZIzj0EAwIwKzEXMBUGA1UEAwwOZ29w
+aGVyLmV4YW1wbGUxEDAOBgNVBAoMB0dvcGhlcnMwHhcNMjYxMDE4MDcyNTI4WhcN
+MzYxMDE1MDcyNTI4WjArMRcwFQYDVQQDDA5nb3BoZXIuZXhhbXBsZTEQMA4GA1UE
+CgwHR29waGVyczBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABC502VDDCjADnZQi
+G0wJ6DhM4esIPY/5GvZuTdk/FIJp3me9cwMOXWZ28ROVw4SRI5b1SG6zKolrhfFE
+o4eR9hqjUzBRMB0GA1UdDgQWBBTfTgAs9jWty8dfeDBI+yGfjFTjITAfBgNVHSME
+GDAWgBTfTgAs9jWty8dfeDBI+yGfjFTjITAPBgNVHRMBAf8EBTADAQH/MAoGCCqG
+SM49BAMCA0cAMEQCIBEM89FsUAz4yPiqXKN0IVKxz4wm2tu2AgvtQJwkR7LFAiBt
+sUeCMHhvK+l5ozivqmuyekKsEoXqs+o1XueUPC4m9Q==
+-----END CERTIFICATE-----
+";
+
+/// Certificate ::= SEQUENCE {
+///     tbsCertificate     TBSCertificate,
+///     signatureAlgorithm AlgorithmIdentifier,
+///     signatureValue     BIT STRING }
+#[derive(Debug, PartialEq)]
+struct Certificate {
+    tbs_certificate: TbsCertificate,
+    signature_algorithm: AlgorithmIdentifier,
+    signature_value: BitString,
+}
+
+/// TBSCertificate ::= SEQUENCE {
+///     version         [0] EXPLICIT Version DEFAULT v1,
+///     serialNumber         CertificateSerialNumber,
+///     signature            AlgorithmIdentifier,
+///     issuer               Name,
+///     validity             Validity,
+///     subject              Name,
+///     subjectPublicKeyInfo SubjectPublicKeyInfo,
+///     issuerUniqueID  [1] IMPLICIT UniqueIdentifier OPTIONAL,
+///     subjectUniqueID [2] IMPLICIT UniqueIdentifier OPTIONAL,
+///     extensions      [3] EXPLICIT Extensions OPTIONAL }
+#[derive(Debug, PartialEq)]
+struct TbsCertificate {
+    version: Option<Explicit<i64, 0>>,
+    serial_number: BigInt,
+    signature: AlgorithmIdentifier,
+    issuer: Name,
+    validity: Validity,
+    subject: Name,
+    subject_public_key_info: SubjectPublicKeyInfo,
+    issuer_unique_id: Option<Implicit<BitString, 1>>,
+    subject_unique_id: Option<Implicit<BitString, 2>>,
+    extensions: Option<Explicit<SequenceOf<Extension>, 3>>,
+}
+
+#[derive(Debug, PartialEq)]
+struct AlgorithmIdentifier {
+    algorithm: ObjectIdentifier,
+    parameters: Option<RawValue>,
+}
+
+type Name = SequenceOf<SetOf<AttributeTypeAndValue>>;
+
+#[derive(Debug, PartialEq)]
+struct AttributeTypeAndValue {
+    type_: ObjectIdentifier,
+    /// A DirectoryString, which is a UTF8String in the certificate.
+    value: Utf8String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Validity {
+    not_before: DateTime,
+    not_after: DateTime,
+}
+
+#[derive(Debug, PartialEq)]
+struct SubjectPublicKeyInfo {
+    algorithm: RawValue,
+    subject_public_key: BitString,
+}
+
+#[derive(Debug, PartialEq)]
+struct Extension {
+    extn_id: ObjectIdentifier,
+    critical: bool,
+    extn_value: Vec<u8>,
+}
+
+/// Implements [Encode] and [Decode] for a SEQUENCE of required (`req`) and OPTIONAL (`opt`)
+/// fields.
+macro_rules! sequence {
+    ($t:ident { $($field:ident: $kind:ident),+ }) => {
+        impl Encode for $t {
+            fn tag(&self) -> Tag {
+                Tag::SEQUENCE
+            }
+
+            fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+                $(encode_field!($kind, out, self.$field);)+
+                Ok(())
+            }
+        }
+
+        impl Decode for $t {
+            fn matches(tag: Tag) -> bool {
+                tag == Tag::SEQUENCE
+            }
+
+            fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+                Ok(Self {
+                    $($field: decode_field!($kind, p),)+
+                })
+            }
+        }
+    };
+}
+
+macro_rules! encode_field {
+    (req, $out:ident, $v:expr) => {
+        $v.encode($out)?
+    };
+    (opt, $out:ident, $v:expr) => {
+        if let Some(v) = &$v {
+            v.encode($out)?;
+        }
+    };
+}
+
+macro_rules! decode_field {
+    (req, $p:ident) => {
+        $p.read()?
+    };
+    (opt, $p:ident) => {
+        $p.read_optional()?
+    };
+}
+
+sequence!(Certificate {
+    tbs_certificate: req,
+    signature_algorithm: req,
+    signature_value: req
+});
+sequence!(TbsCertificate {
+    version: opt,
+    serial_number: req,
+    signature: req,
+    issuer: req,
+    validity: req,
+    subject: req,
+    subject_public_key_info: req,
+    issuer_unique_id: opt,
+    subject_unique_id: opt,
+    extensions: opt
+});
+sequence!(AlgorithmIdentifier {
+    algorithm: req,
+    parameters: opt
+});
+sequence!(AttributeTypeAndValue {
+    type_: req,
+    value: req
+});
+sequence!(Validity {
+    not_before: req,
+    not_after: req
+});
+sequence!(SubjectPublicKeyInfo {
+    algorithm: req,
+    subject_public_key: req
+});
+
+// critical is BOOLEAN DEFAULT FALSE, which DER leaves out when false.
+impl Encode for Extension {
+    fn tag(&self) -> Tag {
+        Tag::SEQUENCE
+    }
+
+    fn encode_content(&self, out: &mut Vec<u8>) -> Result<(), Error> {
+        self.extn_id.encode(out)?;
+        if self.critical {
+            self.critical.encode(out)?;
+        }
+        self.extn_value.encode(out)
+    }
+}
+
+impl Decode for Extension {
+    fn matches(tag: Tag) -> bool {
+        tag == Tag::SEQUENCE
+    }
+
+    fn decode_content(p: &mut Parser<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            extn_id: p.read()?,
+            critical: p.read_optional()?.unwrap_or_default(),
+            extn_value: p.read()?,
+        })
+    }
+}
+
+#[test]
+fn parse_certificate() {
+    let (block, _) = pem::decode(CERTIFICATE.as_bytes()).unwrap();
+    let cert: Certificate = asn1::parse_pem(&block).unwrap();
+
+    let tbs = &cert.tbs_certificate;
+    assert_eq!(Some(Explicit(2)), tbs.version);
+    assert_eq!(
+        Some(&[1u8, 2, 3, 4, 5][..]),
+        tbs.serial_number.unsigned_bytes_be()
+    );
+    assert_eq!("1.2.840.10045.4.3.2", tbs.signature.algorithm.to_string());
+    assert_eq!(tbs.signature, cert.signature_algorithm);
+
+    let names: Vec<(String, &str)> = tbs
+        .subject
+        .0
+        .iter()
+        .flat_map(|rdn| rdn.0.iter())
+        .map(|v| (v.type_.to_string(), v.value.0.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            ("2.5.4.3".to_string(), "gopher.example"),
+            ("2.5.4.10".to_string(), "Gophers")
+        ],
+        names
+    );
+    assert_eq!(tbs.issuer, tbs.subject);
+
+    let validity = &tbs.validity;
+    assert_eq!(
+        (2026, 10, 18),
+        (
+            validity.not_before.year,
+            validity.not_before.month,
+            validity.not_before.day
+        )
+    );
+    assert_eq!(
+        3650 * 86400,
+        validity.not_after.unix() - validity.not_before.unix()
+    );
+
+    assert_eq!(
+        520,
+        tbs.subject_public_key_info.subject_public_key.bit_length
+    );
+    assert!(tbs.issuer_unique_id.is_none());
+
+    let extensions = &tbs.extensions.as_ref().unwrap().0 .0;
+    assert_eq!(3, extensions.len());
+    assert_eq!("2.5.29.19", extensions[2].extn_id.to_string());
+    assert!(extensions[2].critical);
+    assert!(!extensions[0].critical);
+
+    // Re-encoding gives back the exact DER.
+    assert_eq!(block.bytes, asn1::marshal(&cert).unwrap());
+}
+
+#[test]
+fn parse_pem_trailing_data() {
+    let (mut block, _) = pem::decode(CERTIFICATE.as_bytes()).unwrap();
+    block.bytes.push(0);
+    assert_eq!(
+        Err(Error::Syntax("trailing data")),
+        asn1::parse_pem::<Certificate>(&block)
+    );
+}
@@ -1,7 +1,7 @@
 use lazy_static::lazy_static;
 
 use crate::constants;
-use crate::CorruptInputError;
+use crate::{AlphabetError, CorruptInputError};
 
 lazy_static! {
     /// StdEncoding is the standard base32 encoding, as defined in
@@ -32,6 +32,9 @@ pub struct Encoding {
 impl Encoding {
     /// Returns a new `Encoding` defined by the given alphabet,
     /// which must be a 32-byte string.
+    ///
+    /// Duplicated symbols aren't checked, the later one being decoded. Use
+    /// [try_new][Self::try_new] for alphabets that aren't known to be valid.
     pub fn new<T>(encoder: T) -> Self
     where
         T: AsRef<str>,
@@ -63,6 +66,52 @@ impl Encoding {
         }
     }
 
+    /// Like [new][Self::new] but returns an error instead of panicking if the alphabet
+    /// isn't made of 32 distinct ASCII symbols other than CR and LF.
+    ///
+    /// # Example
+    /// ```
+    /// use base32::{AlphabetError, Encoding};
+    ///
+    /// let err = Encoding::try_new("ABCDEFGHIJKLMNOPQRSTUVWXYZ23456A").err();
+    /// assert_eq!(Some(AlphabetError::DuplicateSymbol { symbol: b'A', idx: 31 }), err);
+    /// ```
+    pub fn try_new<T>(encoder: T) -> Result<Self, AlphabetError>
+    where
+        T: AsRef<str>,
+    {
+        let encoder = encoder.as_ref();
+        if encoder.len() != 32 {
+            return Err(AlphabetError::InvalidLength(encoder.len()));
+        }
+
+        let mut seen = [false; 128];
+        for (idx, &c) in encoder.as_bytes().iter().enumerate() {
+            if !c.is_ascii() {
+                return Err(AlphabetError::NonAsciiSymbol { idx });
+            }
+            if c == constants::LF || c == constants::CR {
+                return Err(AlphabetError::NewlineSymbol { idx });
+            }
+            if seen[c as usize] {
+                return Err(AlphabetError::DuplicateSymbol { symbol: c, idx });
+            }
+            seen[c as usize] = true;
+        }
+
+        Ok(Self::new(encoder))
+    }
+
+    /// Returns the 32 symbols of the alphabet, in order.
+    pub fn alphabet(&self) -> &[u8] {
+        &self.encode
+    }
+
+    /// Returns the padding character, or `None` if padding is disabled.
+    pub fn padding(&self) -> Option<u8> {
+        self.pad_char
+    }
+
     /// Decodes `src` using the encoding `enc`. It writes at most
     /// [decoded_len(src.len())][Self::decoded_len] bytes
     /// to `dst` and returns the number of bytes
@@ -204,9 +253,10 @@ impl Encoding {
     #[doc = include_str!("../../examples/encoding_encode_to_string.rs")]
     /// ```
     pub fn encode_to_string(&self, src: &[u8]) -> String {
-        let mut out = " ".repeat(self.encoded_len(src.len()));
-        self.encode(unsafe { out.as_bytes_mut() }, src);
-        out
+        let mut out = vec![0u8; self.encoded_len(src.len())];
+        self.encode(&mut out, src);
+        // A padding character above ASCII is the rune of the same value, as it isn't UTF-8.
+        out.into_iter().map(char::from).collect()
     }
 
     /// Returns the length in bytes of the base32 encoding
@@ -225,23 +275,41 @@ impl Encoding {
     /// be contained in the encoding's alphabet and must be a rune equal or
     /// below '\xff'.
     pub fn with_padding(&mut self, padding: Option<u8>) -> &mut Self {
-        if padding.is_none() {
-            self.pad_char = None;
-            return self;
+        match self.try_with_padding(padding) {
+            Ok(_) => self,
+            Err(AlphabetError::PaddingInAlphabet(_)) => panic!("padding contained in alphabet"),
+            Err(_) => panic!("invalid padding"),
         }
+    }
 
-        let c = padding.unwrap();
-        if c == constants::CR || c == constants::LF {
-            panic!("invalid padding")
-        }
+    /// Like [with_padding][Self::with_padding] but returns an error instead of panicking if
+    /// the padding character is invalid, leaving `self` unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use base32::AlphabetError;
+    ///
+    /// let mut enc = *base32::STD_ENCODING;
+    /// let err = enc.try_with_padding(Some(b'A')).err();
+    /// assert_eq!(Some(AlphabetError::PaddingInAlphabet(b'A')), err);
+    ///
+    /// enc.try_with_padding(Some(b'*')).unwrap();
+    /// assert_eq!("MY******", enc.encode_to_string(b"f"));
+    /// ```
+    pub fn try_with_padding(&mut self, padding: Option<u8>) -> Result<&mut Self, AlphabetError> {
+        if let Some(c) = padding {
+            if c == constants::CR || c == constants::LF {
+                return Err(AlphabetError::NewlinePadding);
+            }
 
-        if self.encode.iter().any(|&v| v == c) {
-            panic!("padding contained in alphabet");
+            if self.encode.contains(&c) {
+                return Err(AlphabetError::PaddingInAlphabet(c));
+            }
         }
 
-        self.pad_char = Some(c);
+        self.pad_char = padding;
 
-        self
+        Ok(self)
     }
 
     /// decode_ is like [Self::decode] but returns an additional 'end' value, which
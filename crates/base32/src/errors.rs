@@ -51,3 +51,51 @@ impl Error for CorruptInputError {
         None
     }
 }
+
+/// Error reported when an alphabet or a padding character can't define an
+/// [Encoding](crate::Encoding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// The alphabet isn't 32 bytes long, but of the given length.
+    InvalidLength(usize),
+    /// The alphabet has a non-ASCII character at the byte index `idx`.
+    NonAsciiSymbol { idx: usize },
+    /// The alphabet has `\r` or `\n` at the index `idx`.
+    NewlineSymbol { idx: usize },
+    /// The symbol at the index `idx` already appears earlier in the alphabet.
+    DuplicateSymbol { symbol: u8, idx: usize },
+    /// The padding character is `\r` or `\n`.
+    NewlinePadding,
+    /// The padding character is a symbol of the alphabet.
+    PaddingInAlphabet(u8),
+}
+
+impl Display for AlphabetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::InvalidLength(n) => {
+                write!(f, "encoding alphabet is {} bytes long, not 32", n)
+            }
+            Self::NonAsciiSymbol { idx } => {
+                write!(f, "encoding alphabet has non-ASCII symbol at byte {}", idx)
+            }
+            Self::NewlineSymbol { idx } => {
+                write!(f, "encoding alphabet has newline character at byte {}", idx)
+            }
+            Self::DuplicateSymbol { symbol, idx } => write!(
+                f,
+                "encoding alphabet has duplicate symbol '{}' at byte {}",
+                symbol.escape_ascii(),
+                idx
+            ),
+            Self::NewlinePadding => write!(f, "padding character is a newline"),
+            Self::PaddingInAlphabet(c) => write!(
+                f,
+                "padding character '{}' contained in alphabet",
+                c.escape_ascii()
+            ),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
@@ -0,0 +1,81 @@
+use base32::{AlphabetError, Encoding, STD_ENCODING};
+
+const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+#[test]
+fn try_new() {
+    let enc = Encoding::try_new(ALPHABET).unwrap();
+    assert_eq!("MZXW6YQ=", enc.encode_to_string(b"foob"));
+
+    let test_vector = vec![
+        ("AB".to_string(), AlphabetError::InvalidLength(2)),
+        (format!("{}=", ALPHABET), AlphabetError::InvalidLength(33)),
+        (
+            format!("{}\u{e9}", &ALPHABET[2..]),
+            AlphabetError::NonAsciiSymbol { idx: 30 },
+        ),
+        (
+            format!("{}\r", &ALPHABET[1..]),
+            AlphabetError::NewlineSymbol { idx: 31 },
+        ),
+        (
+            format!("{}2", &ALPHABET[..31]),
+            AlphabetError::DuplicateSymbol {
+                symbol: b'2',
+                idx: 31,
+            },
+        ),
+    ];
+
+    for (i, (alphabet, expect)) in test_vector.into_iter().enumerate() {
+        let got = Encoding::try_new(&alphabet).err();
+        assert_eq!(Some(expect), got, "#{}", i);
+    }
+}
+
+#[test]
+fn try_with_padding() {
+    let test_vector = vec![
+        (b'\n', AlphabetError::NewlinePadding),
+        (b'\r', AlphabetError::NewlinePadding),
+        (b'7', AlphabetError::PaddingInAlphabet(b'7')),
+    ];
+
+    for (i, (padding, expect)) in test_vector.into_iter().enumerate() {
+        let mut enc = *STD_ENCODING;
+        let got = enc.try_with_padding(Some(padding)).err();
+        assert_eq!(Some(expect), got, "#{}", i);
+        assert_eq!(Some(b'='), enc.padding(), "#{} padding changed", i);
+    }
+
+    let mut enc = *STD_ENCODING;
+    enc.try_with_padding(Some(b'.')).unwrap();
+    assert_eq!("MY......", enc.encode_to_string(b"f"));
+    enc.try_with_padding(None).unwrap();
+    assert_eq!("MY", enc.encode_to_string(b"f"));
+}
+
+#[test]
+fn alphabet_error_display() {
+    let test_vector = vec![
+        (
+            AlphabetError::InvalidLength(2),
+            "encoding alphabet is 2 bytes long, not 32",
+        ),
+        (
+            AlphabetError::DuplicateSymbol {
+                symbol: b'A',
+                idx: 1,
+            },
+            "encoding alphabet has duplicate symbol 'A' at byte 1",
+        ),
+        (
+            AlphabetError::PaddingInAlphabet(b'A'),
+            "padding character 'A' contained in alphabet",
+        ),
+    ];
+
+    for (i, (err, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(expect, err.to_string(), "#{}", i);
+    }
+}
@@ -0,0 +1,100 @@
+use base64::{Encoding, STD_ENCODING, URL_ENCODING};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const SIZES: &[usize] = &[8192, 1 << 20];
+
+/// An encoding without fast paths, as a baseline.
+fn scalar_encoding() -> Encoding {
+    Encoding::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789*/")
+}
+
+fn encodings() -> Vec<(&'static str, Encoding)> {
+    vec![
+        ("std", *STD_ENCODING),
+        ("url", *URL_ENCODING),
+        ("scalar", scalar_encoding()),
+    ]
+}
+
+fn data(n: usize) -> Vec<u8> {
+    (0..n).map(|v| (v * 131 + v / 7) as u8).collect()
+}
+
+fn decode(c: &mut Criterion) {
+    for &n in SIZES {
+        for (name, enc) in encodings() {
+            let src = enc.encode_to_string(&data(n)).into_bytes();
+
+            let mut buf = vec![0u8; enc.decoded_len(src.len())];
+            c.bench_function(&format!("decode/{}/{}", name, n), |b| {
+                b.iter(|| {
+                    let _ = enc.decode(&mut buf, black_box(&src));
+                })
+            });
+        }
+    }
+}
+
+fn decode_mime(c: &mut Criterion) {
+    for (name, enc) in encodings() {
+        let mut src = vec![];
+        for line in enc.encode_to_string(&data(1 << 20)).as_bytes().chunks(76) {
+            src.extend_from_slice(line);
+            src.extend_from_slice(b"\r\n");
+        }
+
+        let mut buf = vec![0u8; enc.decoded_len(src.len())];
+        c.bench_function(&format!("decode_mime/{}", name), |b| {
+            b.iter(|| {
+                let _ = enc.decode(&mut buf, black_box(&src));
+            })
+        });
+    }
+}
+
+fn decode_string(c: &mut Criterion) {
+    let data = STD_ENCODING.encode_to_string(&[0u8; 8192]);
+    let mut out = vec![];
+    c.bench_function("decode_string", |b| {
+        b.iter(|| {
+            out = STD_ENCODING.decode_string(black_box(&data)).unwrap();
+        })
+    });
+}
+
+fn encode(c: &mut Criterion) {
+    for &n in SIZES {
+        for (name, enc) in encodings() {
+            let src = data(n);
+
+            let mut buf = vec![0u8; enc.encoded_len(src.len())];
+            c.bench_function(&format!("encode/{}/{}", name, n), |b| {
+                b.iter(|| {
+                    enc.encode(&mut buf, black_box(&src));
+                })
+            });
+        }
+    }
+}
+
+fn encode_to_string(c: &mut Criterion) {
+    let data = [0u8; 8192];
+
+    let mut out = String::new();
+    c.bench_function("encode_to_string", |b| {
+        b.iter(|| {
+            out = STD_ENCODING.encode_to_string(black_box(&data));
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    decode,
+    decode_mime,
+    decode_string,
+    encode,
+    encode_to_string
+);
+criterion_main!(benches);
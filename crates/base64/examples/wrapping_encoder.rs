@@ -0,0 +1,21 @@
+use std::io::Write;
+
+use base64::{LineEnding, LineWrap};
+
+fn main() {
+    let input = b"Man is distinguished, not only by his reason";
+
+    let mut got = vec![];
+
+    let wrap = LineWrap::new(20, LineEnding::Lf);
+    let mut encoder = base64::new_wrapping_encoder(*base64::STD_ENCODING, wrap, &mut got);
+    encoder.write_all(input).unwrap();
+
+    // Flushing encodes the last partial block and ends the last line.
+    encoder.flush().unwrap();
+    std::mem::drop(encoder);
+
+    const EXPECT: &[u8] = b"TWFuIGlzIGRpc3Rpbmd1\naXNoZWQsIG5vdCBvbmx5\nIGJ5IGhpcyByZWFzb24=\n";
+
+    assert_eq!(EXPECT, got);
+}
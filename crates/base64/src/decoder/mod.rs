@@ -1,26 +1,58 @@
-use std::io::{self, ErrorKind, Read};
+use std::collections::VecDeque;
+use std::io::{self, Read};
 use std::ops::Range;
 
-use crate::{CorruptInputError, Encoding};
+use crate::{CorruptInputError, CorruptInputKind, Encoding};
 
 struct Decoder<R>
 where
     R: Read,
 {
     enc: Encoding,
-    r: R,
+    r: NewlineFilteringReader<R>,
     buf: [u8; 1024],
     nbuf: usize,
     outbuf: [u8; 1024 / 4 * 3],
     outbuf_pending: Range<usize>,
     corrupted_err: Option<CorruptInputError>,
+    /// Number of filtered bytes decoded before `buf`.
+    consumed: usize,
+    /// Number of bytes decoded so far.
+    written: usize,
 }
 
+/// NewlineFilteringReader strips newlines from the wrapped reader, remembering where they
+/// were so that offsets in the filtered stream can be mapped back to the original input.
 struct NewlineFilteringReader<R>
 where
     R: Read,
 {
     wrapped: R,
+    /// Number of bytes returned so far.
+    kept: usize,
+    /// Offsets in the filtered stream of the newlines stripped after `discard_before`.
+    newlines: VecDeque<usize>,
+    /// Number of newlines stripped before the last `discard_before` offset.
+    discarded: usize,
+}
+
+impl<R> Decoder<R>
+where
+    R: Read,
+{
+    /// Makes `err`, reported by decoding `buf`, relative to the original input and output.
+    fn localize_err(&self, mut err: CorruptInputError) -> CorruptInputError {
+        err.idx = self.r.input_offset(self.consumed + err.idx);
+        err.written += self.written;
+        err
+    }
+
+    /// Records that `nr` bytes of `buf` have been decoded into `nw` bytes.
+    fn advance(&mut self, nr: usize, nw: usize) {
+        self.consumed += nr;
+        self.written += nw;
+        self.r.discard_before(self.consumed);
+    }
 }
 
 impl<R> Read for Decoder<R>
@@ -56,13 +88,15 @@ where
                 let nw = match self.enc.decode(&mut self.outbuf, &self.buf[..self.nbuf]) {
                     Ok(nw) => nw,
                     Err(err) => {
-                        self.corrupted_err = Some(err);
+                        let localized = self.localize_err(err);
+                        self.corrupted_err = Some(localized);
                         if err.written == 0 {
-                            return Err(new_other_io_err(err));
+                            return Err(new_other_io_err(localized));
                         }
                         err.written
                     }
                 };
+                self.advance(self.nbuf, nw);
                 self.nbuf = 0;
                 let n = builtin::copy(p, &self.outbuf[..nw]);
                 self.outbuf_pending.start = n;
@@ -73,7 +107,16 @@ where
             }
 
             if eof && (self.nbuf > 0) {
-                return Err(ErrorKind::UnexpectedEof.into());
+                // The final fragment is incomplete: tell why the way decoding it on its own
+                // would, such as missing padding after "Yg=".
+                let err = self
+                    .enc
+                    .decode(&mut self.outbuf, &self.buf[..self.nbuf])
+                    .err()
+                    .unwrap_or_else(|| CorruptInputError::new(CorruptInputKind::InvalidLength, 0));
+                let err = self.localize_err(err);
+                self.corrupted_err = Some(err);
+                return Err(new_other_io_err(err));
             }
 
             return Ok(0);
@@ -83,25 +126,27 @@ where
         let nr = self.nbuf / 4 * 4;
         let nw = self.nbuf / 4 * 3;
         let (written, nr) = if nw > p.len() {
-            match self.enc.decode(&mut self.outbuf, &self.buf[..nr]) {
-                Ok(v) => {
-                    self.outbuf_pending.start = builtin::copy(p, &self.outbuf[..v]);
-                    self.outbuf_pending.end = v;
-                    (self.outbuf_pending.start, nr)
-                }
+            let (v, nr) = match self.enc.decode(&mut self.outbuf, &self.buf[..nr]) {
+                Ok(v) => (v, nr),
                 Err(err) => {
-                    self.corrupted_err = Some(err);
+                    self.corrupted_err = Some(self.localize_err(err));
                     (err.written, err.idx)
                 }
-            }
+            };
+            self.outbuf_pending.start = builtin::copy(p, &self.outbuf[..v]);
+            self.outbuf_pending.end = v;
+            self.advance(nr, v);
+            (self.outbuf_pending.start, nr)
         } else {
-            match self.enc.decode(p, &self.buf[..nr]) {
+            let (v, nr) = match self.enc.decode(p, &self.buf[..nr]) {
                 Ok(v) => (v, nr),
                 Err(err) => {
-                    self.corrupted_err = Some(err);
+                    self.corrupted_err = Some(self.localize_err(err));
                     (err.written, err.idx)
                 }
-            }
+            };
+            self.advance(nr, v);
+            (v, nr)
         };
 
         self.nbuf -= nr;
@@ -111,6 +156,34 @@ where
     }
 }
 
+impl<R> NewlineFilteringReader<R>
+where
+    R: Read,
+{
+    fn new(wrapped: R) -> Self {
+        Self {
+            wrapped,
+            kept: 0,
+            newlines: VecDeque::new(),
+            discarded: 0,
+        }
+    }
+
+    /// Returns the offset in the original input of the byte at offset `pos` in the filtered
+    /// stream, which must not be before the last `discard_before` offset.
+    fn input_offset(&self, pos: usize) -> usize {
+        pos + self.discarded + self.newlines.iter().take_while(|&&v| v <= pos).count()
+    }
+
+    /// Forgets the newlines before the filtered offset `pos`, which won't be asked about.
+    fn discard_before(&mut self, pos: usize) {
+        while matches!(self.newlines.front(), Some(&v) if v <= pos) {
+            self.newlines.pop_front();
+            self.discarded += 1;
+        }
+    }
+}
+
 impl<R> Read for NewlineFilteringReader<R>
 where
     R: Read,
@@ -122,6 +195,7 @@ where
             let mut offset = 0usize; // 1st non-ok index
             for i in 0..n {
                 if std::matches!(p[i], crate::CR | crate::LF) {
+                    self.newlines.push_back(self.kept + offset);
                     continue;
                 }
                 if i != offset {
@@ -130,6 +204,7 @@ where
                 offset += 1;
             }
             if offset > 0 {
+                self.kept += offset;
                 return Ok(offset);
             }
             n = self.wrapped.read(p)?; // Previous buffer entirely whitespace, read again
@@ -140,18 +215,24 @@ where
 }
 
 /// Constructs a new base64 stream decoder.
+///
+/// Corrupt input is reported by an [io::Error] of kind [Other][io::ErrorKind::Other] wrapping
+/// a [CorruptInputError], whose offset counts the newlines skipped from the start of `r` and
+/// whose number of bytes written counts all the bytes decoded before.
 pub fn new_decoder<R>(enc: Encoding, r: R) -> impl Read
 where
     R: Read,
 {
     Decoder {
         enc,
-        r: NewlineFilteringReader { wrapped: r },
+        r: NewlineFilteringReader::new(r),
         buf: [0u8; 1024],
         nbuf: 0,
         outbuf: [0u8; 1024 / 4 * 3],
         outbuf_pending: Range::default(),
         corrupted_err: None,
+        consumed: 0,
+        written: 0,
     }
 }
 
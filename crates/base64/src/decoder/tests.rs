@@ -22,9 +22,7 @@ bqbPb06551Y4
     ];
 
     for c in test_vector {
-        let mut r = super::NewlineFilteringReader {
-            wrapped: c.as_bytes(),
-        };
+        let mut r = super::NewlineFilteringReader::new(c.as_bytes());
 
         let mut got = String::new();
         r.read_to_string(&mut got).unwrap();
@@ -3,7 +3,7 @@ use std::{
     ops::Range,
 };
 
-use crate::Encoding;
+use crate::{Encoding, LineWrap, LineWriter};
 
 struct Encoder<W>
 where
@@ -105,15 +105,13 @@ where
             self.out_pending.start += self.w.write(&self.out[self.out_pending.clone()])?;
         }
 
-        if self.nbuf == 0 {
-            return Ok(());
-        }
-
-        self.enc.encode(&mut self.out, &self.buf[..self.nbuf]);
+        if self.nbuf > 0 {
+            self.enc.encode(&mut self.out, &self.buf[..self.nbuf]);
 
-        let n = self.enc.encoded_len(self.nbuf);
-        self.w.write_all(&self.out[..n])?;
-        self.nbuf = 0;
+            let n = self.enc.encoded_len(self.nbuf);
+            self.w.write_all(&self.out[..n])?;
+            self.nbuf = 0;
+        }
 
         self.w.flush()
     }
@@ -141,3 +139,20 @@ where
         out_pending: Range::default(),
     }
 }
+
+/// Returns a new base64 stream encoder like [new_encoder], breaking the encoding into lines
+/// as told by `wrap`, such as [LineWrap::MIME] or [LineWrap::PEM]. Every line, the last
+/// one included, is followed by the line ending; nothing is written for an empty input.
+///
+/// The last line is ended when the returned writer is flushed or dropped.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../examples/wrapping_encoder.rs")]
+/// ```
+pub fn new_wrapping_encoder<W>(enc: Encoding, wrap: LineWrap, w: W) -> impl Write
+where
+    W: Write,
+{
+    new_encoder(enc, LineWriter::new(wrap, w))
+}
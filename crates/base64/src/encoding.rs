@@ -1,4 +1,5 @@
-use crate::{constants, CorruptInputError};
+use crate::simd::{self, Alphabet};
+use crate::{constants, AlphabetError, CorruptInputError, CorruptInputKind};
 use binary::{BigEndian, ByteOrder};
 
 use lazy_static::lazy_static;
@@ -62,6 +63,8 @@ pub struct Encoding {
     encode: [u8; 64],
     decode_map: [u8; 256],
     strict: bool,
+    /// The alphabet if it has fast paths.
+    fast: Option<Alphabet>,
 }
 
 impl Encoding {
@@ -70,6 +73,9 @@ impl Encoding {
     /// or CR / LF ('\r', '\n').
     /// The resulting `Encoding` uses the default padding character ('='),
     /// which may be changed or disabled via [with_padding][Self::with_padding].
+    ///
+    /// Duplicated symbols aren't checked, the later one being decoded. Use
+    /// [try_new][Self::try_new] for alphabets that aren't known to be valid.
     pub fn new(encoder: &str) -> Self {
         if encoder.len() != 64 {
             panic!("encoding alphabet is not 64-bytes long")
@@ -83,9 +89,47 @@ impl Encoding {
             panic!("encoding alphabet contains newline character");
         }
 
+        Self::new_unchecked(encoder.as_bytes())
+    }
+
+    /// Like [new][Self::new] but returns an error instead of panicking if the alphabet
+    /// isn't made of 64 distinct ASCII symbols other than CR and LF.
+    ///
+    /// # Example
+    /// ```
+    /// use base64::{AlphabetError, Encoding};
+    ///
+    /// let alphabet = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+A";
+    /// let err = Encoding::try_new(alphabet).err();
+    /// assert_eq!(Some(AlphabetError::DuplicateSymbol { symbol: b'A', idx: 63 }), err);
+    /// ```
+    pub fn try_new(encoder: &str) -> Result<Self, AlphabetError> {
+        let encoder = encoder.as_bytes();
+        if encoder.len() != 64 {
+            return Err(AlphabetError::InvalidLength(encoder.len()));
+        }
+
+        let mut seen = [false; 128];
+        for (idx, &c) in encoder.iter().enumerate() {
+            if !c.is_ascii() {
+                return Err(AlphabetError::NonAsciiSymbol { idx });
+            }
+            if c == constants::LF || c == constants::CR {
+                return Err(AlphabetError::NewlineSymbol { idx });
+            }
+            if seen[c as usize] {
+                return Err(AlphabetError::DuplicateSymbol { symbol: c, idx });
+            }
+            seen[c as usize] = true;
+        }
+
+        Ok(Self::new_unchecked(encoder))
+    }
+
+    fn new_unchecked(encoder: &[u8]) -> Self {
         let encode = {
             let mut v = [0u8; 64];
-            v.copy_from_slice(encoder.as_bytes());
+            v.copy_from_slice(encoder);
             v
         };
 
@@ -100,6 +144,7 @@ impl Encoding {
         };
 
         Self {
+            fast: Alphabet::new(&encode),
             encode,
             decode_map,
             pad_char: Some(constants::STD_PADDING),
@@ -107,6 +152,21 @@ impl Encoding {
         }
     }
 
+    /// Returns the 64 symbols of the alphabet, in order.
+    pub fn alphabet(&self) -> &[u8] {
+        &self.encode
+    }
+
+    /// Tells whether strict decoding is enabled, as by [strict][Self::strict].
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the padding character, or `None` if padding is disabled.
+    pub fn padding(&self) -> Option<u8> {
+        self.pad_char
+    }
+
     /// Decodes `src` using the encoding `self`. It writes at most
     /// [decoded_len(src.len())][Self::decoded_len] bytes to `dst` and returns the number of bytes
     /// written. If `src` contains invalid base64 data, it will return the
@@ -119,8 +179,76 @@ impl Encoding {
     #[doc = include_str!("../examples/decode.rs")]
     /// ```
     pub fn decode(&self, dst: &mut [u8], src: &[u8]) -> Result<usize, CorruptInputError> {
+        self.decode_prefix(dst, src, false).map(|(n, _)| n)
+    }
+
+    /// Decodes `src` like [decode][Self::decode], appending the decoded bytes to `dst` and
+    /// returning their number. On error, `dst` is left with the bytes decoded before the
+    /// corruption appended.
+    ///
+    /// # Example
+    /// ```
+    /// let mut dst = b"decoded: ".to_vec();
+    /// base64::STD_ENCODING.append_decode(&mut dst, b"Zm9vYmFy").unwrap();
+    /// assert_eq!(b"decoded: foobar".to_vec(), dst);
+    /// ```
+    pub fn append_decode(&self, dst: &mut Vec<u8>, src: &[u8]) -> Result<usize, CorruptInputError> {
+        let n = dst.len();
+        dst.resize(n + self.decoded_len(src.len()), 0);
+        match self.decode(&mut dst[n..], src) {
+            Ok(written) => {
+                dst.truncate(n + written);
+                Ok(written)
+            }
+            Err(err) => {
+                dst.truncate(n + err.written);
+                Err(err)
+            }
+        }
+    }
+
+    /// Decodes `buf` like [decode][Self::decode], overwriting it with the decoded bytes,
+    /// and returns them as a prefix of `buf`.
+    ///
+    /// On error, the content of `buf` is unspecified, the `written` decoded bytes aside.
+    ///
+    /// # Example
+    /// ```
+    /// let mut buf = b"Zm9v\nYmFy".to_vec();
+    /// let decoded = base64::STD_ENCODING.decode_in_place(&mut buf).unwrap();
+    /// assert_eq!(b"foobar", decoded);
+    /// ```
+    pub fn decode_in_place<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], CorruptInputError> {
+        // The decoding is never longer than the input it comes from, so decoding through a
+        // small buffer never overwrites input still to be decoded.
+        let mut out = [0u8; 1024];
+        let (mut written, mut consumed) = (0, 0);
+        while consumed < buf.len() {
+            let (nw, nr) = self
+                .decode_prefix(&mut out, &buf[consumed..], true)
+                .map_err(|mut err| {
+                    err.idx += consumed;
+                    err.written += written;
+                    err
+                })?;
+            buf[written..written + nw].copy_from_slice(&out[..nw]);
+            written += nw;
+            consumed += nr;
+        }
+
+        Ok(&buf[..written])
+    }
+
+    /// Decodes `src` into `dst`, returning the number of bytes written and consumed. If
+    /// `partial` is true, the decoding stops at the last quantum `dst` has room for.
+    fn decode_prefix(
+        &self,
+        dst: &mut [u8],
+        src: &[u8],
+        partial: bool,
+    ) -> Result<(usize, usize), CorruptInputError> {
         if src.is_empty() {
-            return Ok(0);
+            return Ok((0, 0));
         }
 
         let (ndst, nsrc) = (dst.len(), src.len());
@@ -133,6 +261,10 @@ impl Encoding {
             err
         };
 
+        let (nr, nw) = self.decode_fast(dst, src);
+        src = &src[nr..];
+        dst = &mut dst[nw..];
+
         if usize::BITS == 64 {
             while src.len() >= 8 && dst.len() >= 8 {
                 match assemble64(
@@ -156,6 +288,11 @@ impl Encoding {
                             .map_err(|err| localize_err(err, dst.len(), src.len()))?;
                         src = &src[nr..];
                         dst = &mut dst[nw..];
+
+                        // Past newlines, the fast paths may apply again.
+                        let (nr, nw) = self.decode_fast(dst, src);
+                        src = &src[nr..];
+                        dst = &mut dst[nw..];
                     }
                 }
             }
@@ -184,7 +321,7 @@ impl Encoding {
         }
 
         // @dev whether 'if' is ok?
-        while !src.is_empty() {
+        while !src.is_empty() && (!partial || dst.len() >= 3) {
             let (nr, nw) = self
                 .decode_quantum(dst, src)
                 .map_err(|err| localize_err(err, dst.len(), src.len()))?;
@@ -192,7 +329,7 @@ impl Encoding {
             dst = &mut dst[nw..];
         }
 
-        Ok(ndst - dst.len())
+        Ok((ndst - dst.len(), nsrc - src.len()))
     }
 
     /// Returns the maximum length in bytes of the decoded data
@@ -235,8 +372,12 @@ impl Encoding {
             return;
         }
 
-        let mut chunks = src.chunks_exact(3);
-        let mut dst_idx = 0;
+        let (consumed, mut dst_idx) = match self.fast {
+            Some(a) => simd::encode(a, dst, src),
+            None => (0, 0),
+        };
+
+        let mut chunks = src[consumed..].chunks_exact(3);
         for chunk in chunks.by_ref() {
             let v = ((chunk[0] as usize) << 16) | ((chunk[1] as usize) << 8) | (chunk[2] as usize);
 
@@ -274,6 +415,20 @@ impl Encoding {
         }
     }
 
+    /// Appends the base64 encoding of `src` to `dst`.
+    ///
+    /// # Example
+    /// ```
+    /// let mut dst = b"encoded: ".to_vec();
+    /// base64::STD_ENCODING.append_encode(&mut dst, b"foobar");
+    /// assert_eq!(b"encoded: Zm9vYmFy".to_vec(), dst);
+    /// ```
+    pub fn append_encode(&self, dst: &mut Vec<u8>, src: &[u8]) {
+        let n = dst.len();
+        dst.resize(n + self.encoded_len(src.len()), 0);
+        self.encode(&mut dst[n..], src);
+    }
+
     /// Returns the length in bytes of the base64 encoding
     /// of an input buffer of length `n`.
     pub fn encoded_len(&self, n: usize) -> usize {
@@ -317,22 +472,41 @@ impl Encoding {
     ///
     /// To switch back to no padding, just use [without_padding][Self::without_padding].
     pub fn with_padding(&mut self, padding: char) -> &Self {
+        match self.try_with_padding(padding) {
+            Ok(_) => self,
+            Err(AlphabetError::PaddingInAlphabet(_)) => panic!("padding contained in alphabet"),
+            Err(_) => panic!("invalid padding"),
+        }
+    }
+
+    /// Like [with_padding][Self::with_padding] but returns an error instead of panicking if
+    /// the padding character is invalid, leaving `self` unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use base64::AlphabetError;
+    ///
+    /// let mut enc = *base64::STD_ENCODING;
+    /// assert_eq!(Some(AlphabetError::PaddingInAlphabet(b'+')), enc.try_with_padding('+').err());
+    /// assert_eq!("Zg**", enc.try_with_padding('*').unwrap().encode_to_string(b"f"));
+    /// ```
+    pub fn try_with_padding(&mut self, padding: char) -> Result<&Self, AlphabetError> {
         if !padding.is_ascii() {
-            panic!("invalid padding")
+            return Err(AlphabetError::NonAsciiPadding(padding));
         }
 
         let c = padding as u8;
         if c == constants::CR || c == constants::LF {
-            panic!("invalid padding")
+            return Err(AlphabetError::NewlinePadding);
         }
 
-        if self.encode.iter().any(|&x| x == c) {
-            panic!("padding contained in alphabet");
+        if self.encode.contains(&c) {
+            return Err(AlphabetError::PaddingInAlphabet(c));
         }
 
         self.pad_char = Some(c);
 
-        self
+        Ok(self)
     }
 
     /// Creates a new encoding identical to `self` except without padding.
@@ -344,6 +518,15 @@ impl Encoding {
         self
     }
 
+    /// Decodes the longest prefix of `src` the fast paths of the alphabet can, if any,
+    /// returning the number of bytes consumed and written.
+    fn decode_fast(&self, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+        match self.fast {
+            Some(a) => simd::decode(a, dst, src),
+            None => (0, 0),
+        }
+    }
+
     fn decode_quantum(
         &self,
         dst: &mut [u8],
@@ -353,6 +536,8 @@ impl Encoding {
 
         let mut src_idx = 0usize;
         let mut j = 0usize;
+        // Index of the first symbol of the quantum.
+        let mut start = 0usize;
         let dlen = loop {
             if j >= dbuf.len() {
                 break j;
@@ -361,9 +546,17 @@ impl Encoding {
             if src.len() == src_idx {
                 match j {
                     0 => return Ok((src_idx, 0)),
-                    1 => return Err(CorruptInputError::new(src, src_idx - 1, 0)),
+                    1 => {
+                        return Err(CorruptInputError::new(
+                            CorruptInputKind::InvalidLength,
+                            start,
+                        ))
+                    }
                     _ if self.pad_char.is_some() => {
-                        return Err(CorruptInputError::new(src, src_idx - 2, 0))
+                        return Err(CorruptInputError::new(
+                            CorruptInputKind::InvalidLength,
+                            start,
+                        ))
                     }
                     _ => {}
                 }
@@ -374,6 +567,9 @@ impl Encoding {
             src_idx += 1;
 
             if self.decode_map[c as usize] != 0xff {
+                if j == 0 {
+                    start = src_idx - 1;
+                }
                 dbuf[j] = self.decode_map[c as usize];
                 j += 1;
                 continue;
@@ -384,12 +580,21 @@ impl Encoding {
             }
 
             if self.pad_char != Some(c) {
-                return Err(CorruptInputError::new(src, src_idx - 1, 0));
+                return Err(CorruptInputError::new(
+                    CorruptInputKind::InvalidByte(c),
+                    src_idx - 1,
+                ));
             }
 
             // We've reached the end and there's padding
             match j {
-                0 | 1 => return Err(CorruptInputError::new(src, src_idx - 1, 0)), // at most 2 padding char
+                // at most 2 padding char
+                0 | 1 => {
+                    return Err(CorruptInputError::new(
+                        CorruptInputKind::InvalidPadding,
+                        src_idx - 1,
+                    ))
+                }
                 2 => {
                     // "==" is expected, the first "=" is already consumed.
                     // skip over newlines
@@ -401,11 +606,17 @@ impl Encoding {
 
                     if src_idx == src.len() {
                         // not enough padding
-                        return Err(CorruptInputError::new(src, src.len(), 0));
+                        return Err(CorruptInputError::new(
+                            CorruptInputKind::InvalidPadding,
+                            src.len(),
+                        ));
                     }
 
                     if self.pad_char != Some(src[src_idx]) {
-                        return Err(CorruptInputError::new(src, src_idx - 1, 0));
+                        return Err(CorruptInputError::new(
+                            CorruptInputKind::InvalidPadding,
+                            src_idx - 1,
+                        ));
                         // incorrect padding
                     }
 
@@ -421,7 +632,10 @@ impl Encoding {
             }
 
             if src_idx < src.len() {
-                return Err(CorruptInputError::new(src, src_idx, 0));
+                return Err(CorruptInputError::new(
+                    CorruptInputKind::InvalidPadding,
+                    src_idx,
+                ));
             }
 
             break j;
@@ -444,14 +658,20 @@ impl Encoding {
         if dlen >= 3 {
             dst[1] = dbuf[1];
             if self.strict && dbuf[2] != 0 {
-                return Err(CorruptInputError::new(src, src_idx - 1, 0));
+                return Err(CorruptInputError::new(
+                    CorruptInputKind::NonCanonicalTrailingBits,
+                    src_idx - 1,
+                ));
             }
             dbuf[1] = 0;
         }
         if dlen >= 2 {
             dst[0] = dbuf[0];
             if self.strict && ((dbuf[1] != 0) || (dbuf[2] != 0)) {
-                return Err(CorruptInputError::new(src, src_idx - 2, 0));
+                return Err(CorruptInputError::new(
+                    CorruptInputKind::NonCanonicalTrailingBits,
+                    src_idx - 2,
+                ));
             }
         }
 
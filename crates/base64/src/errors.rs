@@ -1,40 +1,57 @@
 use std::{error::Error, fmt::Display};
 
 /// Error occurs during decoding.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CorruptInputError {
-    /// Corrupted character if any.
-    pub c: Option<u8>,
-    /// Index of the corrupted character.
+    /// What is wrong with the input.
+    pub kind: CorruptInputKind,
+    /// Index of the corruption in the input.
     pub idx: usize,
     /// Number of bytes has been written to destination buffer.
     pub written: usize,
 }
 
+/// Kind of corruption reported by [CorruptInputError].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorruptInputKind {
+    /// The byte is neither a symbol of the alphabet, the padding character nor a newline.
+    InvalidByte(u8),
+    /// The padding is misplaced, incomplete or followed by data.
+    InvalidPadding,
+    /// The input ends in the middle of a quantum, as for a missing padding.
+    InvalidLength,
+    /// The bits of the last symbol beyond the decoded data aren't zero, which only
+    /// [strict][crate::Encoding::strict] decoding rejects.
+    NonCanonicalTrailingBits,
+}
+
 impl CorruptInputError {
-    pub(crate) fn new(src: &[u8], idx: usize, written: usize) -> Self {
-        let c = src.get(idx).copied();
-        Self { c, idx, written }
+    pub(crate) fn new(kind: CorruptInputKind, idx: usize) -> Self {
+        Self {
+            kind,
+            idx,
+            written: 0,
+        }
     }
 }
 
 impl Display for CorruptInputError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(c) = self.c {
-            write!(
-                f,
-                "illegal base64 data '{}' at input byte {} after writing {} bytes",
-                c.escape_ascii(),
-                self.idx,
-                self.written
-            )
-        } else {
-            write!(
-                f,
-                "illegal base64 data at input byte {} after writing {} bytes",
-                self.idx, self.written
-            )
+        match self.kind {
+            CorruptInputKind::InvalidByte(c) => {
+                write!(f, "illegal base64 byte '{}'", c.escape_ascii())?
+            }
+            CorruptInputKind::InvalidPadding => write!(f, "illegal base64 padding")?,
+            CorruptInputKind::InvalidLength => write!(f, "truncated base64 data")?,
+            CorruptInputKind::NonCanonicalTrailingBits => {
+                write!(f, "non-zero base64 trailing bits")?
+            }
         }
+        write!(
+            f,
+            " at input byte {} after writing {} bytes",
+            self.idx, self.written
+        )
     }
 }
 
@@ -51,3 +68,54 @@ impl Error for CorruptInputError {
         None
     }
 }
+
+/// Error reported when an alphabet or a padding character can't define an
+/// [Encoding](crate::Encoding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// The alphabet isn't 64 bytes long, but of the given length.
+    InvalidLength(usize),
+    /// The alphabet has a non-ASCII character at the byte index `idx`.
+    NonAsciiSymbol { idx: usize },
+    /// The alphabet has `\r` or `\n` at the index `idx`.
+    NewlineSymbol { idx: usize },
+    /// The symbol at the index `idx` already appears earlier in the alphabet.
+    DuplicateSymbol { symbol: u8, idx: usize },
+    /// The padding character isn't ASCII.
+    NonAsciiPadding(char),
+    /// The padding character is `\r` or `\n`.
+    NewlinePadding,
+    /// The padding character is a symbol of the alphabet.
+    PaddingInAlphabet(u8),
+}
+
+impl Display for AlphabetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::InvalidLength(n) => {
+                write!(f, "encoding alphabet is {} bytes long, not 64", n)
+            }
+            Self::NonAsciiSymbol { idx } => {
+                write!(f, "encoding alphabet has non-ASCII symbol at byte {}", idx)
+            }
+            Self::NewlineSymbol { idx } => {
+                write!(f, "encoding alphabet has newline character at byte {}", idx)
+            }
+            Self::DuplicateSymbol { symbol, idx } => write!(
+                f,
+                "encoding alphabet has duplicate symbol '{}' at byte {}",
+                symbol.escape_ascii(),
+                idx
+            ),
+            Self::NonAsciiPadding(c) => write!(f, "padding character {:?} isn't ASCII", c),
+            Self::NewlinePadding => write!(f, "padding character is a newline"),
+            Self::PaddingInAlphabet(c) => write!(
+                f,
+                "padding character '{}' contained in alphabet",
+                c.escape_ascii()
+            ),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
@@ -7,9 +7,12 @@ mod decoder;
 mod encoder;
 mod encoding;
 mod errors;
+mod simd;
+mod wrap;
 
 pub use constants::*;
 pub use decoder::*;
 pub use encoder::*;
 pub use encoding::*;
 pub use errors::*;
+pub use wrap::*;
@@ -0,0 +1,79 @@
+//! Fast paths for the standard and URL alphabets, encoding and decoding many quanta at once.
+//!
+//! They only handle whole blocks of data symbols, and stop at the first block holding
+//! anything else (newlines, padding or invalid bytes), leaving it to the scalar code which
+//! reports errors precisely.
+
+use crate::constants;
+
+mod swar;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+/// The symbols of an alphabet made of `A-Z`, `a-z` and `0-9` followed by two others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Alphabet {
+    c62: u8,
+    c63: u8,
+}
+
+impl Alphabet {
+    /// Returns the alphabet of `encode` if it's the standard or URL one.
+    pub(crate) fn new(encode: &[u8; 64]) -> Option<Self> {
+        let known = [constants::ENCODE_STD, constants::ENCODE_URL];
+        if !known.iter().any(|v| v.as_bytes() == encode) {
+            return None;
+        }
+
+        Some(Self {
+            c62: encode[62],
+            c63: encode[63],
+        })
+    }
+}
+
+/// Encodes a prefix of `src` of whole 3-byte groups into `dst`, returning the number of bytes
+/// consumed and written.
+pub(crate) fn encode(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    #[cfg(target_arch = "x86_64")]
+    let (nr, nw) = x86::encode(a, dst, src);
+    #[cfg(not(target_arch = "x86_64"))]
+    let (nr, nw) = (0, 0);
+
+    let (r, w) = swar::encode(a, &mut dst[nw..], &src[nr..]);
+    (nr + r, nw + w)
+}
+
+/// Decodes a prefix of `src` of whole 4-symbol quanta into `dst`, returning the number of
+/// bytes consumed and written. Newlines between the quanta, as ending wrapped lines, are
+/// skipped.
+pub(crate) fn decode(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    let (mut nr, mut nw) = (0, 0);
+    loop {
+        let (r, w) = decode_quanta(a, &mut dst[nw..], &src[nr..]);
+        nr += r;
+        nw += w;
+
+        let newlines = src[nr..]
+            .iter()
+            .take_while(|&&c| c == constants::CR || c == constants::LF)
+            .count();
+        if r == 0 || newlines == 0 {
+            return (nr, nw);
+        }
+        nr += newlines;
+    }
+}
+
+fn decode_quanta(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    #[cfg(target_arch = "x86_64")]
+    let (nr, nw) = x86::decode(a, dst, src);
+    #[cfg(not(target_arch = "x86_64"))]
+    let (nr, nw) = (0, 0);
+
+    let (r, w) = swar::decode(a, &mut dst[nw..], &src[nr..]);
+    (nr + r, nw + w)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,108 @@
+//! Word-at-a-time paths, handling 8 symbols in a `u64` by means of tables of pre-shifted
+//! values, so that a whole word is validated by a single test.
+
+use crate::constants;
+
+use super::Alphabet;
+
+/// Set in the decoded values of the bytes out of the alphabet, above the 24 data bits.
+const INVALID: u32 = 0xff00_0000;
+
+struct Tables {
+    /// The symbols of all 12-bit values, two by two.
+    encode: [u16; 4096],
+    /// The values of the symbols, shifted to their place in a quantum.
+    decode: [[u32; 256]; 4],
+}
+
+impl Tables {
+    const fn new(alphabet: &str) -> Self {
+        let alphabet = alphabet.as_bytes();
+
+        let mut encode = [0u16; 4096];
+        let mut i = 0;
+        while i < encode.len() {
+            encode[i] = (alphabet[i >> 6] as u16) << 8 | alphabet[i & 0x3f] as u16;
+            i += 1;
+        }
+
+        let mut decode = [[INVALID; 256]; 4];
+        let mut i = 0;
+        while i < alphabet.len() {
+            let c = alphabet[i] as usize;
+            let mut j = 0;
+            while j < decode.len() {
+                decode[j][c] = (i as u32) << (18 - 6 * j);
+                j += 1;
+            }
+            i += 1;
+        }
+
+        Self { encode, decode }
+    }
+
+    fn of(a: Alphabet) -> &'static Self {
+        static STD: Tables = Tables::new(constants::ENCODE_STD);
+        static URL: Tables = Tables::new(constants::ENCODE_URL);
+
+        if a.c62 == STD.encode[62] as u8 {
+            &STD
+        } else {
+            &URL
+        }
+    }
+
+    fn decode_quantum(&self, src: &[u8]) -> u32 {
+        self.decode[0][src[0] as usize]
+            | self.decode[1][src[1] as usize]
+            | self.decode[2][src[2] as usize]
+            | self.decode[3][src[3] as usize]
+    }
+}
+
+pub(super) fn encode(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    let t = Tables::of(a);
+    let (mut i, mut j) = (0, 0);
+    // Reads 8 bytes to encode 6 of them.
+    while src.len() - i >= 8 && dst.len() - j >= 8 {
+        let n = u64::from_be_bytes(src[i..i + 8].try_into().expect("8 bytes"));
+        let v = (t.encode[(n >> 52) as usize] as u64) << 48
+            | (t.encode[(n >> 40) as usize & 0xfff] as u64) << 32
+            | (t.encode[(n >> 28) as usize & 0xfff] as u64) << 16
+            | t.encode[(n >> 16) as usize & 0xfff] as u64;
+        dst[j..j + 8].copy_from_slice(&v.to_be_bytes());
+        i += 6;
+        j += 8;
+    }
+
+    (i, j)
+}
+
+pub(super) fn decode(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    let t = Tables::of(a);
+    let (mut i, mut j) = (0, 0);
+    // Writes 8 bytes to decode 6 of them.
+    while src.len() - i >= 8 && dst.len() - j >= 8 {
+        let hi = t.decode_quantum(&src[i..]);
+        let lo = t.decode_quantum(&src[i + 4..]);
+        if (hi | lo) & INVALID != 0 {
+            break;
+        }
+        let v = (hi as u64) << 40 | (lo as u64) << 16;
+        dst[j..j + 8].copy_from_slice(&v.to_be_bytes());
+        i += 8;
+        j += 6;
+    }
+
+    while src.len() - i >= 4 && dst.len() - j >= 3 {
+        let v = t.decode_quantum(&src[i..]);
+        if v & INVALID != 0 {
+            break;
+        }
+        dst[j..j + 3].copy_from_slice(&v.to_be_bytes()[1..]);
+        i += 4;
+        j += 3;
+    }
+
+    (i, j)
+}
@@ -0,0 +1,185 @@
+use crate::constants;
+
+use super::Alphabet;
+
+type Path = fn(Alphabet, &mut [u8], &[u8]) -> (usize, usize);
+
+/// Returns the encoding and decoding paths the CPU supports, by name.
+fn paths() -> Vec<(&'static str, Path, Path)> {
+    let mut v: Vec<(&'static str, Path, Path)> =
+        vec![("swar", super::swar::encode, super::swar::decode)];
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        use super::x86;
+
+        if is_x86_feature_detected!("ssse3") {
+            v.push((
+                "ssse3",
+                |a, dst, src| unsafe { x86::encode_ssse3(a, dst, src) },
+                |a, dst, src| unsafe { x86::decode_ssse3(a, dst, src) },
+            ));
+        }
+        if is_x86_feature_detected!("avx2") {
+            v.push((
+                "avx2",
+                |a, dst, src| unsafe { x86::encode_avx2(a, dst, src) },
+                |a, dst, src| unsafe { x86::decode_avx2(a, dst, src) },
+            ));
+        }
+    }
+
+    v.push(("dispatch", super::encode, super::decode));
+    v
+}
+
+fn alphabet_of(alphabet: &str) -> Option<Alphabet> {
+    Alphabet::new(alphabet.as_bytes().try_into().unwrap())
+}
+
+/// Encodes `src` one quantum at a time.
+fn encode_ref(alphabet: &str, src: &[u8]) -> Vec<u8> {
+    let alphabet = alphabet.as_bytes();
+    let mut out = vec![];
+    for c in src.chunks_exact(3) {
+        let v = (c[0] as usize) << 16 | (c[1] as usize) << 8 | c[2] as usize;
+        out.extend([18, 12, 6, 0].iter().map(|&s| alphabet[(v >> s) & 0x3f]));
+    }
+    out
+}
+
+fn data(n: usize) -> Vec<u8> {
+    // A xorshift generator, covering all byte values.
+    let mut x = 0x2545_f491_4f6c_dd1du64;
+    (0..n)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x as u8
+        })
+        .collect()
+}
+
+#[test]
+fn alphabet() {
+    assert!(alphabet_of(constants::ENCODE_STD).is_some());
+    assert!(alphabet_of(constants::ENCODE_URL).is_some());
+
+    let funny = constants::ENCODE_STD.replace('+', "*");
+    assert_eq!(None, alphabet_of(&funny));
+}
+
+#[test]
+fn encode() {
+    for alphabet in [constants::ENCODE_STD, constants::ENCODE_URL] {
+        let a = alphabet_of(alphabet).unwrap();
+        for (name, encode, _) in paths() {
+            for n in 0..200 {
+                let src = data(n);
+                let mut dst = vec![0u8; n / 3 * 4];
+                let (nr, nw) = encode(a, &mut dst, &src);
+
+                assert!(
+                    n - nr < 16,
+                    "{} encode of {} bytes stopped at {}",
+                    name,
+                    n,
+                    nr
+                );
+                assert_eq!(0, nr % 3, "{} encode of {} bytes", name, n);
+                assert_eq!(nr / 3 * 4, nw, "{} encode of {} bytes", name, n);
+                assert_eq!(
+                    encode_ref(alphabet, &src[..nr]),
+                    &dst[..nw],
+                    "{} encode of {} bytes",
+                    name,
+                    n
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn decode() {
+    for alphabet in [constants::ENCODE_STD, constants::ENCODE_URL] {
+        let a = alphabet_of(alphabet).unwrap();
+        for (name, _, decode) in paths() {
+            for n in 0..200 {
+                let expect = data(n / 4 * 3);
+                let src = encode_ref(alphabet, &expect);
+                let mut dst = vec![0u8; src.len()];
+                let (nr, nw) = decode(a, &mut dst, &src);
+
+                assert!(
+                    src.len() - nr < 16,
+                    "{} decode of {} stopped at {}",
+                    name,
+                    n,
+                    nr
+                );
+                assert_eq!(0, nr % 4, "{} decode of {} symbols", name, n);
+                assert_eq!(nr / 4 * 3, nw, "{} decode of {} symbols", name, n);
+                assert_eq!(&expect[..nw], &dst[..nw], "{} decode of {}", name, n);
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_stops_at_non_symbol() {
+    let alphabet = constants::ENCODE_STD;
+    let a = alphabet_of(alphabet).unwrap();
+    let src = encode_ref(alphabet, &data(96));
+
+    for (name, _, decode) in paths() {
+        for i in 0..src.len() {
+            // The other alphabet, the padding, newlines, bytes out of ASCII and the bounds of
+            // the ranges of symbols.
+            for c in [
+                b'-', b'_', b'=', b'\n', b'\r', 0x80, 0xc1, 0xff, b'@', b'[', b'`', b'{',
+            ] {
+                let mut src = src.clone();
+                src[i] = c;
+                let mut dst = vec![0u8; src.len()];
+                let (nr, _) = decode(a, &mut dst, &src);
+                // Only the dispatch skips newlines, between quanta.
+                let skipped = name == "dispatch" && (c == b'\n' || c == b'\r') && i % 4 == 0;
+                assert!(
+                    nr <= i || skipped,
+                    "{} decoded {:?} at {}",
+                    name,
+                    c as char,
+                    i
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn decode_wrapped() {
+    let alphabet = constants::ENCODE_STD;
+    let a = alphabet_of(alphabet).unwrap();
+    let expect = data(3000);
+    let encoded = encode_ref(alphabet, &expect);
+
+    for (width, ending) in [
+        (76, &b"\r\n"[..]),
+        (64, b"\n"),
+        (4, b"\n"),
+        (8, b"\r\n\r\n"),
+    ] {
+        let mut src = vec![];
+        for line in encoded.chunks(width) {
+            src.extend_from_slice(line);
+            src.extend_from_slice(ending);
+        }
+
+        let mut dst = vec![0u8; src.len()];
+        let (nr, nw) = super::decode(a, &mut dst, &src);
+        assert_eq!((src.len(), expect.len()), (nr, nw), "lines of {}", width);
+        assert_eq!(expect, &dst[..nw], "lines of {}", width);
+    }
+}
@@ -0,0 +1,251 @@
+//! SSSE3 and AVX2 paths, following the algorithms of Wojciech Muła and Daniel Lemire in
+//! [Faster Base64 Encoding and Decoding using AVX2 Instructions].
+//!
+//! [Faster Base64 Encoding and Decoding using AVX2 Instructions]: https://arxiv.org/abs/1704.00605
+
+use std::arch::x86_64::*;
+
+use super::Alphabet;
+
+pub(super) fn encode(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2.
+        unsafe { encode_avx2(a, dst, src) }
+    } else if is_x86_feature_detected!("ssse3") {
+        // SAFETY: the CPU supports SSSE3.
+        unsafe { encode_ssse3(a, dst, src) }
+    } else {
+        (0, 0)
+    }
+}
+
+pub(super) fn decode(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2.
+        unsafe { decode_avx2(a, dst, src) }
+    } else if is_x86_feature_detected!("ssse3") {
+        // SAFETY: the CPU supports SSSE3.
+        unsafe { decode_ssse3(a, dst, src) }
+    } else {
+        (0, 0)
+    }
+}
+
+/// Encodes 12 bytes out of 16 loaded at a time, so 4 bytes must follow the last group.
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn encode_ssse3(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    let lut = _mm_loadu_si128(encode_shift_lut(a).as_ptr() as *const __m128i);
+
+    let (mut i, mut j) = (0, 0);
+    while src.len() - i >= 16 && dst.len() - j >= 16 {
+        let v = _mm_loadu_si128(src.as_ptr().add(i) as *const __m128i);
+        let v = _mm_shuffle_epi8(
+            v,
+            _mm_set_epi8(10, 11, 9, 10, 7, 8, 6, 7, 4, 5, 3, 4, 1, 2, 0, 1),
+        );
+
+        // Moves each sextet to its own byte.
+        let t0 = _mm_and_si128(v, _mm_set1_epi32(0x0fc0fc00));
+        let t1 = _mm_mulhi_epu16(t0, _mm_set1_epi32(0x04000040));
+        let t2 = _mm_and_si128(v, _mm_set1_epi32(0x003f03f0));
+        let t3 = _mm_mullo_epi16(t2, _mm_set1_epi32(0x01000010));
+        let sextets = _mm_or_si128(t1, t3);
+
+        // Maps each class of sextets to the index of its offset in `lut`.
+        let idx = _mm_subs_epu8(sextets, _mm_set1_epi8(51));
+        let upper = _mm_cmpgt_epi8(_mm_set1_epi8(26), sextets);
+        let idx = _mm_or_si128(idx, _mm_and_si128(upper, _mm_set1_epi8(13)));
+        let out = _mm_add_epi8(sextets, _mm_shuffle_epi8(lut, idx));
+
+        _mm_storeu_si128(dst.as_mut_ptr().add(j) as *mut __m128i, out);
+        i += 12;
+        j += 16;
+    }
+
+    (i, j)
+}
+
+/// Encodes 24 bytes at a time, as 2 lanes of 12 bytes out of 16 loaded, so 4 bytes must
+/// follow the last group.
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn encode_avx2(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    let lut = _mm256_broadcastsi128_si256(_mm_loadu_si128(
+        encode_shift_lut(a).as_ptr() as *const __m128i
+    ));
+
+    let (mut i, mut j) = (0, 0);
+    while src.len() - i >= 28 && dst.len() - j >= 32 {
+        let lo = _mm_loadu_si128(src.as_ptr().add(i) as *const __m128i);
+        let hi = _mm_loadu_si128(src.as_ptr().add(i + 12) as *const __m128i);
+        let v = _mm256_inserti128_si256(_mm256_castsi128_si256(lo), hi, 1);
+        let v = _mm256_shuffle_epi8(
+            v,
+            _mm256_set_epi8(
+                10, 11, 9, 10, 7, 8, 6, 7, 4, 5, 3, 4, 1, 2, 0, 1, 10, 11, 9, 10, 7, 8, 6, 7, 4, 5,
+                3, 4, 1, 2, 0, 1,
+            ),
+        );
+
+        let t0 = _mm256_and_si256(v, _mm256_set1_epi32(0x0fc0fc00));
+        let t1 = _mm256_mulhi_epu16(t0, _mm256_set1_epi32(0x04000040));
+        let t2 = _mm256_and_si256(v, _mm256_set1_epi32(0x003f03f0));
+        let t3 = _mm256_mullo_epi16(t2, _mm256_set1_epi32(0x01000010));
+        let sextets = _mm256_or_si256(t1, t3);
+
+        let idx = _mm256_subs_epu8(sextets, _mm256_set1_epi8(51));
+        let upper = _mm256_cmpgt_epi8(_mm256_set1_epi8(26), sextets);
+        let idx = _mm256_or_si256(idx, _mm256_and_si256(upper, _mm256_set1_epi8(13)));
+        let out = _mm256_add_epi8(sextets, _mm256_shuffle_epi8(lut, idx));
+
+        _mm256_storeu_si256(dst.as_mut_ptr().add(j) as *mut __m256i, out);
+        i += 24;
+        j += 32;
+    }
+
+    let (r, w) = encode_ssse3(a, &mut dst[j..], &src[i..]);
+    (i + r, j + w)
+}
+
+/// Decodes 16 symbols at a time into 12 bytes, out of 16 stored.
+#[target_feature(enable = "ssse3")]
+pub(super) unsafe fn decode_ssse3(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    let (mut i, mut j) = (0, 0);
+    while src.len() - i >= 16 && dst.len() - j >= 16 {
+        let v = _mm_loadu_si128(src.as_ptr().add(i) as *const __m128i);
+
+        let upper = in_range_ssse3(v, b'A', b'Z');
+        let lower = in_range_ssse3(v, b'a', b'z');
+        let digit = in_range_ssse3(v, b'0', b'9');
+        let c62 = _mm_cmpeq_epi8(v, _mm_set1_epi8(a.c62 as i8));
+        let c63 = _mm_cmpeq_epi8(v, _mm_set1_epi8(a.c63 as i8));
+
+        let valid = _mm_or_si128(
+            _mm_or_si128(_mm_or_si128(upper, lower), digit),
+            _mm_or_si128(c62, c63),
+        );
+        if _mm_movemask_epi8(valid) != 0xffff {
+            break;
+        }
+
+        let shift = _mm_or_si128(
+            _mm_or_si128(
+                _mm_and_si128(upper, _mm_set1_epi8(0u8.wrapping_sub(b'A') as i8)),
+                _mm_and_si128(lower, _mm_set1_epi8(26u8.wrapping_sub(b'a') as i8)),
+            ),
+            _mm_or_si128(
+                _mm_and_si128(digit, _mm_set1_epi8(52u8.wrapping_sub(b'0') as i8)),
+                _mm_or_si128(
+                    _mm_and_si128(c62, _mm_set1_epi8(62u8.wrapping_sub(a.c62) as i8)),
+                    _mm_and_si128(c63, _mm_set1_epi8(63u8.wrapping_sub(a.c63) as i8)),
+                ),
+            ),
+        );
+        let sextets = _mm_add_epi8(v, shift);
+
+        // Packs each 4 sextets into 3 bytes, then the 3-byte groups together.
+        let merged = _mm_maddubs_epi16(sextets, _mm_set1_epi32(0x01400140));
+        let merged = _mm_madd_epi16(merged, _mm_set1_epi32(0x00011000));
+        let out = _mm_shuffle_epi8(
+            merged,
+            _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1),
+        );
+
+        _mm_storeu_si128(dst.as_mut_ptr().add(j) as *mut __m128i, out);
+        i += 16;
+        j += 12;
+    }
+
+    (i, j)
+}
+
+/// Decodes 32 symbols at a time into 24 bytes, out of 32 stored.
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn decode_avx2(a: Alphabet, dst: &mut [u8], src: &[u8]) -> (usize, usize) {
+    let (mut i, mut j) = (0, 0);
+    while src.len() - i >= 32 && dst.len() - j >= 32 {
+        let v = _mm256_loadu_si256(src.as_ptr().add(i) as *const __m256i);
+
+        let upper = in_range_avx2(v, b'A', b'Z');
+        let lower = in_range_avx2(v, b'a', b'z');
+        let digit = in_range_avx2(v, b'0', b'9');
+        let c62 = _mm256_cmpeq_epi8(v, _mm256_set1_epi8(a.c62 as i8));
+        let c63 = _mm256_cmpeq_epi8(v, _mm256_set1_epi8(a.c63 as i8));
+
+        let valid = _mm256_or_si256(
+            _mm256_or_si256(_mm256_or_si256(upper, lower), digit),
+            _mm256_or_si256(c62, c63),
+        );
+        if _mm256_movemask_epi8(valid) != -1 {
+            break;
+        }
+
+        let shift = _mm256_or_si256(
+            _mm256_or_si256(
+                _mm256_and_si256(upper, _mm256_set1_epi8(0u8.wrapping_sub(b'A') as i8)),
+                _mm256_and_si256(lower, _mm256_set1_epi8(26u8.wrapping_sub(b'a') as i8)),
+            ),
+            _mm256_or_si256(
+                _mm256_and_si256(digit, _mm256_set1_epi8(52u8.wrapping_sub(b'0') as i8)),
+                _mm256_or_si256(
+                    _mm256_and_si256(c62, _mm256_set1_epi8(62u8.wrapping_sub(a.c62) as i8)),
+                    _mm256_and_si256(c63, _mm256_set1_epi8(63u8.wrapping_sub(a.c63) as i8)),
+                ),
+            ),
+        );
+        let sextets = _mm256_add_epi8(v, shift);
+
+        let merged = _mm256_maddubs_epi16(sextets, _mm256_set1_epi32(0x01400140));
+        let merged = _mm256_madd_epi16(merged, _mm256_set1_epi32(0x00011000));
+        let out = _mm256_shuffle_epi8(
+            merged,
+            _mm256_setr_epi8(
+                2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1, 2, 1, 0, 6, 5, 4, 10, 9, 8,
+                14, 13, 12, -1, -1, -1, -1,
+            ),
+        );
+        // Moves the 12 bytes of the upper lane next to the ones of the lower lane.
+        let out = _mm256_permutevar8x32_epi32(out, _mm256_setr_epi32(0, 1, 2, 4, 5, 6, 3, 7));
+
+        _mm256_storeu_si256(dst.as_mut_ptr().add(j) as *mut __m256i, out);
+        i += 32;
+        j += 24;
+    }
+
+    let (r, w) = decode_ssse3(a, &mut dst[j..], &src[i..]);
+    (i + r, j + w)
+}
+
+/// Sets the bytes of `v` in `lo..=hi` to 0xff, and the others to 0. `lo` and `hi` must be
+/// ASCII.
+#[inline]
+#[target_feature(enable = "ssse3")]
+unsafe fn in_range_ssse3(v: __m128i, lo: u8, hi: u8) -> __m128i {
+    _mm_and_si128(
+        _mm_cmpgt_epi8(v, _mm_set1_epi8(lo as i8 - 1)),
+        _mm_cmpgt_epi8(_mm_set1_epi8(hi as i8 + 1), v),
+    )
+}
+
+/// Like [in_range_ssse3], on 32 bytes.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn in_range_avx2(v: __m256i, lo: u8, hi: u8) -> __m256i {
+    _mm256_and_si256(
+        _mm256_cmpgt_epi8(v, _mm256_set1_epi8(lo as i8 - 1)),
+        _mm256_cmpgt_epi8(_mm256_set1_epi8(hi as i8 + 1), v),
+    )
+}
+
+/// Returns the offsets from sextets to symbols, indexed by 0 for `a-z`, 1 to 10 for `0-9`,
+/// 11 and 12 for the last two symbols and 13 for `A-Z`.
+fn encode_shift_lut(a: Alphabet) -> [u8; 16] {
+    let mut lut = [0u8; 16];
+    lut[0] = b'a' - 26;
+    for v in &mut lut[1..11] {
+        *v = b'0'.wrapping_sub(52);
+    }
+    lut[11] = a.c62.wrapping_sub(62);
+    lut[12] = a.c63.wrapping_sub(63);
+    lut[13] = b'A';
+    lut
+}
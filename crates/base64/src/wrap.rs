@@ -0,0 +1,118 @@
+use std::io::Write;
+
+use crate::constants;
+
+/// Line ending ending each line of a wrapped encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, as used by PEM.
+    Lf,
+    /// `\r\n`, as used by MIME.
+    CrLf,
+}
+
+/// LineWrap tells how a [wrapping encoder][crate::new_wrapping_encoder] breaks its
+/// output into lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineWrap {
+    width: usize,
+    ending: LineEnding,
+}
+
+impl LineEnding {
+    fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Self::Lf => &[constants::LF],
+            Self::CrLf => &[constants::CR, constants::LF],
+        }
+    }
+}
+
+impl LineWrap {
+    /// Lines of 76 characters ended by CRLF, as specified by [RFC 2045] for MIME.
+    ///
+    /// [RFC 2045]: https://rfc-editor.org/rfc/rfc2045.html#section-6.8
+    pub const MIME: Self = Self {
+        width: 76,
+        ending: LineEnding::CrLf,
+    };
+
+    /// Lines of 64 characters ended by LF, as written by PEM encoders.
+    pub const PEM: Self = Self {
+        width: 64,
+        ending: LineEnding::Lf,
+    };
+
+    /// Returns a wrapping into lines of `width` characters ended by `ending`.
+    ///
+    /// # Panics
+    /// If `width` is 0.
+    pub fn new(width: usize, ending: LineEnding) -> Self {
+        if width == 0 {
+            panic!("line width must be positive");
+        }
+        Self { width, ending }
+    }
+
+    /// Returns the number of characters per line.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the line ending.
+    pub fn ending(&self) -> LineEnding {
+        self.ending
+    }
+}
+
+/// LineWriter breaks the data written to it into lines according to `wrap`. Flushing it
+/// ends the current line, if any.
+pub(crate) struct LineWriter<W>
+where
+    W: Write,
+{
+    wrap: LineWrap,
+    w: W,
+    /// Number of characters written on the current line.
+    col: usize,
+}
+
+impl<W> LineWriter<W>
+where
+    W: Write,
+{
+    pub(crate) fn new(wrap: LineWrap, w: W) -> Self {
+        Self { wrap, w, col: 0 }
+    }
+}
+
+impl<W> Write for LineWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, b: &[u8]) -> std::io::Result<usize> {
+        let mut b = b;
+        let n = b.len();
+        while !b.is_empty() {
+            let m = (self.wrap.width - self.col).min(b.len());
+            self.w.write_all(&b[..m])?;
+            b = &b[m..];
+            self.col += m;
+
+            if self.col == self.wrap.width {
+                self.w.write_all(self.wrap.ending.as_bytes())?;
+                self.col = 0;
+            }
+        }
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.col > 0 {
+            self.w.write_all(self.wrap.ending.as_bytes())?;
+            self.col = 0;
+        }
+        self.w.flush()
+    }
+}
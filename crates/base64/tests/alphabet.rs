@@ -0,0 +1,84 @@
+use base64::{AlphabetError, Encoding, STD_ENCODING};
+
+const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[test]
+fn try_new() {
+    let enc = Encoding::try_new(ALPHABET).unwrap();
+    assert_eq!("Zm9vYg==", enc.encode_to_string(b"foob"));
+
+    let test_vector = vec![
+        ("AB".to_string(), AlphabetError::InvalidLength(2)),
+        (format!("{}=", ALPHABET), AlphabetError::InvalidLength(65)),
+        (
+            format!("{}\u{e9}", &ALPHABET[2..]),
+            AlphabetError::NonAsciiSymbol { idx: 62 },
+        ),
+        (
+            format!("{}\r", &ALPHABET[1..]),
+            AlphabetError::NewlineSymbol { idx: 63 },
+        ),
+        (
+            format!("{}+", &ALPHABET[..63]),
+            AlphabetError::DuplicateSymbol {
+                symbol: b'+',
+                idx: 63,
+            },
+        ),
+    ];
+
+    for (i, (alphabet, expect)) in test_vector.into_iter().enumerate() {
+        let got = Encoding::try_new(&alphabet).err();
+        assert_eq!(Some(expect), got, "#{}", i);
+    }
+}
+
+#[test]
+fn try_with_padding() {
+    let test_vector = vec![
+        ('\u{e9}', AlphabetError::NonAsciiPadding('\u{e9}')),
+        ('\n', AlphabetError::NewlinePadding),
+        ('\r', AlphabetError::NewlinePadding),
+        ('/', AlphabetError::PaddingInAlphabet(b'/')),
+    ];
+
+    for (i, (padding, expect)) in test_vector.into_iter().enumerate() {
+        let mut enc = *STD_ENCODING;
+        let got = enc.try_with_padding(padding).err();
+        assert_eq!(Some(expect), got, "#{}", i);
+        assert_eq!(Some(b'='), enc.padding(), "#{} padding changed", i);
+    }
+
+    let mut enc = *STD_ENCODING;
+    enc.try_with_padding('.').unwrap();
+    assert_eq!("Zg..", enc.encode_to_string(b"f"));
+}
+
+#[test]
+fn alphabet_error_display() {
+    let test_vector = vec![
+        (
+            AlphabetError::InvalidLength(2),
+            "encoding alphabet is 2 bytes long, not 64",
+        ),
+        (
+            AlphabetError::DuplicateSymbol {
+                symbol: b'A',
+                idx: 1,
+            },
+            "encoding alphabet has duplicate symbol 'A' at byte 1",
+        ),
+        (
+            AlphabetError::NonAsciiPadding('\u{e9}'),
+            "padding character '\u{e9}' isn't ASCII",
+        ),
+        (
+            AlphabetError::PaddingInAlphabet(b'+'),
+            "padding character '+' contained in alphabet",
+        ),
+    ];
+
+    for (i, (err, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(expect, err.to_string(), "#{}", i);
+    }
+}
@@ -5,7 +5,7 @@ use std::{
     time::Duration,
 };
 
-use base64::{Encoding, RAW_STD_ENCODING, STD_ENCODING};
+use base64::{CorruptInputKind, Encoding, RAW_STD_ENCODING, STD_ENCODING};
 
 mod testbot;
 
@@ -117,7 +117,110 @@ fn decode_corrupt() {
             }
             Err(err) => assert_eq!(err.idx, tc.offset.unwrap(), "corruption in {}", tc.input),
         }
+
+        let mut buf = tc.input.clone().into_bytes();
+        let got = STD_ENCODING
+            .decode_in_place(&mut buf)
+            .err()
+            .map(|err| err.idx);
+        assert_eq!(tc.offset, got, "decode_in_place corruption in {}", tc.input);
+    }
+}
+
+#[test]
+fn decode_error_kind() {
+    use CorruptInputKind::*;
+
+    let strict = *STD_ENCODING.clone().strict();
+    let test_vector = vec![
+        (*STD_ENCODING, "Zm9v!mFy", InvalidByte(b'!'), 4, 3),
+        (*STD_ENCODING, "Zm9v\r\nY\x00Fy", InvalidByte(0), 7, 3),
+        (*RAW_STD_ENCODING, "Zg==", InvalidByte(b'='), 2, 0),
+        (*STD_ENCODING, "Zm9v=mFy", InvalidPadding, 4, 3),
+        (*STD_ENCODING, "Zm9vYg=", InvalidPadding, 7, 3),
+        (*STD_ENCODING, "Zm9vYg==Zg==", InvalidPadding, 8, 3),
+        (*STD_ENCODING, "Zm9vY", InvalidLength, 4, 3),
+        (*STD_ENCODING, "Zm9vYmF", InvalidLength, 4, 3),
+        (*STD_ENCODING, "Zm9vYm\nF", InvalidLength, 4, 3),
+        (*STD_ENCODING, "Zm9v\r\nYm", InvalidLength, 6, 3),
+        (*STD_ENCODING, "Zm9vY\n", InvalidLength, 4, 3),
+        (*STD_ENCODING, "Zm9", InvalidLength, 0, 0),
+        (*RAW_STD_ENCODING, "Zm9vY", InvalidLength, 4, 3),
+        (strict, "Zm9vYh==", NonCanonicalTrailingBits, 6, 3),
+        (strict, "Zm9vYmG=", NonCanonicalTrailingBits, 7, 3),
+    ];
+
+    for (i, (enc, input, kind, idx, written)) in test_vector.into_iter().enumerate() {
+        let err = enc.decode_string(input).unwrap_err();
+        assert_eq!(
+            (kind, idx, written),
+            (err.kind, err.idx, err.written),
+            "#{}",
+            i
+        );
+    }
+}
+
+#[test]
+fn append_decode() {
+    for (i, p) in testbot::PAIRS.iter().enumerate() {
+        for tt in testbot::ENCODING_TESTS.iter() {
+            let encoded = (tt.conv)(p.encoded);
+
+            let mut dst = b"prefix".to_vec();
+            let n = tt
+                .enc
+                .append_decode(&mut dst, encoded.as_bytes())
+                .expect("unexpected error");
+            assert_eq!(p.decoded.len(), n, "#{} append_decode({})", i, encoded);
+            assert_eq!(b"prefix", &dst[..6], "#{} append_decode({})", i, encoded);
+            assert_eq!(p.decoded, &dst[6..], "#{} append_decode({})", i, encoded);
+        }
     }
+
+    // The bytes decoded before the corruption are kept.
+    let mut dst = b"prefix".to_vec();
+    let err = STD_ENCODING
+        .append_decode(&mut dst, b"Zm9vYm!y")
+        .unwrap_err();
+    assert_eq!(6, err.idx);
+    assert_eq!(b"prefixfoo".to_vec(), dst);
+}
+
+#[test]
+fn decode_in_place() {
+    for (i, p) in testbot::PAIRS.iter().enumerate() {
+        for tt in testbot::ENCODING_TESTS.iter() {
+            let encoded = (tt.conv)(p.encoded);
+
+            let mut buf = encoded.clone().into_bytes();
+            let got = tt.enc.decode_in_place(&mut buf).expect("unexpected error");
+            assert_eq!(p.decoded, got, "#{} decode_in_place({})", i, encoded);
+        }
+    }
+
+    // Inputs longer than the internal buffer, with newlines.
+    let decoded: Vec<u8> = (0..5000).map(|v| (v * 7) as u8).collect();
+    let encoded = STD_ENCODING.encode_to_string(&decoded);
+    for wrap in [0, 1, 3, 76] {
+        let mut buf = vec![];
+        for (j, c) in encoded.bytes().enumerate() {
+            buf.push(c);
+            if wrap > 0 && j % wrap == 0 {
+                buf.extend_from_slice(b"\r\n");
+            }
+        }
+
+        let got = STD_ENCODING
+            .decode_in_place(&mut buf)
+            .expect("decode_in_place");
+        assert_eq!(decoded, got, "wrapped at {}", wrap);
+    }
+
+    let mut buf = encoded.into_bytes();
+    buf[4000] = b'!';
+    let err = STD_ENCODING.decode_in_place(&mut buf).unwrap_err();
+    assert_eq!((4000, 3000), (err.idx, err.written));
 }
 
 #[test]
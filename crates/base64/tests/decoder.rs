@@ -1,6 +1,6 @@
 use std::io::Read;
 
-use base64::{RAW_URL_ENCODING, STD_ENCODING, URL_ENCODING};
+use base64::{CorruptInputError, CorruptInputKind, RAW_URL_ENCODING, STD_ENCODING, URL_ENCODING};
 
 mod testbot;
 
@@ -76,3 +76,50 @@ fn decoder_raw() {
         .expect(&format!("reading new_decoder(URL_ENCODING, {src})"));
     assert_eq!(want.as_ref(), dec3.as_slice(), "dec3 != want");
 }
+
+#[test]
+fn decoder_error() {
+    use CorruptInputKind::*;
+
+    // Offsets count the newlines stripped before decoding.
+    let mut wrapped = vec![];
+    for line in STD_ENCODING
+        .encode_to_string(&[7; 3000])
+        .as_bytes()
+        .chunks(76)
+    {
+        wrapped.extend_from_slice(line);
+        wrapped.extend_from_slice(b"\r\n");
+    }
+    // Byte 1000 of the encoding, on the 14th line.
+    wrapped[1000 + 13 * 2] = b'!';
+
+    let test_vector = vec![
+        (b"Zm9v\nYm!y".to_vec(), InvalidByte(b'!'), 7, 3),
+        (b"Zm9v\r\n\r\nYmFy\nYg=\n".to_vec(), InvalidPadding, 17, 6),
+        (b"Zm9v\nYmFy\nYg".to_vec(), InvalidLength, 10, 6),
+        (wrapped, InvalidByte(b'!'), 1026, 750),
+    ];
+
+    for (i, (input, kind, idx, written)) in test_vector.into_iter().enumerate() {
+        let mut decoder = base64::new_decoder(*STD_ENCODING, input.as_slice());
+        let mut got = vec![];
+        let err = decoder.read_to_end(&mut got).unwrap_err();
+        let err = err
+            .into_inner()
+            .and_then(|v| v.downcast::<CorruptInputError>().ok())
+            .unwrap_or_else(|| panic!("#{} not a CorruptInputError", i));
+        assert_eq!(
+            (kind, idx, written),
+            (err.kind, err.idx, err.written),
+            "#{}",
+            i
+        );
+        assert_eq!(written, got.len(), "#{}", i);
+
+        // The same as decoding all at once.
+        let mut dst = vec![0; STD_ENCODING.decoded_len(input.len())];
+        let want = STD_ENCODING.decode(&mut dst, &input).unwrap_err();
+        assert_eq!((want.kind, want.idx), (err.kind, err.idx), "#{} decode", i);
+    }
+}
@@ -65,3 +65,22 @@ fn encoded_len() {
         assert_eq!(tt.want, got, "encoded_len({})", tt.n);
     }
 }
+
+#[test]
+fn append_encode() {
+    for (i, p) in testbot::PAIRS.iter().enumerate() {
+        for tt in testbot::ENCODING_TESTS.iter() {
+            let mut dst = b"prefix".to_vec();
+            tt.enc.append_encode(&mut dst, p.decoded);
+
+            let expect = "prefix".to_string() + &(tt.conv)(p.encoded);
+            assert_eq!(
+                expect.as_bytes(),
+                dst,
+                "#{} append_encode({})",
+                i,
+                p.escape_ascii_decoded
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::Write;
 
-use base64::STD_ENCODING;
+use base64::{LineEnding, LineWrap, STD_ENCODING};
 
 mod testbot;
 
@@ -46,3 +46,71 @@ fn encoder_buffering() {
         );
     }
 }
+
+#[test]
+fn wrapping_encoder() {
+    const SIXTY_FOUR: &str = "0123456789012345678901234567890123456789012345678901234567890123";
+    let encoded = |s: &str| STD_ENCODING.encode_to_string(s.as_bytes());
+
+    let test_vector = vec![
+        (LineWrap::PEM, "".to_string(), "".to_string()),
+        (LineWrap::PEM, "a".to_string(), "YQ==\n".to_string()),
+        (
+            LineWrap::PEM,
+            SIXTY_FOUR[..48].to_string(),
+            encoded(&SIXTY_FOUR[..48]) + "\n",
+        ),
+        (
+            LineWrap::PEM,
+            SIXTY_FOUR[..49].to_string(),
+            encoded(&SIXTY_FOUR[..48]) + "\nOA==\n",
+        ),
+        (
+            LineWrap::MIME,
+            SIXTY_FOUR[..57].to_string() + "abc",
+            encoded(&SIXTY_FOUR[..57]) + "\r\nYWJj\r\n",
+        ),
+        (
+            LineWrap::new(3, LineEnding::CrLf),
+            "foob".to_string(),
+            "Zm9\r\nvYg\r\n==\r\n".to_string(),
+        ),
+        (
+            LineWrap::new(4, LineEnding::Lf),
+            "foobar".to_string(),
+            "Zm9v\nYmFy\n".to_string(),
+        ),
+    ];
+
+    for (i, (wrap, input, expect)) in test_vector.into_iter().enumerate() {
+        // Writing byte by byte must give the same result.
+        for bs in [input.len().max(1), 1] {
+            let mut bb = vec![];
+            let mut encoder = base64::new_wrapping_encoder(*STD_ENCODING, wrap, &mut bb);
+            for chunk in input.as_bytes().chunks(bs) {
+                encoder.write_all(chunk).unwrap();
+            }
+            encoder.flush().unwrap();
+            std::mem::drop(encoder);
+
+            let got = String::from_utf8(bb).unwrap();
+            assert_eq!(expect, got, "#{} by {}", i, bs);
+        }
+    }
+}
+
+#[test]
+fn wrapping_encoder_drop() {
+    let mut bb = vec![];
+    let mut encoder = base64::new_wrapping_encoder(*STD_ENCODING, LineWrap::MIME, &mut bb);
+    encoder.write_all(b"foo").unwrap();
+    std::mem::drop(encoder);
+
+    assert_eq!(b"Zm9v\r\n".to_vec(), bb);
+}
+
+#[test]
+#[should_panic(expected = "line width must be positive")]
+fn line_wrap_zero_width() {
+    LineWrap::new(0, LineEnding::Lf);
+}
@@ -0,0 +1,104 @@
+use proc_macro2::Span;
+use syn::{Attribute, DeriveInput, Field, Ident, LitInt, LitStr, Member, Result, Type};
+
+const ATTR: &str = "binary";
+
+/// `Container` collects the `#[binary(...)]` attributes of a struct.
+pub struct Container {
+    /// The `binary::ByteOrder` applied to fixed-size fields.
+    pub order: Ident,
+}
+
+/// `Kind` tells how a field gets encoded.
+pub enum Kind {
+    /// A `binary::FixedSize` value in the given byte order.
+    Fixed(Ident),
+    /// An unsigned integer encoded with `binary::put_uvarint`.
+    Uvarint,
+    /// A signed integer encoded with `binary::put_varint`.
+    Varint,
+}
+
+/// `Layout` collects the `#[binary(...)]` attributes of a field.
+pub struct Layout<'a> {
+    pub member: Member,
+    pub ty: &'a Type,
+    pub kind: Kind,
+    /// Number of zero bytes preceding the field.
+    pub pad_before: usize,
+    /// Number of zero bytes following the field.
+    pub pad_after: usize,
+}
+
+impl Container {
+    pub fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut order = None;
+        for attr in binary_attrs(&input.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("order") {
+                    order = Some(meta.value()?.parse::<LitStr>()?.parse::<Ident>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported binary attribute"))
+                }
+            })?;
+        }
+
+        let order = order.unwrap_or_else(|| Ident::new("BigEndian", Span::call_site()));
+        Ok(Self { order })
+    }
+}
+
+impl<'a> Layout<'a> {
+    pub fn parse(container: &Container, index: usize, field: &'a Field) -> Result<Self> {
+        let member = match &field.ident {
+            Some(v) => Member::Named(v.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+
+        let (mut order, mut uvarint, mut varint) = (None, false, false);
+        let (mut pad_before, mut pad_after) = (0usize, 0usize);
+        for attr in binary_attrs(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("order") {
+                    order = Some(meta.value()?.parse::<LitStr>()?.parse::<Ident>()?);
+                } else if meta.path.is_ident("uvarint") {
+                    uvarint = true;
+                } else if meta.path.is_ident("varint") {
+                    varint = true;
+                } else if meta.path.is_ident("pad_before") {
+                    pad_before = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                } else if meta.path.is_ident("pad_after") {
+                    pad_after = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                } else {
+                    return Err(meta.error("unsupported binary attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let kind = match (order, uvarint, varint) {
+            (None, true, false) => Kind::Uvarint,
+            (None, false, true) => Kind::Varint,
+            (order, false, false) => Kind::Fixed(order.unwrap_or_else(|| container.order.clone())),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "`order`, `uvarint` and `varint` are mutually exclusive",
+                ))
+            }
+        };
+
+        Ok(Self {
+            member,
+            ty: &field.ty,
+            kind,
+            pad_before,
+            pad_after,
+        })
+    }
+}
+
+fn binary_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|v| v.path().is_ident(ATTR))
+}
@@ -0,0 +1,258 @@
+//! Derive macros implementing `binary::BinaryEncode` and `binary::BinaryDecode` for structs.
+//!
+//! Fields are laid out in declaration order and tuned by the `binary` attribute as follow
+//! - `#[binary(order = "LittleEndian")]` on the struct sets the byte order of all fixed-size
+//!   fields, which defaults to `BigEndian`;
+//! - `#[binary(order = "LittleEndian")]` on a field overrides the byte order of that field;
+//! - `#[binary(uvarint)]` and `#[binary(varint)]` on an integer field encodes it as an unsigned
+//!   or a zigzag signed varint respectively, failing with `Error::Overflow` for a value out of
+//!   the range of `u64` or `i64`;
+//! - `#[binary(pad_before = N)]` and `#[binary(pad_after = N)]` on a field emits `N` zero bytes
+//!   before or after the field, which are skipped when decoding.
+//!
+//! Fields without a varint attribute must implement `binary::FixedSize`.
+//!
+//! # Example
+//! ```
+//! use binary::{BinaryDecode, BinaryEncode};
+//!
+//! #[derive(BinaryEncode, BinaryDecode, Debug, PartialEq)]
+//! #[binary(order = "LittleEndian")]
+//! struct Header {
+//!     magic: [u8; 4],
+//!     #[binary(order = "BigEndian")]
+//!     version: u16,
+//!     #[binary(pad_before = 2, uvarint)]
+//!     length: u32,
+//! }
+//!
+//! let header = Header {
+//!     magic: *b"RIFF",
+//!     version: 0x0102,
+//!     length: 300,
+//! };
+//!
+//! let mut buf = vec![0u8; header.encoded_len()];
+//! assert_eq!(10, header.encode(&mut buf).unwrap());
+//! assert_eq!(b"RIFF\x01\x02\x00\x00\xac\x02", buf.as_slice());
+//!
+//! let (got, n) = Header::decode(&buf).unwrap();
+//! assert_eq!(header, got);
+//! assert_eq!(buf.len(), n);
+//! ```
+
+mod attr;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Result, Type};
+
+use crate::attr::{Container, Kind, Layout};
+
+/// Derives `binary::BinaryEncode` for a struct. See the [crate] doc for the layout.
+#[proc_macro_derive(BinaryEncode, attributes(binary))]
+pub fn derive_binary_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_encode(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `binary::BinaryDecode` for a struct. See the [crate] doc for the layout.
+#[proc_macro_derive(BinaryDecode, attributes(binary))]
+pub fn derive_binary_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_decode(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_encode(input: &DeriveInput) -> Result<TokenStream2> {
+    let layouts = parse_layouts(input)?;
+
+    let mut lens = Vec::with_capacity(layouts.len());
+    let mut steps = Vec::with_capacity(layouts.len());
+    for v in layouts.iter() {
+        let (member, pad_before, pad_after) = (&v.member, v.pad_before, v.pad_after);
+
+        let (len, step) = match &v.kind {
+            Kind::Fixed(order) => (
+                quote!(::binary::FixedSize::size(&self.#member)),
+                quote! {
+                    ::binary::FixedSize::write_to::<_, ::binary::#order>(&self.#member, &mut w, &mut n)
+                        .map_err(|err| ::binary::Error::IO(err, n))?;
+                },
+            ),
+            Kind::Uvarint => varint_encoding(
+                quote!(uvarint_len),
+                quote!(put_uvarint),
+                quote!(<u64 as ::core::convert::TryFrom<_>>::try_from(self.#member)),
+            ),
+            Kind::Varint => varint_encoding(
+                quote!(varint_len),
+                quote!(put_varint),
+                quote!(<i64 as ::core::convert::TryFrom<_>>::try_from(self.#member)),
+            ),
+        };
+
+        lens.push(quote!(#pad_before + #len + #pad_after));
+        steps.push(quote! {
+            ::std::io::Write::write_all(&mut w, &[0u8; #pad_before])
+                .map_err(|err| ::binary::Error::IO(err, n))?;
+            #step
+            ::std::io::Write::write_all(&mut w, &[0u8; #pad_after])
+                .map_err(|err| ::binary::Error::IO(err, n))?;
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::binary::BinaryEncode for #name #ty_generics #where_clause {
+            fn encoded_len(&self) -> usize {
+                0 #(+ #lens)*
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn encode(&self, dst: &mut [u8]) -> ::core::result::Result<usize, ::binary::Error> {
+                let total = dst.len();
+                let mut w = dst;
+                let mut n = 0usize;
+                #(#steps)*
+                Ok(total - w.len())
+            }
+        }
+    })
+}
+
+fn expand_decode(input: &DeriveInput) -> Result<TokenStream2> {
+    let layouts = parse_layouts(input)?;
+
+    let mut vars = Vec::with_capacity(layouts.len());
+    let mut steps = Vec::with_capacity(layouts.len());
+    for (i, v) in layouts.iter().enumerate() {
+        let (ty, pad_before, pad_after) = (v.ty, v.pad_before, v.pad_after);
+        let var = format_ident!("__field{}", i);
+
+        let step = match &v.kind {
+            Kind::Fixed(order) => {
+                let init = default_value(ty);
+                quote! {
+                    let mut #var: #ty = #init;
+                    ::binary::FixedSize::read_from::<_, ::binary::#order>(&mut #var, &mut r, &mut n)
+                        .map_err(|err| ::binary::Error::IO(err, n))?;
+                }
+            }
+            Kind::Uvarint => varint_decoding(&var, ty, quote!(uvariant)),
+            Kind::Varint => varint_decoding(&var, ty, quote!(variant)),
+        };
+
+        steps.push(quote! {
+            ::std::io::Read::read_exact(&mut r, &mut [0u8; #pad_before])
+                .map_err(|err| ::binary::Error::IO(err, n))?;
+            #step
+            ::std::io::Read::read_exact(&mut r, &mut [0u8; #pad_after])
+                .map_err(|err| ::binary::Error::IO(err, n))?;
+        });
+        vars.push(var);
+    }
+
+    let value = match &input.data {
+        Data::Struct(v) if matches!(v.fields, Fields::Named(_)) => {
+            let members = layouts.iter().map(|v| &v.member);
+            quote!(Self { #(#members: #vars),* })
+        }
+        Data::Struct(v) if matches!(v.fields, Fields::Unnamed(_)) => quote!(Self(#(#vars),*)),
+        _ => quote!(Self),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::binary::BinaryDecode for #name #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn decode(src: &[u8]) -> ::core::result::Result<(Self, usize), ::binary::Error> {
+                let mut r = src;
+                let mut n = 0usize;
+                #(#steps)*
+                Ok((#value, src.len() - r.len()))
+            }
+        }
+    })
+}
+
+fn parse_layouts(input: &DeriveInput) -> Result<Vec<Layout<'_>>> {
+    let fields = match &input.data {
+        Data::Struct(v) => &v.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "binary encoding can only be derived for structs",
+            ))
+        }
+    };
+
+    let container = Container::parse(input)?;
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, v)| Layout::parse(&container, i, v))
+        .collect()
+}
+
+/// Returns the length of the varint `value` and the statement writing it into `w`, where
+/// `value` converts the field to a 64-bit integer, failing if it doesn't fit.
+///
+/// The length of a value out of range is `MAX_VARINT_LEN64`, as
+/// encoding it fails with `Error::Overflow` anyway.
+fn varint_encoding(
+    len: TokenStream2,
+    put: TokenStream2,
+    value: TokenStream2,
+) -> (TokenStream2, TokenStream2) {
+    let len = quote!(#value.map_or(::binary::MAX_VARINT_LEN64, ::binary::#len));
+
+    let step = quote! {{
+        let v = #value.map_err(|_| ::binary::Error::Overflow)?;
+        let mut buf = [0u8; ::binary::MAX_VARINT_LEN64];
+        let m = ::binary::#put(&mut buf, v);
+        ::std::io::Write::write_all(&mut w, &buf[..m])
+            .map_err(|err| ::binary::Error::IO(err, n))?;
+        n += 1;
+    }};
+
+    (len, step)
+}
+
+/// Returns the statement decoding a varint from `r` into the variable `var`.
+fn varint_decoding(var: &syn::Ident, ty: &Type, get: TokenStream2) -> TokenStream2 {
+    quote! {
+        let #var: #ty = {
+            let (x, m) = ::binary::#get(r);
+            if m == 0 {
+                let err = ::std::io::Error::from(::std::io::ErrorKind::UnexpectedEof);
+                return Err(::binary::Error::IO(err, n));
+            } else if m < 0 {
+                return Err(::binary::Error::Overflow);
+            }
+            r = &r[(m as usize)..];
+            n += 1;
+            ::core::convert::TryFrom::try_from(x).map_err(|_| ::binary::Error::Overflow)?
+        };
+    }
+}
+
+/// Returns the expression initializing a value of type `ty`, which works for arrays of any
+/// length whose elements implement [Default].
+fn default_value(ty: &Type) -> TokenStream2 {
+    match ty {
+        Type::Array(v) => {
+            let elem = default_value(&v.elem);
+            quote!(::core::array::from_fn(|_| #elem))
+        }
+        Type::Group(v) => default_value(&v.elem),
+        Type::Paren(v) => default_value(&v.elem),
+        _ => quote!(::core::default::Default::default()),
+    }
+}
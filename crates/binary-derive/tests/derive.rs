@@ -0,0 +1,169 @@
+use std::io;
+
+use binary::{BinaryDecode, BinaryEncode, Error};
+
+#[derive(BinaryEncode, BinaryDecode, Debug, PartialEq)]
+struct Packet {
+    kind: u8,
+    flags: [bool; 2],
+    #[binary(order = "LittleEndian")]
+    id: u32,
+    #[binary(pad_before = 1, pad_after = 2)]
+    ratio: f32,
+    #[binary(uvarint)]
+    len: usize,
+    #[binary(varint)]
+    delta: i16,
+    tail: [[u16; 2]; 40],
+}
+
+#[derive(BinaryEncode, BinaryDecode, Debug, PartialEq)]
+#[binary(order = "LittleEndian")]
+struct Pair(u16, #[binary(order = "BigEndian")] u16);
+
+#[derive(BinaryEncode, BinaryDecode, Debug, PartialEq)]
+struct Counter {
+    #[binary(uvarint)]
+    value: u8,
+}
+
+#[derive(BinaryEncode, Debug)]
+struct Wide {
+    #[binary(uvarint)]
+    unsigned: i32,
+    #[binary(varint)]
+    signed: u128,
+}
+
+fn new_packet() -> Packet {
+    Packet {
+        kind: 7,
+        flags: [true, false],
+        id: 0x01020304,
+        ratio: f32::from_bits(0x3f800000),
+        len: 300,
+        delta: -2,
+        tail: [[0x0a0b, 0x0c0d]; 40],
+    }
+}
+
+#[rustfmt::skip]
+fn packet_bytes() -> Vec<u8> {
+    let mut out = vec![
+        7,
+        1, 0,
+        4, 3, 2, 1,
+        0, 0x3f, 0x80, 0, 0, 0, 0,
+        0xac, 0x02,
+        3,
+    ];
+    for _ in 0..40 {
+        out.extend_from_slice(&[0x0a, 0x0b, 0x0c, 0x0d]);
+    }
+    out
+}
+
+#[test]
+fn encode() {
+    let packet = new_packet();
+    let expect = packet_bytes();
+
+    assert_eq!(expect.len(), packet.encoded_len());
+
+    let mut got = vec![0u8; packet.encoded_len()];
+    let n = packet.encode(&mut got).expect("encode");
+    assert_eq!(expect.len(), n);
+    assert_eq!(expect, got);
+}
+
+#[test]
+fn decode() {
+    let mut src = packet_bytes();
+    src.extend_from_slice(b"rest");
+
+    let (got, n) = Packet::decode(&src).expect("decode");
+    assert_eq!(new_packet(), got);
+    assert_eq!(src.len() - 4, n);
+}
+
+#[test]
+fn tuple_struct() {
+    let pair = Pair(0x0102, 0x0304);
+
+    let mut buf = [0u8; 4];
+    assert_eq!(4, pair.encode(&mut buf).expect("encode"));
+    assert_eq!([2, 1, 3, 4], buf);
+
+    let (got, n) = Pair::decode(&buf).expect("decode");
+    assert_eq!(pair, got);
+    assert_eq!(4, n);
+}
+
+#[test]
+fn encode_short_buffer() {
+    let mut buf = [0u8; 16];
+    match new_packet().encode(&mut buf) {
+        Err(Error::IO(err, n)) => {
+            assert_eq!(io::ErrorKind::WriteZero, err.kind());
+            assert_eq!(6, n, "unexpected #(elements) written");
+        }
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn decode_short_buffer() {
+    let src = packet_bytes();
+    for (i, n) in [(0, 0), (5, 3), (15, 5), (16, 6)] {
+        match Packet::decode(&src[..i]) {
+            Err(Error::IO(err, m)) => {
+                assert_eq!(io::ErrorKind::UnexpectedEof, err.kind(), "#{i}");
+                assert_eq!(n, m, "#{i} unexpected #(elements) read");
+            }
+            v => panic!("#{i} unexpected result: {:?}", v),
+        }
+    }
+}
+
+#[test]
+fn decode_overflow() {
+    assert_eq!(
+        Counter { value: 255 },
+        Counter::decode(&[0xff, 0x01]).unwrap().0
+    );
+
+    match Counter::decode(&[0x80, 0x02]) {
+        Err(Error::Overflow) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn encode_overflow() {
+    let mut buf = [0u8; 32];
+
+    let v = Wide {
+        unsigned: 300,
+        signed: 1,
+    };
+    assert_eq!(3, v.encoded_len());
+    assert_eq!(3, v.encode(&mut buf).unwrap());
+    assert_eq!(b"\xac\x02\x02", &buf[..3]);
+
+    let test_vector = [
+        Wide {
+            unsigned: -1,
+            signed: 1,
+        },
+        Wide {
+            unsigned: 0,
+            signed: i64::MAX as u128 + 1,
+        },
+    ];
+    for (i, v) in test_vector.iter().enumerate() {
+        match v.encode(&mut buf) {
+            Err(Error::Overflow) => {}
+            v => panic!("#{i} unexpected result: {:?}", v),
+        }
+    }
+}
@@ -1,14 +1,99 @@
 use core::convert::TryInto;
 use std::fmt::{self, Display, Formatter};
 
+use crate::Error;
+
 /// `BigEndian` is the big-endian implementation of [ByteOrder].
 pub struct BigEndian;
 
 /// `LittleEndian` is the little-endian implementation of [ByteOrder].
 pub struct LittleEndian;
 
+/// `NativeEndian` is the native-endian implementation of [ByteOrder], which resolves to
+/// [BigEndian] or [LittleEndian] according to the target at compile time.
+pub struct NativeEndian;
+
+#[cfg(target_endian = "big")]
+type Native = BigEndian;
+#[cfg(target_endian = "little")]
+type Native = LittleEndian;
+
+macro_rules! try_get_fn {
+    ($name:ident, $t:ty, $len:expr, $get:ident) => {
+        #[doc = concat!("Checked version of [ByteOrder::", stringify!($get), "], which fails with")]
+        /// [Error::ShortBuffer] rather than panics if `src` is too small.
+        fn $name(src: &[u8]) -> Result<$t, Error> {
+            check_len(src.len(), $len).map(|_| Self::$get(src))
+        }
+    };
+}
+
+macro_rules! try_put_fn {
+    ($name:ident, $t:ty, $len:expr, $put:ident) => {
+        #[doc = concat!("Checked version of [ByteOrder::", stringify!($put), "], which fails with")]
+        /// [Error::ShortBuffer] rather than panics if `dst` is too small.
+        fn $name(dst: &mut [u8], v: $t) -> Result<(), Error> {
+            check_len(dst.len(), $len).map(|_| Self::$put(dst, v))
+        }
+    };
+}
+
+macro_rules! read_into_fn {
+    ($name:ident, $t:ty, $len:expr, $get:ident) => {
+        #[doc = concat!("Decodes `src` into the `", stringify!($t), "` values of `dst`.")]
+        ///
+        /// # Panics
+        #[doc = concat!("It panics if `src.len()` isn't `", stringify!($len), " * dst.len()`.")]
+        fn $name(src: &[u8], dst: &mut [$t]) {
+            assert_eq!(
+                src.len(),
+                $len * dst.len(),
+                "binary: mismatched slice lengths"
+            );
+            for (v, b) in dst.iter_mut().zip(src.chunks_exact($len)) {
+                *v = Self::$get(b);
+            }
+        }
+    };
+}
+
+macro_rules! write_from_fn {
+    ($name:ident, $t:ty, $len:expr, $put:ident) => {
+        #[doc = concat!("Encodes the `", stringify!($t), "` values of `src` into `dst`.")]
+        ///
+        /// # Panics
+        #[doc = concat!("It panics if `dst.len()` isn't `", stringify!($len), " * src.len()`.")]
+        fn $name(src: &[$t], dst: &mut [u8]) {
+            assert_eq!(
+                dst.len(),
+                $len * src.len(),
+                "binary: mismatched slice lengths"
+            );
+            for (b, &v) in dst.chunks_exact_mut($len).zip(src.iter()) {
+                Self::$put(b, v);
+            }
+        }
+    };
+}
+
+macro_rules! from_slice_fn {
+    ($name:ident, $t:ty, $get:ident) => {
+        #[doc = concat!("Converts the `", stringify!($t), "` values of `v` in place between")]
+        /// this byte order and the native one, e.g. after reinterpreting raw bytes as integers.
+        /// It's a no-op if this byte order is the native one.
+        fn $name(v: &mut [$t]) {
+            for x in v.iter_mut() {
+                *x = Self::$get(&x.to_ne_bytes());
+            }
+        }
+    };
+}
+
 /// A ByteOrder specifies how to convert byte slices into
-/// 16-, 32-, or 64-bit unsigned integers.
+/// 16-, 24-, 32-, 64- or 128-bit integers and 32- or 64-bit floating-point numbers.
+///
+/// Only the unsigned 16-, 32- and 64-bit integer conversions are required, from which the
+/// 24- and 128-bit, signed integer and floating-point ones are derived.
 pub trait ByteOrder: Display {
     fn uint16(src: &[u8]) -> u16;
     fn uint32(src: &[u8]) -> u32;
@@ -17,6 +102,163 @@ pub trait ByteOrder: Display {
     fn put_uint16(dst: &mut [u8], v: u16);
     fn put_uint32(dst: &mut [u8], v: u32);
     fn put_uint64(dst: &mut [u8], v: u64);
+
+    /// Reads a 24-bit unsigned integer from the first 3 bytes of `src`.
+    fn uint24(src: &[u8]) -> u32 {
+        let lo = src[2] as u32;
+        let hi = Self::uint16(src) as u32;
+        if is_big_endian::<Self>() {
+            (hi << 8) | lo
+        } else {
+            (lo << 16) | hi
+        }
+    }
+
+    fn uint128(src: &[u8]) -> u128 {
+        let _ = src[15];
+
+        let (a, b) = (Self::uint64(src) as u128, Self::uint64(&src[8..]) as u128);
+        if is_big_endian::<Self>() {
+            (a << 64) | b
+        } else {
+            (b << 64) | a
+        }
+    }
+
+    /// Writes the lower 24 bits of `v` into the first 3 bytes of `dst`.
+    fn put_uint24(dst: &mut [u8], v: u32) {
+        let _ = dst[2];
+
+        if is_big_endian::<Self>() {
+            Self::put_uint16(dst, (v >> 8) as u16);
+            dst[2] = v as u8;
+        } else {
+            Self::put_uint16(dst, v as u16);
+            dst[2] = (v >> 16) as u8;
+        }
+    }
+
+    fn put_uint128(dst: &mut [u8], v: u128) {
+        let _ = dst[15];
+
+        let (hi, lo) = ((v >> 64) as u64, v as u64);
+        if is_big_endian::<Self>() {
+            Self::put_uint64(dst, hi);
+            Self::put_uint64(&mut dst[8..], lo);
+        } else {
+            Self::put_uint64(dst, lo);
+            Self::put_uint64(&mut dst[8..], hi);
+        }
+    }
+
+    fn int16(src: &[u8]) -> i16 {
+        Self::uint16(src) as i16
+    }
+
+    /// Reads a 24-bit two's complement integer from the first 3 bytes of `src`, sign-extended
+    /// into an i32.
+    fn int24(src: &[u8]) -> i32 {
+        ((Self::uint24(src) << 8) as i32) >> 8
+    }
+
+    fn int32(src: &[u8]) -> i32 {
+        Self::uint32(src) as i32
+    }
+
+    fn int64(src: &[u8]) -> i64 {
+        Self::uint64(src) as i64
+    }
+
+    fn int128(src: &[u8]) -> i128 {
+        Self::uint128(src) as i128
+    }
+
+    fn float32(src: &[u8]) -> f32 {
+        f32::from_bits(Self::uint32(src))
+    }
+
+    fn float64(src: &[u8]) -> f64 {
+        f64::from_bits(Self::uint64(src))
+    }
+
+    fn put_int16(dst: &mut [u8], v: i16) {
+        Self::put_uint16(dst, v as u16)
+    }
+
+    /// Writes the lower 24 bits of `v` in two's complement into the first 3 bytes of `dst`.
+    fn put_int24(dst: &mut [u8], v: i32) {
+        Self::put_uint24(dst, v as u32)
+    }
+
+    fn put_int32(dst: &mut [u8], v: i32) {
+        Self::put_uint32(dst, v as u32)
+    }
+
+    fn put_int64(dst: &mut [u8], v: i64) {
+        Self::put_uint64(dst, v as u64)
+    }
+
+    fn put_int128(dst: &mut [u8], v: i128) {
+        Self::put_uint128(dst, v as u128)
+    }
+
+    fn put_float32(dst: &mut [u8], v: f32) {
+        Self::put_uint32(dst, v.to_bits())
+    }
+
+    fn put_float64(dst: &mut [u8], v: f64) {
+        Self::put_uint64(dst, v.to_bits())
+    }
+
+    try_get_fn!(try_uint16, u16, 2, uint16);
+    try_get_fn!(try_uint24, u32, 3, uint24);
+    try_get_fn!(try_uint32, u32, 4, uint32);
+    try_get_fn!(try_uint64, u64, 8, uint64);
+    try_get_fn!(try_uint128, u128, 16, uint128);
+    try_get_fn!(try_int16, i16, 2, int16);
+    try_get_fn!(try_int24, i32, 3, int24);
+    try_get_fn!(try_int32, i32, 4, int32);
+    try_get_fn!(try_int64, i64, 8, int64);
+    try_get_fn!(try_int128, i128, 16, int128);
+    try_get_fn!(try_float32, f32, 4, float32);
+    try_get_fn!(try_float64, f64, 8, float64);
+
+    try_put_fn!(try_put_uint16, u16, 2, put_uint16);
+    try_put_fn!(try_put_uint24, u32, 3, put_uint24);
+    try_put_fn!(try_put_uint32, u32, 4, put_uint32);
+    try_put_fn!(try_put_uint64, u64, 8, put_uint64);
+    try_put_fn!(try_put_uint128, u128, 16, put_uint128);
+    try_put_fn!(try_put_int16, i16, 2, put_int16);
+    try_put_fn!(try_put_int24, i32, 3, put_int24);
+    try_put_fn!(try_put_int32, i32, 4, put_int32);
+    try_put_fn!(try_put_int64, i64, 8, put_int64);
+    try_put_fn!(try_put_int128, i128, 16, put_int128);
+    try_put_fn!(try_put_float32, f32, 4, put_float32);
+    try_put_fn!(try_put_float64, f64, 8, put_float64);
+
+    read_into_fn!(read_u16_into, u16, 2, uint16);
+    read_into_fn!(read_u32_into, u32, 4, uint32);
+    read_into_fn!(read_u64_into, u64, 8, uint64);
+    read_into_fn!(read_f32_into, f32, 4, float32);
+    read_into_fn!(read_f64_into, f64, 8, float64);
+
+    write_from_fn!(write_u16_from, u16, 2, put_uint16);
+    write_from_fn!(write_u32_from, u32, 4, put_uint32);
+    write_from_fn!(write_u64_from, u64, 8, put_uint64);
+    write_from_fn!(write_f32_from, f32, 4, put_float32);
+    write_from_fn!(write_f64_from, f64, 8, put_float64);
+
+    from_slice_fn!(from_slice_u16, u16, uint16);
+    from_slice_fn!(from_slice_u32, u32, uint32);
+    from_slice_fn!(from_slice_u64, u64, uint64);
+}
+
+/// An AppendByteOrder specifies how to append 16-, 32-, or 64-bit unsigned integers
+/// into a byte vector.
+pub trait AppendByteOrder: Display {
+    fn append_uint16(dst: &mut Vec<u8>, v: u16);
+    fn append_uint32(dst: &mut Vec<u8>, v: u32);
+    fn append_uint64(dst: &mut Vec<u8>, v: u64);
 }
 
 impl ByteOrder for BigEndian {
@@ -24,6 +266,12 @@ impl ByteOrder for BigEndian {
         u16::from_be_bytes(src[0..2].try_into().expect("unfallible"))
     }
 
+    fn uint24(src: &[u8]) -> u32 {
+        let _ = src[2];
+
+        ((src[0] as u32) << 16) | ((src[1] as u32) << 8) | (src[2] as u32)
+    }
+
     fn uint32(src: &[u8]) -> u32 {
         u32::from_be_bytes(src[0..4].try_into().expect("unfallible"))
     }
@@ -32,6 +280,10 @@ impl ByteOrder for BigEndian {
         u64::from_be_bytes(src[0..8].try_into().expect("unfallible"))
     }
 
+    fn uint128(src: &[u8]) -> u128 {
+        u128::from_be_bytes(src[0..16].try_into().expect("unfallible"))
+    }
+
     fn put_uint16(dst: &mut [u8], v: u16) {
         let _ = dst[1];
 
@@ -39,6 +291,14 @@ impl ByteOrder for BigEndian {
         dst[1] = (v & 0xff) as u8;
     }
 
+    fn put_uint24(dst: &mut [u8], v: u32) {
+        let _ = dst[2];
+
+        dst[0] = ((v >> 16) & 0xff) as u8;
+        dst[1] = ((v >> 8) & 0xff) as u8;
+        dst[2] = (v & 0xff) as u8;
+    }
+
     fn put_uint32(dst: &mut [u8], v: u32) {
         let _ = dst[3];
 
@@ -60,6 +320,24 @@ impl ByteOrder for BigEndian {
         dst[6] = ((v >> 8) & 0xff) as u8;
         dst[7] = (v & 0xff) as u8;
     }
+
+    fn put_uint128(dst: &mut [u8], v: u128) {
+        dst[0..16].copy_from_slice(&v.to_be_bytes());
+    }
+}
+
+impl AppendByteOrder for BigEndian {
+    fn append_uint16(dst: &mut Vec<u8>, v: u16) {
+        dst.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn append_uint32(dst: &mut Vec<u8>, v: u32) {
+        dst.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn append_uint64(dst: &mut Vec<u8>, v: u64) {
+        dst.extend_from_slice(&v.to_be_bytes());
+    }
 }
 
 impl Display for BigEndian {
@@ -78,6 +356,12 @@ impl ByteOrder for LittleEndian {
         u16::from_le_bytes(src[0..2].try_into().expect("unfallible"))
     }
 
+    fn uint24(src: &[u8]) -> u32 {
+        let _ = src[2];
+
+        (src[0] as u32) | ((src[1] as u32) << 8) | ((src[2] as u32) << 16)
+    }
+
     fn uint32(src: &[u8]) -> u32 {
         u32::from_le_bytes(src[0..4].try_into().expect("unfallible"))
     }
@@ -86,6 +370,10 @@ impl ByteOrder for LittleEndian {
         u64::from_le_bytes(src[0..8].try_into().expect("unfallible"))
     }
 
+    fn uint128(src: &[u8]) -> u128 {
+        u128::from_le_bytes(src[0..16].try_into().expect("unfallible"))
+    }
+
     fn put_uint16(dst: &mut [u8], v: u16) {
         let _ = dst[1];
 
@@ -93,6 +381,14 @@ impl ByteOrder for LittleEndian {
         dst[1] = ((v >> 8) & 0xff) as u8;
     }
 
+    fn put_uint24(dst: &mut [u8], v: u32) {
+        let _ = dst[2];
+
+        dst[0] = (v & 0xff) as u8;
+        dst[1] = ((v >> 8) & 0xff) as u8;
+        dst[2] = ((v >> 16) & 0xff) as u8;
+    }
+
     fn put_uint32(dst: &mut [u8], v: u32) {
         let _ = dst[3];
 
@@ -114,6 +410,24 @@ impl ByteOrder for LittleEndian {
         dst[6] = ((v >> 48) & 0xff) as u8;
         dst[7] = ((v >> 56) & 0xff) as u8;
     }
+
+    fn put_uint128(dst: &mut [u8], v: u128) {
+        dst[0..16].copy_from_slice(&v.to_le_bytes());
+    }
+}
+
+impl AppendByteOrder for LittleEndian {
+    fn append_uint16(dst: &mut Vec<u8>, v: u16) {
+        dst.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn append_uint32(dst: &mut Vec<u8>, v: u32) {
+        dst.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn append_uint64(dst: &mut Vec<u8>, v: u64) {
+        dst.extend_from_slice(&v.to_le_bytes());
+    }
 }
 
 impl Display for LittleEndian {
@@ -127,5 +441,80 @@ impl Display for LittleEndian {
     }
 }
 
+impl ByteOrder for NativeEndian {
+    fn uint16(src: &[u8]) -> u16 {
+        Native::uint16(src)
+    }
+
+    fn uint24(src: &[u8]) -> u32 {
+        Native::uint24(src)
+    }
+
+    fn uint32(src: &[u8]) -> u32 {
+        Native::uint32(src)
+    }
+
+    fn uint64(src: &[u8]) -> u64 {
+        Native::uint64(src)
+    }
+
+    fn uint128(src: &[u8]) -> u128 {
+        Native::uint128(src)
+    }
+
+    fn put_uint16(dst: &mut [u8], v: u16) {
+        Native::put_uint16(dst, v)
+    }
+
+    fn put_uint24(dst: &mut [u8], v: u32) {
+        Native::put_uint24(dst, v)
+    }
+
+    fn put_uint32(dst: &mut [u8], v: u32) {
+        Native::put_uint32(dst, v)
+    }
+
+    fn put_uint64(dst: &mut [u8], v: u64) {
+        Native::put_uint64(dst, v)
+    }
+
+    fn put_uint128(dst: &mut [u8], v: u128) {
+        Native::put_uint128(dst, v)
+    }
+}
+
+impl AppendByteOrder for NativeEndian {
+    fn append_uint16(dst: &mut Vec<u8>, v: u16) {
+        Native::append_uint16(dst, v)
+    }
+
+    fn append_uint32(dst: &mut Vec<u8>, v: u32) {
+        Native::append_uint32(dst, v)
+    }
+
+    fn append_uint64(dst: &mut Vec<u8>, v: u64) {
+        Native::append_uint64(dst, v)
+    }
+}
+
+impl Display for NativeEndian {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "NativeEndian")
+    }
+}
+
+/// Tells whether `O` puts the most significant byte first, so that the default methods of
+/// [ByteOrder] can combine the required ones.
+fn is_big_endian<O: ByteOrder + ?Sized>() -> bool {
+    O::uint16(&[1, 0]) == 0x0100
+}
+
+fn check_len(got: usize, need: usize) -> Result<(), Error> {
+    if got < need {
+        return Err(Error::ShortBuffer(need, got));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,7 @@
+use std::fmt::{self, Display, Formatter};
 use std::panic;
 
-use crate::{ByteOrder, LittleEndian};
+use crate::{AppendByteOrder, BigEndian, ByteOrder, Error, LittleEndian, NativeEndian};
 
 #[test]
 fn uint64_small_slice_length_panics() {
@@ -23,3 +24,358 @@ fn put_uint64_small_slice_length_panics() {
 
     assert!(panicked, "missing panic");
 }
+
+#[test]
+fn try_small_slice_length_fails() {
+    let mut b = [1u8, 2, 3, 4, 5, 6, 7, 8];
+
+    match LittleEndian::try_uint64(&b[..4]) {
+        Err(Error::ShortBuffer(8, 4)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    match BigEndian::try_uint32(&b[..3]) {
+        Err(Error::ShortBuffer(4, 3)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    match BigEndian::try_uint16(&b[..1]) {
+        Err(Error::ShortBuffer(2, 1)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+
+    match LittleEndian::try_put_uint64(&mut b[..4], 0x0102030405060708) {
+        Err(Error::ShortBuffer(8, 4)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    match BigEndian::try_put_uint32(&mut b[..0], 0x01020304) {
+        Err(Error::ShortBuffer(4, 0)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    match BigEndian::try_put_uint16(&mut b[..1], 0x0102) {
+        Err(Error::ShortBuffer(2, 1)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert_eq!([1u8, 2, 3, 4, 5, 6, 7, 8], b, "dst modified on failure");
+
+    let mut b = [0u8; 16];
+    let test_vector: Vec<(usize, Result<(), Error>)> = vec![
+        (3, BigEndian::try_uint24(&b[..2]).map(|_| ())),
+        (16, LittleEndian::try_uint128(&b[..15]).map(|_| ())),
+        (2, BigEndian::try_int16(&b[..1]).map(|_| ())),
+        (3, LittleEndian::try_int24(&b[..2]).map(|_| ())),
+        (4, BigEndian::try_int32(&b[..3]).map(|_| ())),
+        (8, LittleEndian::try_int64(&b[..7]).map(|_| ())),
+        (16, BigEndian::try_int128(&b[..15]).map(|_| ())),
+        (4, LittleEndian::try_float32(&b[..3]).map(|_| ())),
+        (8, BigEndian::try_float64(&b[..7]).map(|_| ())),
+        (3, BigEndian::try_put_uint24(&mut b[..2], 1)),
+        (16, LittleEndian::try_put_uint128(&mut b[..15], 1)),
+        (2, BigEndian::try_put_int16(&mut b[..1], -1)),
+        (3, LittleEndian::try_put_int24(&mut b[..2], -1)),
+        (4, BigEndian::try_put_int32(&mut b[..3], -1)),
+        (8, LittleEndian::try_put_int64(&mut b[..7], -1)),
+        (16, BigEndian::try_put_int128(&mut b[..15], -1)),
+        (4, LittleEndian::try_put_float32(&mut b[..3], 1.0)),
+        (8, BigEndian::try_put_float64(&mut b[..7], 1.0)),
+    ];
+    for (i, (need, v)) in test_vector.into_iter().enumerate() {
+        match v {
+            Err(Error::ShortBuffer(n, m)) if n == need && m == need - 1 => {}
+            v => panic!("#{i} unexpected result: {:?}", v),
+        }
+    }
+    assert_eq!([0u8; 16], b, "dst modified on failure");
+}
+
+#[test]
+fn try_ok() {
+    let mut b = [0u8; 8];
+
+    BigEndian::try_put_uint16(&mut b, 0x0102).unwrap();
+    assert_eq!(0x0102, BigEndian::try_uint16(&b).unwrap());
+    LittleEndian::try_put_uint32(&mut b, 0x01020304).unwrap();
+    assert_eq!(0x01020304, LittleEndian::try_uint32(&b).unwrap());
+    BigEndian::try_put_uint64(&mut b, 0x0102030405060708).unwrap();
+    assert_eq!(0x0102030405060708, BigEndian::try_uint64(&b).unwrap());
+
+    let mut b = [0u8; 16];
+
+    LittleEndian::try_put_uint24(&mut b, 0x010203).unwrap();
+    assert_eq!(0x010203, LittleEndian::try_uint24(&b).unwrap());
+    BigEndian::try_put_uint128(&mut b, u128::MAX - 1).unwrap();
+    assert_eq!(u128::MAX - 1, BigEndian::try_uint128(&b).unwrap());
+    BigEndian::try_put_int16(&mut b, -2).unwrap();
+    assert_eq!(-2, BigEndian::try_int16(&b).unwrap());
+    LittleEndian::try_put_int24(&mut b, -3).unwrap();
+    assert_eq!(-3, LittleEndian::try_int24(&b).unwrap());
+    BigEndian::try_put_int32(&mut b, i32::MIN).unwrap();
+    assert_eq!(i32::MIN, BigEndian::try_int32(&b).unwrap());
+    LittleEndian::try_put_int64(&mut b, i64::MIN).unwrap();
+    assert_eq!(i64::MIN, LittleEndian::try_int64(&b).unwrap());
+    BigEndian::try_put_int128(&mut b, i128::MIN).unwrap();
+    assert_eq!(i128::MIN, BigEndian::try_int128(&b).unwrap());
+    LittleEndian::try_put_float32(&mut b, -1.5).unwrap();
+    assert_eq!(-1.5, LittleEndian::try_float32(&b).unwrap());
+    BigEndian::try_put_float64(&mut b, std::f64::consts::E).unwrap();
+    assert_eq!(std::f64::consts::E, BigEndian::try_float64(&b).unwrap());
+}
+
+#[test]
+fn big_endian() {
+    let b = [
+        0x81u8, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        0x10,
+    ];
+
+    assert_eq!(0x8102, BigEndian::uint16(&b));
+    assert_eq!(-0x7efe, BigEndian::int16(&b));
+    assert_eq!(0x810203, BigEndian::uint24(&b));
+    assert_eq!(-0x7efdfd, BigEndian::int24(&b));
+    assert_eq!(0x81020304, BigEndian::uint32(&b));
+    assert_eq!(0x8102030405060708, BigEndian::uint64(&b));
+    assert_eq!(0x8102030405060708090a0b0c0d0e0f10, BigEndian::uint128(&b));
+    assert_eq!(
+        0x8102030405060708090a0b0c0d0e0f10u128 as i128,
+        BigEndian::int128(&b)
+    );
+    assert_eq!(f32::from_bits(0x81020304), BigEndian::float32(&b));
+    assert_eq!(f64::from_bits(0x8102030405060708), BigEndian::float64(&b));
+
+    test_round_trip::<BigEndian>();
+}
+
+#[test]
+fn little_endian() {
+    let b = [
+        0x10u8, 0x0f, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02,
+        0x81,
+    ];
+
+    assert_eq!(0x0f10, LittleEndian::uint16(&b));
+    assert_eq!(0x0e0f10, LittleEndian::uint24(&b));
+    assert_eq!(0x0d0e0f10, LittleEndian::uint32(&b));
+    assert_eq!(0x090a0b0c0d0e0f10, LittleEndian::uint64(&b));
+    assert_eq!(
+        0x8102030405060708090a0b0c0d0e0f10,
+        LittleEndian::uint128(&b)
+    );
+    assert_eq!(-0x7efe, LittleEndian::int16(&b[14..]));
+    assert_eq!(-0x7efdfd, LittleEndian::int24(&b[13..]));
+    assert_eq!(f32::from_bits(0x0d0e0f10), LittleEndian::float32(&b));
+    assert_eq!(
+        f64::from_bits(0x090a0b0c0d0e0f10),
+        LittleEndian::float64(&b)
+    );
+
+    test_round_trip::<LittleEndian>();
+}
+
+fn test_round_trip<B: ByteOrder>() {
+    let mut b = [0u8; 16];
+
+    B::put_int16(&mut b, -2);
+    assert_eq!(-2, B::int16(&b));
+
+    B::put_int24(&mut b, -0x400000);
+    assert_eq!(-0x400000, B::int24(&b));
+    B::put_int24(&mut b, 0x3fffff);
+    assert_eq!(0x3fffff, B::int24(&b));
+    B::put_uint24(&mut b, 0x12345678);
+    assert_eq!(0x345678, B::uint24(&b));
+
+    B::put_int32(&mut b, i32::MIN);
+    assert_eq!(i32::MIN, B::int32(&b));
+
+    B::put_int64(&mut b, -1234567890123);
+    assert_eq!(-1234567890123, B::int64(&b));
+
+    B::put_uint128(&mut b, u128::MAX - 1);
+    assert_eq!(u128::MAX - 1, B::uint128(&b));
+    B::put_int128(&mut b, i128::MIN + 1);
+    assert_eq!(i128::MIN + 1, B::int128(&b));
+
+    B::put_float32(&mut b, -1.5);
+    assert_eq!(-1.5, B::float32(&b));
+    B::put_float64(&mut b, std::f64::consts::PI);
+    assert_eq!(std::f64::consts::PI, B::float64(&b));
+}
+
+#[test]
+fn append() {
+    let mut got = vec![0xff];
+    BigEndian::append_uint16(&mut got, 0x0102);
+    BigEndian::append_uint32(&mut got, 0x03040506);
+    BigEndian::append_uint64(&mut got, 0x0708090a0b0c0d0e);
+    LittleEndian::append_uint16(&mut got, 0x0102);
+    LittleEndian::append_uint32(&mut got, 0x03040506);
+    LittleEndian::append_uint64(&mut got, 0x0708090a0b0c0d0e);
+
+    #[rustfmt::skip]
+    let expect = [
+        0xff,
+        1, 2,
+        3, 4, 5, 6,
+        7, 8, 9, 10, 11, 12, 13, 14,
+        2, 1,
+        6, 5, 4, 3,
+        14, 13, 12, 11, 10, 9, 8, 7,
+    ];
+    assert_eq!(&expect[..], got.as_slice());
+}
+
+#[test]
+fn native_endian() {
+    let mut b = [0u8; 16];
+
+    NativeEndian::put_uint16(&mut b, 0x0102);
+    assert_eq!(0x0102u16.to_ne_bytes(), b[..2]);
+    NativeEndian::put_uint32(&mut b, 0x01020304);
+    assert_eq!(0x01020304u32.to_ne_bytes(), b[..4]);
+    NativeEndian::put_uint64(&mut b, 0x0102030405060708);
+    assert_eq!(0x0102030405060708u64.to_ne_bytes(), b[..8]);
+    assert_eq!(0x0102030405060708, NativeEndian::uint64(&b));
+
+    let mut got = vec![];
+    NativeEndian::append_uint32(&mut got, 0x01020304);
+    assert_eq!(0x01020304u32.to_ne_bytes(), got.as_slice());
+
+    assert_eq!("NativeEndian", NativeEndian.to_string());
+
+    test_round_trip::<NativeEndian>();
+}
+
+#[test]
+fn bulk() {
+    let src: Vec<u8> = (0u8..24).collect();
+
+    let mut got = [0u32; 6];
+    BigEndian::read_u32_into(&src, &mut got);
+    assert_eq!(BigEndian::uint32(&src[4..]), got[1]);
+    let mut back = vec![0u8; src.len()];
+    BigEndian::write_u32_from(&got, &mut back);
+    assert_eq!(src, back);
+
+    let mut got = [0u16; 12];
+    LittleEndian::read_u16_into(&src, &mut got);
+    assert_eq!(0x0302, got[1]);
+    LittleEndian::write_u16_from(&got, &mut back);
+    assert_eq!(src, back);
+
+    let mut got = [0u64; 3];
+    BigEndian::read_u64_into(&src, &mut got);
+    assert_eq!(0x08090a0b0c0d0e0f, got[1]);
+    BigEndian::write_u64_from(&got, &mut back);
+    assert_eq!(src, back);
+
+    let floats = [1.5f32, -2.0, f32::MAX];
+    let mut buf = [0u8; 12];
+    LittleEndian::write_f32_from(&floats, &mut buf);
+    let mut got = [0f32; 3];
+    LittleEndian::read_f32_into(&buf, &mut got);
+    assert_eq!(floats, got);
+
+    let floats = [1.5f64, -2.0, f64::MIN_POSITIVE];
+    let mut buf = [0u8; 24];
+    BigEndian::write_f64_from(&floats, &mut buf);
+    assert_eq!(1.5, BigEndian::float64(&buf));
+    let mut got = [0f64; 3];
+    BigEndian::read_f64_into(&buf, &mut got);
+    assert_eq!(floats, got);
+}
+
+#[test]
+fn bulk_mismatched_lengths_panics() {
+    let panicked = panic::catch_unwind(|| {
+        let mut dst = [0u32; 2];
+        BigEndian::read_u32_into(&[0u8; 7], &mut dst);
+    })
+    .is_err();
+
+    assert!(panicked, "missing panic");
+}
+
+#[test]
+fn from_slice() {
+    let mut v = [0x0102u16, 0x0304];
+    BigEndian::from_slice_u16(&mut v);
+    assert_eq!([0x0102u16.to_be(), 0x0304u16.to_be()], v);
+
+    let mut v = [0x01020304u32, 0x05060708];
+    LittleEndian::from_slice_u32(&mut v);
+    assert_eq!([0x01020304u32.to_le(), 0x05060708u32.to_le()], v);
+
+    let mut v = [0x0102030405060708u64];
+    BigEndian::from_slice_u64(&mut v);
+    assert_eq!([0x0102030405060708u64.to_be()], v);
+    BigEndian::from_slice_u64(&mut v);
+    assert_eq!([0x0102030405060708u64], v);
+}
+
+/// Implements only the required methods of [ByteOrder], as a third-party implementation would.
+macro_rules! minimal_order {
+    ($name:ident, $order:ident) => {
+        struct $name;
+
+        impl ByteOrder for $name {
+            fn uint16(src: &[u8]) -> u16 {
+                $order::uint16(src)
+            }
+
+            fn uint32(src: &[u8]) -> u32 {
+                $order::uint32(src)
+            }
+
+            fn uint64(src: &[u8]) -> u64 {
+                $order::uint64(src)
+            }
+
+            fn put_uint16(dst: &mut [u8], v: u16) {
+                $order::put_uint16(dst, v)
+            }
+
+            fn put_uint32(dst: &mut [u8], v: u32) {
+                $order::put_uint32(dst, v)
+            }
+
+            fn put_uint64(dst: &mut [u8], v: u64) {
+                $order::put_uint64(dst, v)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, stringify!($name))
+            }
+        }
+    };
+}
+
+minimal_order!(MinimalBigEndian, BigEndian);
+minimal_order!(MinimalLittleEndian, LittleEndian);
+
+#[test]
+fn default_methods() {
+    test_default_methods::<MinimalBigEndian, BigEndian>();
+    test_default_methods::<MinimalLittleEndian, LittleEndian>();
+
+    test_round_trip::<MinimalBigEndian>();
+    test_round_trip::<MinimalLittleEndian>();
+}
+
+fn test_default_methods<M: ByteOrder, B: ByteOrder>() {
+    let b: Vec<u8> = (0x81u8..0x91).collect();
+    assert_eq!(B::uint24(&b), M::uint24(&b));
+    assert_eq!(B::uint128(&b), M::uint128(&b));
+
+    let (mut expect, mut got) = ([0u8; 16], [0u8; 16]);
+    B::put_uint24(&mut expect, 0x818283);
+    M::put_uint24(&mut got, 0x818283);
+    assert_eq!(expect, got);
+    B::put_uint128(&mut expect, 0x8102030405060708090a0b0c0d0e0f10);
+    M::put_uint128(&mut got, 0x8102030405060708090a0b0c0d0e0f10);
+    assert_eq!(expect, got);
+
+    let panicked = panic::catch_unwind(|| M::uint128(&b[..15])).is_err();
+    assert!(panicked, "missing panic");
+    let panicked = panic::catch_unwind(move || M::put_uint128(&mut got[..15], 0)).is_err();
+    assert!(panicked, "missing panic");
+}
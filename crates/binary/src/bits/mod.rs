@@ -0,0 +1,331 @@
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+
+use crate::Error;
+
+/// `MsbFirst` is the [BitOrder] consuming every byte from its most significant bit, as used by
+/// most codec headers (MPEG, H.264, ...). The first bit read is the most significant bit of the
+/// value.
+pub struct MsbFirst;
+
+/// `LsbFirst` is the [BitOrder] consuming every byte from its least significant bit, as used by
+/// DEFLATE and friends. The first bit read is the least significant bit of the value.
+pub struct LsbFirst;
+
+/// A BitOrder specifies how bits are packed into bytes.
+pub trait BitOrder {
+    /// Tells whether the most significant bit of a byte comes first.
+    const MSB_FIRST: bool;
+}
+
+impl BitOrder for MsbFirst {
+    const MSB_FIRST: bool = true;
+}
+
+impl BitOrder for LsbFirst {
+    const MSB_FIRST: bool = false;
+}
+
+/// `BitReader` reads values of arbitrary bit width from an underlying [Read], packed in the
+/// bit order `O`. Slices are supported through the [Read] implementation of `&[u8]`.
+///
+/// # Example
+/// ```
+/// use binary::{BitReader, MsbFirst};
+///
+/// let data = [0b1010_1100u8, 0b1000_0000];
+/// let mut r = BitReader::<_, MsbFirst>::new(&data[..]);
+///
+/// assert_eq!(0b101, r.read_bits(3).unwrap());
+/// assert_eq!(2, r.read_exp_golomb().unwrap());
+/// r.align();
+/// assert_eq!(0b1000_0000, r.read_bits(8).unwrap());
+/// ```
+pub struct BitReader<R, O> {
+    r: R,
+    cur: u8,
+    left: u32,
+    pos: usize,
+    _order: PhantomData<O>,
+}
+
+/// `BitWriter` writes values of arbitrary bit width into an underlying [Write], packed in the
+/// bit order `O`. Slices are supported through the [Write] implementation of `&mut [u8]`.
+///
+/// A partially filled byte is only written after [BitWriter::align] or [BitWriter::flush],
+/// where the remaining bits are zero-padded. Dropping the writer flushes it too.
+///
+/// # Example
+/// ```
+/// use binary::{BitWriter, LsbFirst};
+///
+/// let mut out = vec![];
+/// {
+///     let mut w = BitWriter::<_, LsbFirst>::new(&mut out);
+///     w.write_bits(0b101, 3).unwrap();
+///     w.write_bits(0b11, 2).unwrap();
+///     w.flush().unwrap();
+/// }
+///
+/// assert_eq!(vec![0b000_11_101u8], out);
+/// ```
+pub struct BitWriter<W, O>
+where
+    W: Write,
+{
+    w: W,
+    cur: u8,
+    used: u32,
+    pos: usize,
+    _order: PhantomData<O>,
+}
+
+impl<R, O> BitReader<R, O>
+where
+    R: Read,
+    O: BitOrder,
+{
+    /// Returns a new bit reader reading from `r`.
+    pub fn new(r: R) -> Self {
+        Self {
+            r,
+            cur: 0,
+            left: 0,
+            pos: 0,
+            _order: PhantomData,
+        }
+    }
+
+    /// Discards the remaining bits of the current byte, so that the next read starts at a byte
+    /// boundary.
+    pub fn align(&mut self) {
+        self.pos += self.left as usize;
+        self.left = 0;
+    }
+
+    /// Unwraps this reader, returning the underlying reader. Bits left in the current byte are
+    /// lost.
+    pub fn into_inner(self) -> R {
+        self.r
+    }
+
+    /// Tells whether the next read starts at a byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.left == 0
+    }
+
+    /// Returns the number of bits consumed so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Reads a single bit.
+    pub fn read_bit(&mut self) -> Result<bool, Error> {
+        self.read_bits(1).map(|v| v == 1)
+    }
+
+    /// Reads `n` bits and returns them as the lower bits of a u64.
+    ///
+    /// The error is [Error::IO] carrying the number of bits read before the underlying reader
+    /// fails.
+    ///
+    /// # Panics
+    /// `read_bits` panics if `n` is larger than 64.
+    pub fn read_bits(&mut self, n: u32) -> Result<u64, Error> {
+        assert!(n <= 64, "binary: can't read more than 64 bits at once");
+
+        let mut v = 0u64;
+        let mut done = 0u32;
+        while done < n {
+            if self.left == 0 {
+                let mut b = [0u8; 1];
+                self.r
+                    .read_exact(&mut b)
+                    .map_err(|err| Error::IO(err, done as usize))?;
+                self.cur = b[0];
+                self.left = 8;
+            }
+
+            let take = (n - done).min(self.left);
+            if O::MSB_FIRST {
+                let bits = (self.cur >> (self.left - take)) & mask(take);
+                v = (v << take) | (bits as u64);
+            } else {
+                let bits = (self.cur >> (8 - self.left)) & mask(take);
+                v |= (bits as u64) << done;
+            }
+
+            self.left -= take;
+            self.pos += take as usize;
+            done += take;
+        }
+
+        Ok(v)
+    }
+
+    /// Reads an unsigned exponential-Golomb code of order 0, i.e. the `ue(v)` of H.264.
+    ///
+    /// The error is [Error::CorruptInputError] carrying the bit position of the code if it
+    /// doesn't fit into a u64.
+    pub fn read_exp_golomb(&mut self) -> Result<u64, Error> {
+        let start = self.pos;
+
+        let mut zeros = 0u32;
+        while !self.read_bit()? {
+            zeros += 1;
+            if zeros > 63 {
+                return Err(Error::CorruptInputError("exp-Golomb", start));
+            }
+        }
+
+        let v = self.read_bits(zeros)?;
+        Ok(((1u64 << zeros) | v) - 1)
+    }
+
+    /// Reads a signed exponential-Golomb code of order 0, i.e. the `se(v)` of H.264.
+    pub fn read_signed_exp_golomb(&mut self) -> Result<i64, Error> {
+        let k = self.read_exp_golomb()?;
+        let v = ((k >> 1) + (k & 1)) as i64;
+        Ok(if k & 1 == 1 { v } else { -v })
+    }
+}
+
+impl<W, O> BitWriter<W, O>
+where
+    W: Write,
+    O: BitOrder,
+{
+    /// Returns a new bit writer writing into `w`.
+    pub fn new(w: W) -> Self {
+        Self {
+            w,
+            cur: 0,
+            used: 0,
+            pos: 0,
+            _order: PhantomData,
+        }
+    }
+
+    /// Pads the current byte with zero bits and writes it out, so that the next write starts at
+    /// a byte boundary.
+    pub fn align(&mut self) -> Result<(), Error> {
+        if self.used == 0 {
+            return Ok(());
+        }
+
+        let pad = 8 - self.used;
+        self.write_bits(0, pad)
+    }
+
+    /// Aligns the writer with [BitWriter::align] and flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.align()?;
+        self.w.flush().map_err(|err| Error::IO(err, 0))
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.w
+    }
+
+    /// Tells whether the next write starts at a byte boundary.
+    pub fn is_aligned(&self) -> bool {
+        self.used == 0
+    }
+
+    /// Returns the number of bits written so far, including the ones pending in the current
+    /// byte.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        self.write_bits(bit as u64, 1)
+    }
+
+    /// Writes the lower `n` bits of `v`.
+    ///
+    /// The error is [Error::IO] carrying the number of bits written before the underlying
+    /// writer fails.
+    ///
+    /// # Panics
+    /// `write_bits` panics if `n` is larger than 64.
+    pub fn write_bits(&mut self, v: u64, n: u32) -> Result<(), Error> {
+        assert!(n <= 64, "binary: can't write more than 64 bits at once");
+
+        let mut done = 0u32;
+        while done < n {
+            let take = (n - done).min(8 - self.used);
+            if O::MSB_FIRST {
+                let bits = ((v >> (n - done - take)) as u8) & mask(take);
+                self.cur |= bits << (8 - self.used - take);
+            } else {
+                let bits = ((v >> done) as u8) & mask(take);
+                self.cur |= bits << self.used;
+            }
+
+            self.used += take;
+            self.pos += take as usize;
+            done += take;
+
+            if self.used == 8 {
+                self.w
+                    .write_all(&[self.cur])
+                    .map_err(|err| Error::IO(err, (done - take) as usize))?;
+                self.cur = 0;
+                self.used = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes `v` as an unsigned exponential-Golomb code of order 0, i.e. the `ue(v)` of H.264.
+    ///
+    /// # Panics
+    /// `write_exp_golomb` panics if `v` is [u64::MAX], which isn't representable.
+    pub fn write_exp_golomb(&mut self, v: u64) -> Result<(), Error> {
+        let v = v.checked_add(1).expect("binary: exp-Golomb overflows");
+        let zeros = 63 - v.leading_zeros();
+
+        self.write_bits(0, zeros)?;
+        self.write_bit(true)?;
+        self.write_bits(v, zeros)
+    }
+
+    /// Writes `v` as a signed exponential-Golomb code of order 0, i.e. the `se(v)` of H.264.
+    ///
+    /// # Panics
+    /// `write_signed_exp_golomb` panics if `v` is [i64::MIN], which isn't representable.
+    pub fn write_signed_exp_golomb(&mut self, v: i64) -> Result<(), Error> {
+        let k = if v > 0 {
+            (v as u64) * 2 - 1
+        } else {
+            v.unsigned_abs()
+                .checked_mul(2)
+                .expect("binary: exp-Golomb overflows")
+        };
+        self.write_exp_golomb(k)
+    }
+}
+
+impl<W, O> Drop for BitWriter<W, O>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        if self.used > 0 {
+            let _ = self.w.write_all(&[self.cur]);
+        }
+        let _ = self.w.flush();
+    }
+}
+
+/// Returns the mask selecting the lower `n` bits of a byte.
+fn mask(n: u32) -> u8 {
+    (((1u16) << n) - 1) as u8
+}
+
+#[cfg(test)]
+mod tests;
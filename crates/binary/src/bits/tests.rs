@@ -0,0 +1,173 @@
+use std::io;
+
+use crate::{BitOrder, BitReader, BitWriter, Error, LsbFirst, MsbFirst};
+
+const DATA: &[u8] = &[0b1010_1100, 0b0101_0011, 0xff, 0x01];
+
+#[test]
+fn read_msb_first() {
+    let mut r = BitReader::<_, MsbFirst>::new(DATA);
+
+    assert!(r.read_bit().unwrap());
+    assert_eq!(0b010, r.read_bits(3).unwrap());
+    assert_eq!(0b1100_0101, r.read_bits(8).unwrap());
+    assert!(!r.is_aligned());
+    assert_eq!(12, r.position());
+
+    r.align();
+    assert!(r.is_aligned());
+    assert_eq!(16, r.position());
+    assert_eq!(0xff01, r.read_bits(16).unwrap());
+}
+
+#[test]
+fn read_lsb_first() {
+    let mut r = BitReader::<_, LsbFirst>::new(DATA);
+
+    assert!(!r.read_bit().unwrap());
+    assert_eq!(0b110, r.read_bits(3).unwrap());
+    assert_eq!(0b0011_1010, r.read_bits(8).unwrap());
+
+    r.align();
+    assert_eq!(0x01ff, r.read_bits(16).unwrap());
+}
+
+#[test]
+fn read_64_bits() {
+    let data = 0x0123456789abcdefu64.to_be_bytes();
+    let mut r = BitReader::<_, MsbFirst>::new(&data[..]);
+    assert_eq!(0x0123456789abcdef, r.read_bits(64).unwrap());
+
+    let data = 0x0123456789abcdefu64.to_le_bytes();
+    let mut r = BitReader::<_, LsbFirst>::new(&data[..]);
+    assert_eq!(0x0123456789abcdef, r.read_bits(64).unwrap());
+}
+
+#[test]
+fn read_eof() {
+    let mut r = BitReader::<_, MsbFirst>::new(&DATA[..1]);
+
+    r.read_bits(3).unwrap();
+    match r.read_bits(7) {
+        Err(Error::IO(err, n)) => {
+            assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+            assert_eq!(5, n, "unexpected #(bits) read");
+        }
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn exp_golomb() {
+    // ue(v) codes from H.264 9.1 concatenated: 1, 010, 011, 00100, 00101, 00110, 00111, 0001000
+    let data = [
+        0b1010_0110,
+        0b0100_0010,
+        0b1001_1000,
+        0b1110_0010,
+        0b0000_0000,
+    ];
+    let mut r = BitReader::<_, MsbFirst>::new(&data[..]);
+    for expect in 0..8 {
+        assert_eq!(expect, r.read_exp_golomb().unwrap());
+    }
+
+    match r.read_exp_golomb() {
+        Err(Error::IO(err, _)) => assert_eq!(io::ErrorKind::UnexpectedEof, err.kind()),
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn exp_golomb_overflow() {
+    let data = [0u8; 9];
+    let mut r = BitReader::<_, MsbFirst>::new(&data[..]);
+    match r.read_exp_golomb() {
+        Err(Error::CorruptInputError(_, 0)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn write_msb_first() {
+    let mut out = vec![];
+    {
+        let mut w = BitWriter::<_, MsbFirst>::new(&mut out);
+        w.write_bit(true).unwrap();
+        w.write_bits(0b010, 3).unwrap();
+        w.write_bits(0b1100_0101, 8).unwrap();
+        assert_eq!(12, w.position());
+        w.align().unwrap();
+        assert!(w.is_aligned());
+        w.write_bits(0xff01, 16).unwrap();
+        w.write_bits(0b1, 1).unwrap();
+    }
+
+    assert_eq!(vec![0b1010_1100, 0b0101_0000, 0xff, 0x01, 0b1000_0000], out);
+}
+
+#[test]
+fn write_lsb_first() {
+    let mut out = vec![];
+    let mut w = BitWriter::<_, LsbFirst>::new(&mut out);
+    w.write_bit(false).unwrap();
+    w.write_bits(0b110, 3).unwrap();
+    w.write_bits(0b0011_1010, 8).unwrap();
+    w.align().unwrap();
+    w.write_bits(0x01ff, 16).unwrap();
+    w.flush().unwrap();
+    assert_eq!(4, w.get_ref().len());
+    std::mem::drop(w);
+
+    assert_eq!(vec![0b1010_1100, 0b0000_0011, 0xff, 0x01], out);
+}
+
+#[test]
+fn write_short_buffer() {
+    let mut buf = [0u8; 1];
+    let mut w = BitWriter::<_, MsbFirst>::new(&mut buf[..]);
+    match w.write_bits(0xabcd, 16) {
+        Err(Error::IO(err, 8)) => assert_eq!(io::ErrorKind::WriteZero, err.kind()),
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+fn test_round_trip<O: BitOrder>() {
+    let values: Vec<(u64, u32)> = (0..=64)
+        .map(|n| (u64::MAX.checked_shr(64 - n).unwrap_or(0) ^ 0x5555, n))
+        .collect();
+    let golombs = [0u64, 1, 2, 3, 255, 256, 1 << 40, u64::MAX - 1];
+    let signed_golombs = [0i64, 1, -1, 2, -2, i64::MAX, i64::MIN + 1];
+
+    let mut out = vec![];
+    {
+        let mut w = BitWriter::<_, O>::new(&mut out);
+        for &(v, n) in values.iter() {
+            w.write_bits(v, n).unwrap();
+        }
+        for &v in golombs.iter() {
+            w.write_exp_golomb(v).unwrap();
+        }
+        for &v in signed_golombs.iter() {
+            w.write_signed_exp_golomb(v).unwrap();
+        }
+    }
+
+    let mut r = BitReader::<_, O>::new(out.as_slice());
+    for &(v, n) in values.iter() {
+        let expect = if n == 64 { v } else { v & ((1 << n) - 1) };
+        assert_eq!(expect, r.read_bits(n).unwrap(), "read_bits({})", n);
+    }
+    for &v in golombs.iter() {
+        assert_eq!(v, r.read_exp_golomb().unwrap());
+    }
+    for &v in signed_golombs.iter() {
+        assert_eq!(v, r.read_signed_exp_golomb().unwrap());
+    }
+}
+
+#[test]
+fn round_trip() {
+    test_round_trip::<MsbFirst>();
+    test_round_trip::<LsbFirst>();
+}
@@ -0,0 +1,34 @@
+use crate::Error;
+
+/// A `BinaryEncode` type knows how to encode itself into a byte slice.
+///
+/// It's usually implemented with `#[derive(BinaryEncode)]` with the `derive` feature enabled,
+/// which lays out fields in declaration order as
+/// - [FixedSize](crate::FixedSize) values in the byte order of the struct (big-endian by
+///   default), which can be overridden per field;
+/// - varints encoded by [put_uvarint](crate::put_uvarint) or [put_varint](crate::put_varint);
+/// - zero padding bytes.
+pub trait BinaryEncode {
+    /// Returns the number of bytes [BinaryEncode::encode] would write.
+    fn encoded_len(&self) -> usize;
+
+    /// Encodes `self` into `dst` and returns the number of bytes written.
+    ///
+    /// The error is [Error::IO] if `dst` is too small, which carries the number of elements
+    /// encoded before the failure, or [Error::Overflow] if a varint field is out of the range
+    /// of 64-bit integers.
+    fn encode(&self, dst: &mut [u8]) -> Result<usize, Error>;
+}
+
+/// A `BinaryDecode` type knows how to decode itself from a byte slice.
+///
+/// It's usually implemented with `#[derive(BinaryDecode)]` with the `derive` feature enabled,
+/// which requires every field to implement [Default]. Arrays of any length are supported as
+/// long as their elements do. See [BinaryEncode] for the layout.
+pub trait BinaryDecode: Sized {
+    /// Decodes a value from `src` and returns it along with the number of bytes read.
+    ///
+    /// The error is [Error::IO] if `src` is too small, which carries the number of elements
+    /// decoded before the failure, or [Error::Overflow] if a varint doesn't fit into its field.
+    fn decode(src: &[u8]) -> Result<(Self, usize), Error>;
+}
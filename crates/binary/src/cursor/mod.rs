@@ -0,0 +1,279 @@
+use crate::{ByteOrder, Error, MAX_VARINT_LEN64};
+
+/// `ByteReader` parses values sequentially from a byte slice, keeping track of the current
+/// offset.
+///
+/// Every read either consumes exactly the bytes of the value or fails without consuming
+/// anything, with an error recording the offset where the failure happened:
+/// - [Error::UnexpectedEof] if not enough bytes are left;
+/// - [Error::VarintOverflow] if a varint overflows.
+///
+/// # Example
+/// ```
+/// use binary::{BigEndian, ByteReader, LittleEndian};
+///
+/// let data = [0x01, 0x02, 0x03, 0x04, 0xac, 0x02, b'h', b'i'];
+/// let mut r = ByteReader::new(&data);
+///
+/// assert_eq!(0x0102, r.read_u16::<BigEndian>().unwrap());
+/// assert_eq!(0x0403, r.read_u16::<LittleEndian>().unwrap());
+/// assert_eq!(300, r.read_uvarint().unwrap());
+/// assert_eq!(b"hi", r.read_bytes(2).unwrap());
+/// assert!(r.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ByteReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+/// `ByteWriter` writes values sequentially into either a fixed-size byte slice or a growable
+/// byte vector, keeping track of the current offset.
+///
+/// Writing beyond the end of a slice fails without writing anything with
+/// [Error::ShortBuffer], telling the total number of bytes needed and the slice length.
+/// Writing into a vector appends to it and never fails.
+///
+/// # Example
+/// ```
+/// use binary::{BigEndian, ByteWriter};
+///
+/// let mut out = vec![];
+/// let mut w = ByteWriter::from_vec(&mut out);
+/// w.write_u16::<BigEndian>(0x0102).unwrap();
+/// w.write_uvarint(300).unwrap();
+/// w.write_bytes(b"hi").unwrap();
+/// assert_eq!(6, w.position());
+///
+/// assert_eq!(vec![0x01, 0x02, 0xac, 0x02, b'h', b'i'], out);
+/// ```
+#[derive(Debug)]
+pub struct ByteWriter<'a> {
+    dst: Dst<'a>,
+    pos: usize,
+}
+
+#[derive(Debug)]
+enum Dst<'a> {
+    Slice(&'a mut [u8]),
+    /// A vector along with its length when the writer is created.
+    Vec(&'a mut Vec<u8>, usize),
+}
+
+macro_rules! read_fn {
+    ($name:ident, $t:ty, $len:expr, $get:ident) => {
+        #[doc = concat!("Reads a `", stringify!($t), "` in the byte order `B`.")]
+        pub fn $name<B>(&mut self) -> Result<$t, Error>
+        where
+            B: ByteOrder,
+        {
+            self.take($len).map(B::$get)
+        }
+    };
+}
+
+macro_rules! write_fn {
+    ($name:ident, $t:ty, $len:expr, $put:ident) => {
+        #[doc = concat!("Writes a `", stringify!($t), "` in the byte order `B`.")]
+        pub fn $name<B>(&mut self, v: $t) -> Result<(), Error>
+        where
+            B: ByteOrder,
+        {
+            self.reserve($len).map(|dst| B::$put(dst, v))
+        }
+    };
+}
+
+impl<'a> ByteReader<'a> {
+    /// Returns a new reader parsing `buf` from its beginning.
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Tells whether all bytes have been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+
+    /// Returns the offset of the next byte to read.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the bytes not consumed yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Reads the next `n` bytes.
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        self.take(n)
+    }
+
+    /// Reads a single byte.
+    pub fn read_u8(&mut self) -> Result<u8, Error> {
+        self.take(1).map(|v| v[0])
+    }
+
+    /// Reads a single byte as an i8.
+    pub fn read_i8(&mut self) -> Result<i8, Error> {
+        self.read_u8().map(|v| v as i8)
+    }
+
+    read_fn!(read_u16, u16, 2, uint16);
+    read_fn!(read_i16, i16, 2, int16);
+    read_fn!(read_u24, u32, 3, uint24);
+    read_fn!(read_i24, i32, 3, int24);
+    read_fn!(read_u32, u32, 4, uint32);
+    read_fn!(read_i32, i32, 4, int32);
+    read_fn!(read_u64, u64, 8, uint64);
+    read_fn!(read_i64, i64, 8, int64);
+    read_fn!(read_u128, u128, 16, uint128);
+    read_fn!(read_i128, i128, 16, int128);
+    read_fn!(read_f32, f32, 4, float32);
+    read_fn!(read_f64, f64, 8, float64);
+
+    /// Reads an unsigned varint as generated by [put_uvarint](crate::put_uvarint).
+    pub fn read_uvarint(&mut self) -> Result<u64, Error> {
+        let (x, n) = crate::uvariant(self.remaining());
+        self.advance_varint(n).map(|_| x)
+    }
+
+    /// Reads a signed varint as generated by [put_varint](crate::put_varint).
+    pub fn read_varint(&mut self) -> Result<i64, Error> {
+        let (x, n) = crate::variant(self.remaining());
+        self.advance_varint(n).map(|_| x)
+    }
+
+    /// Skips the next `n` bytes.
+    pub fn skip(&mut self, n: usize) -> Result<(), Error> {
+        self.take(n).map(|_| ())
+    }
+
+    fn advance_varint(&mut self, n: isize) -> Result<(), Error> {
+        match n {
+            0 => Err(Error::UnexpectedEof(self.pos)),
+            n if n < 0 => Err(Error::VarintOverflow(self.pos)),
+            n => {
+                self.pos += n as usize;
+                Ok(())
+            }
+        }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.buf.len() - self.pos < n {
+            return Err(Error::UnexpectedEof(self.pos));
+        }
+
+        let out = &self.buf[self.pos..(self.pos + n)];
+        self.pos += n;
+        Ok(out)
+    }
+}
+
+impl<'a> ByteWriter<'a> {
+    /// Returns a new writer filling `dst` from its beginning.
+    pub fn new(dst: &'a mut [u8]) -> Self {
+        Self {
+            dst: Dst::Slice(dst),
+            pos: 0,
+        }
+    }
+
+    /// Returns a new writer appending to `dst`. The position counts from the end of `dst`
+    /// at the time the writer is created.
+    pub fn from_vec(dst: &'a mut Vec<u8>) -> Self {
+        let start = dst.len();
+        Self {
+            dst: Dst::Vec(dst, start),
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Writes all bytes of `v`.
+    pub fn write_bytes(&mut self, v: &[u8]) -> Result<(), Error> {
+        self.reserve(v.len()).map(|dst| dst.copy_from_slice(v))
+    }
+
+    /// Writes a single byte.
+    pub fn write_u8(&mut self, v: u8) -> Result<(), Error> {
+        self.reserve(1).map(|dst| dst[0] = v)
+    }
+
+    /// Writes an i8 as a single byte.
+    pub fn write_i8(&mut self, v: i8) -> Result<(), Error> {
+        self.write_u8(v as u8)
+    }
+
+    write_fn!(write_u16, u16, 2, put_uint16);
+    write_fn!(write_i16, i16, 2, put_int16);
+    write_fn!(write_u24, u32, 3, put_uint24);
+    write_fn!(write_i24, i32, 3, put_int24);
+    write_fn!(write_u32, u32, 4, put_uint32);
+    write_fn!(write_i32, i32, 4, put_int32);
+    write_fn!(write_u64, u64, 8, put_uint64);
+    write_fn!(write_i64, i64, 8, put_int64);
+    write_fn!(write_u128, u128, 16, put_uint128);
+    write_fn!(write_i128, i128, 16, put_int128);
+    write_fn!(write_f32, f32, 4, put_float32);
+    write_fn!(write_f64, f64, 8, put_float64);
+
+    /// Writes an unsigned varint as generated by [put_uvarint](crate::put_uvarint).
+    pub fn write_uvarint(&mut self, x: u64) -> Result<(), Error> {
+        let mut buf = [0u8; MAX_VARINT_LEN64];
+        let n = crate::put_uvarint(&mut buf, x);
+        self.write_bytes(&buf[..n])
+    }
+
+    /// Writes a signed varint as generated by [put_varint](crate::put_varint).
+    pub fn write_varint(&mut self, x: i64) -> Result<(), Error> {
+        let mut buf = [0u8; MAX_VARINT_LEN64];
+        let n = crate::put_varint(&mut buf, x);
+        self.write_bytes(&buf[..n])
+    }
+
+    /// Skips the next `n` bytes, which are zero-filled when appending to a vector and left
+    /// untouched in a slice.
+    pub fn skip(&mut self, n: usize) -> Result<(), Error> {
+        self.reserve(n).map(|_| ())
+    }
+
+    fn reserve(&mut self, n: usize) -> Result<&mut [u8], Error> {
+        let pos = self.pos;
+        let out = match &mut self.dst {
+            Dst::Slice(dst) => {
+                let end = match pos.checked_add(n) {
+                    Some(end) if end <= dst.len() => end,
+                    _ => return Err(Error::ShortBuffer(pos.saturating_add(n), dst.len())),
+                };
+                &mut dst[pos..end]
+            }
+            Dst::Vec(dst, start) => {
+                let start = *start;
+                let short =
+                    |dst: &Vec<u8>| Error::ShortBuffer(pos.saturating_add(n), dst.len() - start);
+                let end = match pos.checked_add(n).and_then(|v| start.checked_add(v)) {
+                    Some(end) => end,
+                    None => return Err(short(dst)),
+                };
+                if dst.len() < end {
+                    dst.try_reserve(end - dst.len()).map_err(|_| short(dst))?;
+                    dst.resize(end, 0);
+                }
+                &mut dst[(start + pos)..end]
+            }
+        };
+
+        self.pos += n;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests;
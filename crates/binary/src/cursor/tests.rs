@@ -0,0 +1,152 @@
+use crate::{BigEndian, ByteReader, ByteWriter, Error, LittleEndian};
+
+#[test]
+fn read() {
+    #[rustfmt::skip]
+    let data = [
+        0xff,
+        0x01, 0x02,
+        0x01, 0x02, 0x03,
+        0x04, 0x03, 0x02, 0x01,
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+        0x3f, 0x80, 0x00, 0x00,
+        0xac, 0x02,
+        0x03,
+        b'a', b'b', b'c',
+    ];
+
+    let mut r = ByteReader::new(&data);
+    assert_eq!(-1, r.read_i8().unwrap());
+    assert_eq!(0x0102, r.read_u16::<BigEndian>().unwrap());
+    assert_eq!(0x030201, r.read_u24::<LittleEndian>().unwrap());
+    assert_eq!(0x01020304, r.read_u32::<LittleEndian>().unwrap());
+    assert_eq!(0x0102030405060708, r.read_i64::<BigEndian>().unwrap());
+    assert_eq!(1.0, r.read_f32::<BigEndian>().unwrap());
+    assert_eq!(300, r.read_uvarint().unwrap());
+    assert_eq!(-2, r.read_varint().unwrap());
+    assert_eq!(25, r.position());
+
+    r.skip(1).unwrap();
+    assert_eq!(b"bc", r.remaining());
+    assert_eq!(b"bc", r.read_bytes(2).unwrap());
+    assert!(r.is_empty());
+}
+
+#[test]
+fn read_eof() {
+    let data = [0x01, 0x02, 0x03, 0x80];
+    let mut r = ByteReader::new(&data);
+    r.read_u8().unwrap();
+
+    match r.read_u32::<BigEndian>() {
+        Err(Error::UnexpectedEof(1)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    match r.read_bytes(4) {
+        Err(Error::UnexpectedEof(1)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert_eq!(1, r.position(), "failed read shouldn't advance");
+
+    r.skip(2).unwrap();
+    match r.read_uvarint() {
+        Err(Error::UnexpectedEof(3)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert_eq!(3, r.position(), "failed read shouldn't advance");
+}
+
+#[test]
+fn read_overflow() {
+    let mut data = vec![0x00];
+    data.extend_from_slice(&[0xff; 10]);
+    data.push(0x01);
+
+    let mut r = ByteReader::new(&data);
+    r.skip(1).unwrap();
+    match r.read_varint() {
+        Err(Error::VarintOverflow(1)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn write_slice() {
+    let mut buf = [0u8; 8];
+    let mut w = ByteWriter::new(&mut buf);
+    w.write_u16::<BigEndian>(0x0102).unwrap();
+    w.write_i16::<LittleEndian>(-2).unwrap();
+    w.skip(1).unwrap();
+    w.write_varint(-2).unwrap();
+
+    match w.write_u32::<BigEndian>(0x01020304) {
+        Err(Error::ShortBuffer(10, 8)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert_eq!(6, w.position(), "failed write shouldn't advance");
+
+    w.write_bytes(b"hi").unwrap();
+    assert_eq!([0x01, 0x02, 0xfe, 0xff, 0x00, 0x03, b'h', b'i'], buf);
+}
+
+#[test]
+fn write_vec() {
+    let mut out = vec![0xff];
+    let mut w = ByteWriter::from_vec(&mut out);
+    w.write_u32::<LittleEndian>(0x01020304).unwrap();
+    w.skip(2).unwrap();
+    w.write_uvarint(300).unwrap();
+    w.write_f64::<BigEndian>(1.0).unwrap();
+    assert_eq!(16, w.position());
+
+    #[rustfmt::skip]
+    let expect = [
+        0xff,
+        0x04, 0x03, 0x02, 0x01,
+        0x00, 0x00,
+        0xac, 0x02,
+        0x3f, 0xf0, 0, 0, 0, 0, 0, 0,
+    ];
+    assert_eq!(&expect[..], out.as_slice());
+}
+
+#[test]
+fn write_skip_overflow() {
+    let mut buf = [0u8; 8];
+    let mut w = ByteWriter::new(&mut buf);
+    w.skip(2).unwrap();
+    match w.skip(usize::MAX) {
+        Err(Error::ShortBuffer(usize::MAX, 8)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert_eq!(2, w.position());
+
+    let mut out = vec![0xff];
+    let mut w = ByteWriter::from_vec(&mut out);
+    w.skip(2).unwrap();
+    match w.skip(usize::MAX - 1) {
+        Err(Error::ShortBuffer(usize::MAX, 2)) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    match w.skip(usize::MAX / 2) {
+        Err(Error::ShortBuffer(n, 2)) if n == usize::MAX / 2 + 2 => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert_eq!(2, w.position());
+    assert_eq!([0xff, 0, 0], out.as_slice());
+}
+
+#[test]
+fn round_trip() {
+    let mut out = vec![];
+    let mut w = ByteWriter::from_vec(&mut out);
+    w.write_u128::<BigEndian>(u128::MAX - 1).unwrap();
+    w.write_i24::<LittleEndian>(-3).unwrap();
+    w.write_uvarint(u64::MAX).unwrap();
+
+    let mut r = ByteReader::new(&out);
+    assert_eq!(u128::MAX - 1, r.read_u128::<BigEndian>().unwrap());
+    assert_eq!(-3, r.read_i24::<LittleEndian>().unwrap());
+    assert_eq!(u64::MAX, r.read_uvarint().unwrap());
+    assert!(r.is_empty());
+}
@@ -8,8 +8,14 @@ pub enum Error {
     CorruptInputError(&'static str, usize),
     #[error("IO error: with {1} elements read/written")]
     IO(io::Error, usize),
-    #[error("binary: varint overflows a 64-bit integer")]
+    #[error("binary: varint overflows its integer type")]
     Overflow,
+    #[error("binary: short buffer, need {0} bytes but got {1}")]
+    ShortBuffer(usize, usize),
+    #[error("binary: unexpected EOF at input byte {0}")]
+    UnexpectedEof(usize),
+    #[error("binary: varint overflows a 64-bit integer at input byte {0}")]
+    VarintOverflow(usize),
 }
 
 impl From<Error> for io::Error {
@@ -0,0 +1,154 @@
+use std::io::{Read, Write};
+
+use crate::{ByteOrder, Error};
+
+macro_rules! read_fn {
+    ($name:ident, $t:ty, $len:expr, $get:ident) => {
+        #[doc = concat!("Reads a `", stringify!($t), "` in the byte order `B`.")]
+        fn $name<B>(&mut self) -> Result<$t, Error>
+        where
+            B: ByteOrder,
+        {
+            let mut b = [0u8; $len];
+            read_exact(self, &mut b).map(|_| B::$get(&b))
+        }
+    };
+}
+
+macro_rules! write_fn {
+    ($name:ident, $t:ty, $len:expr, $put:ident) => {
+        #[doc = concat!("Writes a `", stringify!($t), "` in the byte order `B`.")]
+        fn $name<B>(&mut self, v: $t) -> Result<(), Error>
+        where
+            B: ByteOrder,
+        {
+            let mut b = [0u8; $len];
+            B::$put(&mut b, v);
+            write_all(self, &b)
+        }
+    };
+}
+
+/// `ReadBytesExt` extends every [Read] with methods decoding numbers from the stream.
+///
+/// Failures of the underlying reader are reported as [Error::IO], where the count of elements
+/// read is always 0 since values are decoded as a whole.
+///
+/// # Example
+/// ```
+/// use binary::{BigEndian, LittleEndian, ReadBytesExt};
+///
+/// let mut r: &[u8] = &[0x01, 0x02, 0x03, 0x04, 0xac, 0x02];
+///
+/// assert_eq!(0x0102, r.read_u16::<BigEndian>().unwrap());
+/// assert_eq!(0x0403, r.read_u16::<LittleEndian>().unwrap());
+/// assert_eq!(300, r.read_uvarint().unwrap());
+/// ```
+pub trait ReadBytesExt: Read {
+    /// Reads a single byte.
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        let mut b = [0u8; 1];
+        read_exact(self, &mut b).map(|_| b[0])
+    }
+
+    /// Reads a single byte as an i8.
+    fn read_i8(&mut self) -> Result<i8, Error> {
+        self.read_u8().map(|v| v as i8)
+    }
+
+    read_fn!(read_u16, u16, 2, uint16);
+    read_fn!(read_i16, i16, 2, int16);
+    read_fn!(read_u24, u32, 3, uint24);
+    read_fn!(read_i24, i32, 3, int24);
+    read_fn!(read_u32, u32, 4, uint32);
+    read_fn!(read_i32, i32, 4, int32);
+    read_fn!(read_u64, u64, 8, uint64);
+    read_fn!(read_i64, i64, 8, int64);
+    read_fn!(read_u128, u128, 16, uint128);
+    read_fn!(read_i128, i128, 16, int128);
+    read_fn!(read_f32, f32, 4, float32);
+    read_fn!(read_f64, f64, 8, float64);
+
+    /// Reads an unsigned varint. See [read_uvarint](crate::read_uvarint).
+    fn read_uvarint(&mut self) -> Result<u64, Error> {
+        crate::read_uvarint(self)
+    }
+
+    /// Reads a signed varint. See [read_varint](crate::read_varint).
+    fn read_varint(&mut self) -> Result<i64, Error> {
+        crate::read_varint(self)
+    }
+}
+
+/// `WriteBytesExt` extends every [Write] with methods encoding numbers into the stream.
+///
+/// Failures of the underlying writer are reported as [Error::IO], where the count of elements
+/// written is always 0 since values are encoded as a whole.
+///
+/// # Example
+/// ```
+/// use binary::{BigEndian, WriteBytesExt};
+///
+/// let mut w = vec![];
+/// w.write_u16::<BigEndian>(0x0102).unwrap();
+/// w.write_uvarint(300).unwrap();
+///
+/// assert_eq!(vec![0x01, 0x02, 0xac, 0x02], w);
+/// ```
+pub trait WriteBytesExt: Write {
+    /// Writes a single byte.
+    fn write_u8(&mut self, v: u8) -> Result<(), Error> {
+        write_all(self, &[v])
+    }
+
+    /// Writes an i8 as a single byte.
+    fn write_i8(&mut self, v: i8) -> Result<(), Error> {
+        self.write_u8(v as u8)
+    }
+
+    write_fn!(write_u16, u16, 2, put_uint16);
+    write_fn!(write_i16, i16, 2, put_int16);
+    write_fn!(write_u24, u32, 3, put_uint24);
+    write_fn!(write_i24, i32, 3, put_int24);
+    write_fn!(write_u32, u32, 4, put_uint32);
+    write_fn!(write_i32, i32, 4, put_int32);
+    write_fn!(write_u64, u64, 8, put_uint64);
+    write_fn!(write_i64, i64, 8, put_int64);
+    write_fn!(write_u128, u128, 16, put_uint128);
+    write_fn!(write_i128, i128, 16, put_int128);
+    write_fn!(write_f32, f32, 4, put_float32);
+    write_fn!(write_f64, f64, 8, put_float64);
+
+    /// Writes an unsigned varint and returns the number of bytes written. See
+    /// [write_uvarint](crate::write_uvarint).
+    fn write_uvarint(&mut self, x: u64) -> Result<usize, Error> {
+        crate::write_uvarint(self, x)
+    }
+
+    /// Writes a signed varint and returns the number of bytes written. See
+    /// [write_varint](crate::write_varint).
+    fn write_varint(&mut self, x: i64) -> Result<usize, Error> {
+        crate::write_varint(self, x)
+    }
+}
+
+impl<R> ReadBytesExt for R where R: Read + ?Sized {}
+
+impl<W> WriteBytesExt for W where W: Write + ?Sized {}
+
+fn read_exact<R>(r: &mut R, buf: &mut [u8]) -> Result<(), Error>
+where
+    R: Read + ?Sized,
+{
+    r.read_exact(buf).map_err(|err| Error::IO(err, 0))
+}
+
+fn write_all<W>(w: &mut W, buf: &[u8]) -> Result<(), Error>
+where
+    W: Write + ?Sized,
+{
+    w.write_all(buf).map_err(|err| Error::IO(err, 0))
+}
+
+#[cfg(test)]
+mod tests;
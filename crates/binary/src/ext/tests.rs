@@ -0,0 +1,73 @@
+use std::io;
+
+use crate::{BigEndian, Error, LittleEndian, ReadBytesExt, WriteBytesExt};
+
+#[test]
+fn round_trip() {
+    let mut w = vec![];
+    w.write_u8(0xff).unwrap();
+    w.write_i8(-2).unwrap();
+    w.write_u16::<BigEndian>(0x0102).unwrap();
+    w.write_i16::<LittleEndian>(-3).unwrap();
+    w.write_u24::<BigEndian>(0x010203).unwrap();
+    w.write_i24::<LittleEndian>(-4).unwrap();
+    w.write_u32::<LittleEndian>(0x01020304).unwrap();
+    w.write_i32::<BigEndian>(i32::MIN).unwrap();
+    w.write_u64::<BigEndian>(0x0102030405060708).unwrap();
+    w.write_i64::<LittleEndian>(i64::MIN + 1).unwrap();
+    w.write_u128::<LittleEndian>(u128::MAX - 1).unwrap();
+    w.write_i128::<BigEndian>(-5).unwrap();
+    w.write_f32::<BigEndian>(1.5).unwrap();
+    w.write_f64::<LittleEndian>(-2.5).unwrap();
+    assert_eq!(2, w.write_uvarint(300).unwrap());
+    assert_eq!(1, w.write_varint(-1).unwrap());
+
+    assert_eq!(&[0xff, 0xfe, 0x01, 0x02, 0xfd, 0xff], &w[..6]);
+
+    let mut r = w.as_slice();
+    assert_eq!(0xff, r.read_u8().unwrap());
+    assert_eq!(-2, r.read_i8().unwrap());
+    assert_eq!(0x0102, r.read_u16::<BigEndian>().unwrap());
+    assert_eq!(-3, r.read_i16::<LittleEndian>().unwrap());
+    assert_eq!(0x010203, r.read_u24::<BigEndian>().unwrap());
+    assert_eq!(-4, r.read_i24::<LittleEndian>().unwrap());
+    assert_eq!(0x01020304, r.read_u32::<LittleEndian>().unwrap());
+    assert_eq!(i32::MIN, r.read_i32::<BigEndian>().unwrap());
+    assert_eq!(0x0102030405060708, r.read_u64::<BigEndian>().unwrap());
+    assert_eq!(i64::MIN + 1, r.read_i64::<LittleEndian>().unwrap());
+    assert_eq!(u128::MAX - 1, r.read_u128::<LittleEndian>().unwrap());
+    assert_eq!(-5, r.read_i128::<BigEndian>().unwrap());
+    assert_eq!(1.5, r.read_f32::<BigEndian>().unwrap());
+    assert_eq!(-2.5, r.read_f64::<LittleEndian>().unwrap());
+    assert_eq!(300, r.read_uvarint().unwrap());
+    assert_eq!(-1, r.read_varint().unwrap());
+    assert!(r.is_empty());
+}
+
+#[test]
+fn read_eof() {
+    let mut r: &[u8] = &[0x01, 0x02, 0x03];
+    match r.read_u32::<BigEndian>() {
+        Err(Error::IO(err, 0)) => assert_eq!(io::ErrorKind::UnexpectedEof, err.kind()),
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn write_short_buffer() {
+    let mut buf = [0u8; 3];
+    let mut w = buf.as_mut_slice();
+    match w.write_u32::<BigEndian>(0x01020304) {
+        Err(Error::IO(err, 0)) => assert_eq!(io::ErrorKind::WriteZero, err.kind()),
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn dyn_stream() {
+    let mut w: Box<dyn io::Write> = Box::new(vec![]);
+    w.write_u16::<BigEndian>(1).unwrap();
+
+    let mut r: Box<dyn io::Read> = Box::new(&[0x00u8, 0x01][..]);
+    assert_eq!(1, r.read_u16::<BigEndian>().unwrap());
+}
@@ -0,0 +1,235 @@
+use std::io::{self, Read, Write};
+
+use crate::{BigEndian, ByteOrder, Error};
+
+/// A `FixedSize` value is either a fixed-size arithmetic type (bool, i8, u8, i16, f32, ...)
+/// or an array, slice or struct containing only fixed-size values.
+///
+/// Implementations for user structs are expected to read and write their fields in order,
+/// threading the element counter `n` through every field, as
+/// ```
+/// use std::io::{self, Read, Write};
+///
+/// use binary::{ByteOrder, FixedSize};
+///
+/// struct Header {
+///     magic: [u8; 4],
+///     version: u16,
+///     length: u32,
+/// }
+///
+/// impl FixedSize for Header {
+///     fn read_from<R, B>(&mut self, r: &mut R, n: &mut usize) -> io::Result<()>
+///     where
+///         R: Read,
+///         B: ByteOrder,
+///     {
+///         self.magic.read_from::<R, B>(r, n)?;
+///         self.version.read_from::<R, B>(r, n)?;
+///         self.length.read_from::<R, B>(r, n)
+///     }
+///
+///     fn write_to<W, B>(&self, w: &mut W, n: &mut usize) -> io::Result<()>
+///     where
+///         W: Write,
+///         B: ByteOrder,
+///     {
+///         self.magic.write_to::<W, B>(w, n)?;
+///         self.version.write_to::<W, B>(w, n)?;
+///         self.length.write_to::<W, B>(w, n)
+///     }
+/// }
+/// ```
+pub trait FixedSize {
+    /// Reads structured binary data from `r` into `self` in the byte order `B`, incrementing `n`
+    /// by one for every fixed-size arithmetic element decoded successfully.
+    fn read_from<R, B>(&mut self, r: &mut R, n: &mut usize) -> io::Result<()>
+    where
+        R: Read,
+        B: ByteOrder;
+
+    /// Writes the binary representation of `self` into `w` in the byte order `B`, incrementing
+    /// `n` by one for every fixed-size arithmetic element encoded successfully.
+    fn write_to<W, B>(&self, w: &mut W, n: &mut usize) -> io::Result<()>
+    where
+        W: Write,
+        B: ByteOrder;
+
+    /// Returns how many bytes `self` occupies once encoded.
+    ///
+    /// The default implementation counts the bytes emitted by [FixedSize::write_to].
+    fn size(&self) -> usize {
+        let mut w = Counter(0);
+        self.write_to::<Counter, BigEndian>(&mut w, &mut 0)
+            .expect("counting never fails");
+        w.0
+    }
+}
+
+/// Reads structured binary data from `r` into `data`. Data must be a [FixedSize] value.
+/// Bytes read from `r` are decoded using the specified byte `order` and written to successive
+/// fields of the data. When decoding boolean values, a zero byte is decoded as false, and any
+/// other non-zero byte is decoded as true.
+///
+/// The error is [Error::IO] if fewer bytes than required are read, which carries the number of
+/// elements decoded before the failure.
+pub fn read<R, B, T>(r: &mut R, _order: B, data: &mut T) -> Result<(), Error>
+where
+    R: Read,
+    B: ByteOrder,
+    T: FixedSize + ?Sized,
+{
+    let mut n = 0usize;
+    data.read_from::<R, B>(r, &mut n)
+        .map_err(|err| Error::IO(err, n))
+}
+
+/// Returns how many bytes [write] would generate to encode the value `data`, which must be a
+/// [FixedSize] value, for example to preallocate buffers.
+pub fn size<T>(data: &T) -> usize
+where
+    T: FixedSize + ?Sized,
+{
+    data.size()
+}
+
+/// Writes the binary representation of `data` into `w`. Data must be a [FixedSize] value.
+/// Boolean values encode as one byte: 1 for true, and 0 for false. Bytes written to `w` are
+/// encoded using the specified byte `order` and read from successive fields of the data.
+///
+/// The error is [Error::IO] if `w` fails, which carries the number of elements encoded before
+/// the failure.
+pub fn write<W, B, T>(w: &mut W, _order: B, data: &T) -> Result<(), Error>
+where
+    W: Write,
+    B: ByteOrder,
+    T: FixedSize + ?Sized,
+{
+    let mut n = 0usize;
+    data.write_to::<W, B>(w, &mut n)
+        .map_err(|err| Error::IO(err, n))
+}
+
+macro_rules! impl_fixed_size {
+    ($t:ty, $len:expr, |$src:ident| $decode:expr, |$dst:ident, $v:ident| $encode:expr) => {
+        impl FixedSize for $t {
+            fn read_from<R, B>(&mut self, r: &mut R, n: &mut usize) -> io::Result<()>
+            where
+                R: Read,
+                B: ByteOrder,
+            {
+                let mut $src = [0u8; $len];
+                r.read_exact(&mut $src)?;
+                *self = $decode;
+                *n += 1;
+                Ok(())
+            }
+
+            fn write_to<W, B>(&self, w: &mut W, n: &mut usize) -> io::Result<()>
+            where
+                W: Write,
+                B: ByteOrder,
+            {
+                let mut $dst = [0u8; $len];
+                let $v = *self;
+                $encode;
+                w.write_all(&$dst)?;
+                *n += 1;
+                Ok(())
+            }
+
+            fn size(&self) -> usize {
+                $len
+            }
+        }
+    };
+}
+
+impl_fixed_size!(bool, 1, |b| b[0] != 0, |b, v| b[0] = v as u8);
+impl_fixed_size!(i8, 1, |b| b[0] as i8, |b, v| b[0] = v as u8);
+impl_fixed_size!(u8, 1, |b| b[0], |b, v| b[0] = v);
+impl_fixed_size!(i16, 2, |b| B::int16(&b), |b, v| B::put_int16(&mut b, v));
+impl_fixed_size!(u16, 2, |b| B::uint16(&b), |b, v| B::put_uint16(&mut b, v));
+impl_fixed_size!(i32, 4, |b| B::int32(&b), |b, v| B::put_int32(&mut b, v));
+impl_fixed_size!(u32, 4, |b| B::uint32(&b), |b, v| B::put_uint32(&mut b, v));
+impl_fixed_size!(i64, 8, |b| B::int64(&b), |b, v| B::put_int64(&mut b, v));
+impl_fixed_size!(u64, 8, |b| B::uint64(&b), |b, v| B::put_uint64(&mut b, v));
+impl_fixed_size!(i128, 16, |b| B::int128(&b), |b, v| B::put_int128(&mut b, v));
+impl_fixed_size!(u128, 16, |b| B::uint128(&b), |b, v| B::put_uint128(
+    &mut b, v
+));
+impl_fixed_size!(f32, 4, |b| B::float32(&b), |b, v| B::put_float32(&mut b, v));
+impl_fixed_size!(f64, 8, |b| B::float64(&b), |b, v| B::put_float64(&mut b, v));
+
+impl<T> FixedSize for [T]
+where
+    T: FixedSize,
+{
+    fn read_from<R, B>(&mut self, r: &mut R, n: &mut usize) -> io::Result<()>
+    where
+        R: Read,
+        B: ByteOrder,
+    {
+        for v in self.iter_mut() {
+            v.read_from::<R, B>(r, n)?;
+        }
+        Ok(())
+    }
+
+    fn write_to<W, B>(&self, w: &mut W, n: &mut usize) -> io::Result<()>
+    where
+        W: Write,
+        B: ByteOrder,
+    {
+        for v in self.iter() {
+            v.write_to::<W, B>(w, n)?;
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> usize {
+        self.iter().map(FixedSize::size).sum()
+    }
+}
+
+impl<T, const N: usize> FixedSize for [T; N]
+where
+    T: FixedSize,
+{
+    fn read_from<R, B>(&mut self, r: &mut R, n: &mut usize) -> io::Result<()>
+    where
+        R: Read,
+        B: ByteOrder,
+    {
+        self.as_mut_slice().read_from::<R, B>(r, n)
+    }
+
+    fn write_to<W, B>(&self, w: &mut W, n: &mut usize) -> io::Result<()>
+    where
+        W: Write,
+        B: ByteOrder,
+    {
+        self.as_slice().write_to::<W, B>(w, n)
+    }
+
+    fn size(&self) -> usize {
+        self.as_slice().size()
+    }
+}
+
+/// `Counter` is a writer counting the bytes written into it.
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
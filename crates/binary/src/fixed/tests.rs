@@ -0,0 +1,201 @@
+use std::io::{self, Read, Write};
+
+use crate::{BigEndian, ByteOrder, Error, FixedSize, LittleEndian};
+
+#[derive(Debug, Default, PartialEq)]
+struct Struct {
+    int8: i8,
+    int16: i16,
+    int32: i32,
+    int64: i64,
+    uint8: u8,
+    uint16: u16,
+    uint32: u32,
+    uint64: u64,
+    float32: f32,
+    float64: f64,
+    array: [u8; 4],
+    bool: bool,
+    bool_array: [bool; 4],
+}
+
+impl FixedSize for Struct {
+    fn read_from<R, B>(&mut self, r: &mut R, n: &mut usize) -> io::Result<()>
+    where
+        R: Read,
+        B: ByteOrder,
+    {
+        self.int8.read_from::<R, B>(r, n)?;
+        self.int16.read_from::<R, B>(r, n)?;
+        self.int32.read_from::<R, B>(r, n)?;
+        self.int64.read_from::<R, B>(r, n)?;
+        self.uint8.read_from::<R, B>(r, n)?;
+        self.uint16.read_from::<R, B>(r, n)?;
+        self.uint32.read_from::<R, B>(r, n)?;
+        self.uint64.read_from::<R, B>(r, n)?;
+        self.float32.read_from::<R, B>(r, n)?;
+        self.float64.read_from::<R, B>(r, n)?;
+        self.array.read_from::<R, B>(r, n)?;
+        self.bool.read_from::<R, B>(r, n)?;
+        self.bool_array.read_from::<R, B>(r, n)
+    }
+
+    fn write_to<W, B>(&self, w: &mut W, n: &mut usize) -> io::Result<()>
+    where
+        W: Write,
+        B: ByteOrder,
+    {
+        self.int8.write_to::<W, B>(w, n)?;
+        self.int16.write_to::<W, B>(w, n)?;
+        self.int32.write_to::<W, B>(w, n)?;
+        self.int64.write_to::<W, B>(w, n)?;
+        self.uint8.write_to::<W, B>(w, n)?;
+        self.uint16.write_to::<W, B>(w, n)?;
+        self.uint32.write_to::<W, B>(w, n)?;
+        self.uint64.write_to::<W, B>(w, n)?;
+        self.float32.write_to::<W, B>(w, n)?;
+        self.float64.write_to::<W, B>(w, n)?;
+        self.array.write_to::<W, B>(w, n)?;
+        self.bool.write_to::<W, B>(w, n)?;
+        self.bool_array.write_to::<W, B>(w, n)
+    }
+}
+
+fn new_struct() -> Struct {
+    Struct {
+        int8: 0x01,
+        int16: 0x0203,
+        int32: 0x04050607,
+        int64: 0x08090a0b0c0d0e0f,
+        uint8: 0x10,
+        uint16: 0x1112,
+        uint32: 0x13141516,
+        uint64: 0x1718191a1b1c1d1e,
+        float32: f32::from_bits(0x1f202122),
+        float64: f64::from_bits(0x232425262728292a),
+        array: [0x43, 0x44, 0x45, 0x46],
+        bool: true,
+        bool_array: [true, false, true, false],
+    }
+}
+
+#[rustfmt::skip]
+const BIG: &[u8] = &[
+    1,
+    2, 3,
+    4, 5, 6, 7,
+    8, 9, 10, 11, 12, 13, 14, 15,
+    16,
+    17, 18,
+    19, 20, 21, 22,
+    23, 24, 25, 26, 27, 28, 29, 30,
+    31, 32, 33, 34,
+    35, 36, 37, 38, 39, 40, 41, 42,
+    67, 68, 69, 70,
+    1,
+    1, 0, 1, 0,
+];
+
+#[rustfmt::skip]
+const LITTLE: &[u8] = &[
+    1,
+    3, 2,
+    7, 6, 5, 4,
+    15, 14, 13, 12, 11, 10, 9, 8,
+    16,
+    18, 17,
+    22, 21, 20, 19,
+    30, 29, 28, 27, 26, 25, 24, 23,
+    34, 33, 32, 31,
+    42, 41, 40, 39, 38, 37, 36, 35,
+    67, 68, 69, 70,
+    1,
+    1, 0, 1, 0,
+];
+
+#[test]
+fn big_endian_read() {
+    let mut got = Struct::default();
+    let mut r = BIG;
+    crate::read(&mut r, BigEndian, &mut got).expect("read");
+
+    assert_eq!(new_struct(), got);
+    assert!(r.is_empty(), "{} bytes left unread", r.len());
+}
+
+#[test]
+fn big_endian_write() {
+    let mut got = vec![];
+    crate::write(&mut got, BigEndian, &new_struct()).expect("write");
+
+    assert_eq!(BIG, got.as_slice());
+}
+
+#[test]
+fn little_endian_read() {
+    let mut got = Struct::default();
+    let mut r = LITTLE;
+    crate::read(&mut r, LittleEndian, &mut got).expect("read");
+
+    assert_eq!(new_struct(), got);
+}
+
+#[test]
+fn little_endian_write() {
+    let mut got = vec![];
+    crate::write(&mut got, LittleEndian, &new_struct()).expect("write");
+
+    assert_eq!(LITTLE, got.as_slice());
+}
+
+#[test]
+fn read_slice() {
+    let mut got = [0u32; 4];
+    let mut r: &[u8] = &[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0];
+    crate::read(&mut r, LittleEndian, got.as_mut_slice()).expect("read");
+
+    assert_eq!([1u32, 2, 3, 4], got);
+}
+
+#[test]
+fn read_bool() {
+    let mut got = [false; 3];
+    crate::read(&mut [0u8, 1, 2].as_ref(), BigEndian, &mut got).expect("read");
+
+    assert_eq!([false, true, true], got);
+}
+
+#[test]
+fn read_truncated() {
+    // cut off in the middle of the 'uint32' field, which is the 7th element
+    let mut r = &BIG[..20];
+    match crate::read(&mut r, BigEndian, &mut Struct::default()) {
+        Err(Error::IO(err, n)) => {
+            assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+            assert_eq!(6, n, "unexpected #(elements) read");
+        }
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn write_short_buffer() {
+    let mut buf = [0u8; 20];
+    let mut w = buf.as_mut_slice();
+    match crate::write(&mut w, BigEndian, &new_struct()) {
+        Err(Error::IO(err, n)) => {
+            assert_eq!(io::ErrorKind::WriteZero, err.kind());
+            assert_eq!(6, n, "unexpected #(elements) written");
+        }
+        v => panic!("unexpected result: {:?}", v),
+    }
+}
+
+#[test]
+fn size() {
+    assert_eq!(BIG.len(), crate::size(&new_struct()));
+    assert_eq!(8, crate::size(&[0u16; 4]));
+    assert_eq!(12, crate::size([1.0f32, 2.0, 3.0].as_slice()));
+    assert_eq!(16, crate::size(&-1i128));
+    assert_eq!(1, crate::size(&true));
+}
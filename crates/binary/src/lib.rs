@@ -16,9 +16,22 @@
 //! should look at more advanced solutions such as protocol buffers.
 
 mod binary;
+mod bits;
+mod codec;
+mod cursor;
 mod errors;
+mod ext;
+mod fixed;
 mod varint;
 
 pub use self::binary::*;
+pub use bits::*;
+pub use codec::*;
+pub use cursor::*;
 pub use errors::*;
+pub use ext::*;
+pub use fixed::*;
 pub use varint::*;
+
+#[cfg(feature = "derive")]
+pub use binary_derive::{BinaryDecode, BinaryEncode};
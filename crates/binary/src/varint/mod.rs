@@ -1,4 +1,5 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
+use std::iter::FusedIterator;
 
 use crate::Error;
 
@@ -8,6 +9,48 @@ pub const MAX_VARINT_LEN16: usize = 3;
 pub const MAX_VARINT_LEN32: usize = 5;
 /// `MaxVarintLen64` is the maximum length of a varint-encoded 64-bit integer.
 pub const MAX_VARINT_LEN64: usize = 10;
+/// `MAX_VARINT_LEN128` is the maximum length of a varint-encoded 128-bit integer.
+pub const MAX_VARINT_LEN128: usize = 19;
+/// `MAX_SQLITE_VARINT_LEN` is the maximum length of a SQLite-varint-encoded 64-bit integer.
+pub const MAX_SQLITE_VARINT_LEN: usize = 9;
+
+/// Appends the varint-encoded form of `x`, as generated by [put_uvarint], to `buf`.
+pub fn append_uvarint(buf: &mut Vec<u8>, x: u64) {
+    let mut x = x;
+    while x >= 0x80 {
+        buf.push((x as u8) | 0x80);
+        x >>= 7;
+    }
+    buf.push(x as u8);
+}
+
+/// Appends the varint-encoded form of `x`, as generated by [put_varint], to `buf`.
+pub fn append_varint(buf: &mut Vec<u8>, x: i64) {
+    let ux = if x >= 0 {
+        (x as u64) << 1
+    } else {
+        !((x as u64) << 1)
+    };
+
+    append_uvarint(buf, ux)
+}
+
+/// Returns the number of bytes [put_uvarint] would write to encode `x`.
+pub fn uvarint_len(x: u64) -> usize {
+    let bits = 64 - (x | 1).leading_zeros() as usize;
+    (bits + 6) / 7
+}
+
+/// Returns the number of bytes [put_varint] would write to encode `x`.
+pub fn varint_len(x: i64) -> usize {
+    let ux = if x >= 0 {
+        (x as u64) << 1
+    } else {
+        !((x as u64) << 1)
+    };
+
+    uvarint_len(ux)
+}
 
 /// Encodes a uint64 into `buf` and returns the number of bytes written.
 /// If the buffer is too small, `put_uvarint` will panic.
@@ -42,7 +85,7 @@ pub fn put_varint(buf: &mut [u8], x: i64) -> usize {
 /// Reads an encoded unsigned integer from `r` and returns it as a uint64.
 pub fn read_uvarint<R>(r: &mut R) -> Result<u64, Error>
 where
-    R: Read,
+    R: Read + ?Sized,
 {
     let mut x = 0u64;
     let mut s = 0u32;
@@ -71,7 +114,7 @@ where
 /// Reads an encoded signed integer from `r` and returns it as an int64.
 pub fn read_varint<R>(r: &mut R) -> Result<i64, Error>
 where
-    R: Read,
+    R: Read + ?Sized,
 {
     let ux = read_uvarint(r)?;
     // @see ZigZag encoding as https://developers.google.com/protocol-buffers/docs/encoding#signed-integers
@@ -84,6 +127,41 @@ where
     Ok(x)
 }
 
+/// Encodes a uint64 into `w` and returns the number of bytes written.
+pub fn write_uvarint<W>(w: &mut W, x: u64) -> Result<usize, Error>
+where
+    W: Write + ?Sized,
+{
+    let mut buf = [0u8; MAX_VARINT_LEN64];
+    let n = put_uvarint(&mut buf, x);
+
+    let mut written = 0usize;
+    while written < n {
+        match w.write(&buf[written..n]) {
+            Ok(0) => return Err(Error::IO(io::ErrorKind::WriteZero.into(), written)),
+            Ok(m) => written += m,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(Error::IO(err, written)),
+        }
+    }
+
+    Ok(n)
+}
+
+/// Encodes an int64 into `w` and returns the number of bytes written.
+pub fn write_varint<W>(w: &mut W, x: i64) -> Result<usize, Error>
+where
+    W: Write + ?Sized,
+{
+    let ux = if x >= 0 {
+        (x as u64) << 1
+    } else {
+        !((x as u64) << 1)
+    };
+
+    write_uvarint(w, ux)
+}
+
 /// Decodes a uint64 from `buf` and returns that value and the
 /// number of bytes read (> 0). If an error occurred, the value is 0
 /// and the number of bytes `n` is <= 0 meaning:
@@ -135,5 +213,324 @@ pub fn variant(buf: &[u8]) -> (i64, isize) {
     (x, n)
 }
 
+/// Encodes a uint128 into `buf` and returns the number of bytes written.
+/// If the buffer is too small, `put_uvarint128` will panic.
+pub fn put_uvarint128(buf: &mut [u8], x: u128) -> usize {
+    let mut i = 0usize;
+
+    let mut x = x;
+    while x >= 0x80 {
+        buf[i] = (x as u8) | 0x80;
+        x >>= 7;
+        i += 1;
+    }
+    buf[i] = x as u8;
+
+    i + 1
+}
+
+/// Encodes an int128 into `buf` and returns the number of bytes written.
+/// If the buffer is too small, `put_varint128` will panic.
+pub fn put_varint128(buf: &mut [u8], x: i128) -> usize {
+    let ux = if x >= 0 {
+        (x as u128) << 1
+    } else {
+        !((x as u128) << 1)
+    };
+
+    put_uvarint128(buf, ux)
+}
+
+/// Decodes a uint128 from `buf` and returns that value and the
+/// number of bytes read (> 0). If an error occurred, the value is 0
+/// and the number of bytes `n` is <= 0 meaning:
+/// ```text
+/// n == 0: buf too small
+/// n  < 0: value larger than 128 bits (overflow)
+///         and -n is the number of bytes read
+/// ```
+pub fn uvariant128(buf: &[u8]) -> (u128, isize) {
+    let mut x = 0u128;
+    let mut s = 0u32;
+
+    for (i, &b) in buf.iter().enumerate() {
+        if b < 0x80 {
+            if i > MAX_VARINT_LEN128 - 1 || (i == MAX_VARINT_LEN128 - 1 && b > 3) {
+                return (0, -((i + 1) as isize));
+            }
+
+            let (xx, _) = (b as u128).overflowing_shl(s);
+            return (x | xx, (i + 1) as isize);
+        }
+
+        let (xx, _) = ((b & 0x7f) as u128).overflowing_shl(s);
+        x |= xx;
+        s += 7;
+    }
+
+    (0, 0)
+}
+
+/// Decodes an int128 from `buf` and returns that value and the
+/// number of bytes read (> 0). If an error occurred, the value is 0
+/// and the number of bytes `n` is <= 0 with the following meaning:
+/// ```text
+/// n == 0: buf too small
+/// n  < 0: value larger than 128 bits (overflow)
+///         and -n is the number of bytes read
+/// ```
+pub fn variant128(buf: &[u8]) -> (i128, isize) {
+    let (ux, n) = uvariant128(buf);
+    let x = {
+        let mut v = (ux >> 1) as i128;
+        if ux & 1 != 0 {
+            v = !v;
+        }
+        v
+    };
+
+    (x, n)
+}
+
+/// `Uvarints` iterates over a byte slice of packed unsigned varints, as returned by [uvarints].
+pub struct Uvarints<'a> {
+    buf: &'a [u8],
+    offset: usize,
+}
+
+/// `Varints` iterates over a byte slice of packed signed varints, as returned by [varints].
+pub struct Varints<'a>(Uvarints<'a>);
+
+/// Returns an iterator over the varints packed in `buf` as generated by [put_uvarint], which
+/// yields every value along with its offset in `buf` without allocating.
+///
+/// A truncated entry is reported as [Error::UnexpectedEof] and an overflowing one as
+/// [Error::VarintOverflow], both with the offset of the entry, after which the iteration
+/// stops.
+///
+/// # Example
+/// ```
+/// let buf = [0x01, 0xac, 0x02, 0x7f];
+///
+/// let got: Vec<_> = binary::uvarints(&buf).map(Result::unwrap).collect();
+/// assert_eq!(vec![(1, 0), (300, 1), (127, 3)], got);
+/// ```
+pub fn uvarints(buf: &[u8]) -> Uvarints<'_> {
+    Uvarints { buf, offset: 0 }
+}
+
+/// Returns an iterator over the varints packed in `buf` as generated by [put_varint]. See
+/// [uvarints] for details.
+pub fn varints(buf: &[u8]) -> Varints<'_> {
+    Varints(uvarints(buf))
+}
+
+impl<'a> Iterator for Uvarints<'a> {
+    type Item = Result<(u64, usize), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+
+        let offset = self.offset;
+        let (x, n) = uvariant(self.buf);
+        if n <= 0 {
+            self.buf = &[];
+            let err = if n == 0 {
+                Error::UnexpectedEof(offset)
+            } else {
+                Error::VarintOverflow(offset)
+            };
+            return Some(Err(err));
+        }
+
+        self.buf = &self.buf[(n as usize)..];
+        self.offset += n as usize;
+        Some(Ok((x, offset)))
+    }
+}
+
+impl<'a> FusedIterator for Uvarints<'a> {}
+
+impl<'a> Iterator for Varints<'a> {
+    type Item = Result<(i64, usize), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.0.next()?;
+        Some(v.map(|(ux, offset)| {
+            let x = if ux & 1 == 0 {
+                (ux >> 1) as i64
+            } else {
+                !((ux >> 1) as i64)
+            };
+            (x, offset)
+        }))
+    }
+}
+
+impl<'a> FusedIterator for Varints<'a> {}
+
+/// Encodes an int64 as signed LEB128 into `buf` and returns the number of bytes written, which
+/// is at most [MAX_VARINT_LEN64]. If the buffer is too small, `put_sleb128` will panic.
+///
+/// Signed LEB128, as used by DWARF and WebAssembly, sign-extends the last byte rather than
+/// zigzag-encoding the value as [put_varint] does. The unsigned LEB128 is exactly the
+/// encoding of [put_uvarint].
+pub fn put_sleb128(buf: &mut [u8], x: i64) -> usize {
+    let mut i = 0usize;
+
+    let mut x = x;
+    loop {
+        let b = (x as u8) & 0x7f;
+        x >>= 7;
+        if (x == 0 && (b & 0x40) == 0) || (x == -1 && (b & 0x40) != 0) {
+            buf[i] = b;
+            return i + 1;
+        }
+        buf[i] = b | 0x80;
+        i += 1;
+    }
+}
+
+/// Decodes a signed LEB128 from `buf` and returns that value and the
+/// number of bytes read (> 0). If an error occurred, the value is 0
+/// and the number of bytes `n` is <= 0 meaning:
+/// ```text
+/// n == 0: buf too small
+/// n  < 0: value larger than 64 bits (overflow)
+///         and -n is the number of bytes read
+/// ```
+pub fn sleb128(buf: &[u8]) -> (i64, isize) {
+    let mut x = 0i64;
+    let mut s = 0u32;
+
+    for (i, &b) in buf.iter().enumerate() {
+        if i == MAX_VARINT_LEN64 - 1 {
+            // the last byte holds bit 63, and the rest must be its sign-extension
+            if b >= 0x80 || (b != 0 && b != 0x7f) {
+                return (0, -((i + 1) as isize));
+            }
+            return (x | ((b as i64) << s), (i + 1) as isize);
+        }
+
+        x |= ((b & 0x7f) as i64) << s;
+        s += 7;
+        if b < 0x80 {
+            if (b & 0x40) != 0 {
+                x |= -1i64 << s;
+            }
+            return (x, (i + 1) as isize);
+        }
+    }
+
+    (0, 0)
+}
+
+/// Encodes a uint64 as a SQLite varint into `buf` and returns the number of bytes written,
+/// which is at most [MAX_SQLITE_VARINT_LEN]. If the buffer is too small, `put_sqlite_varint`
+/// will panic.
+///
+/// SQLite varints are big-endian: each of the first 8 bytes carries 7 bits with the high bit
+/// set if more bytes follow, and the 9th byte, if any, carries 8 bits.
+pub fn put_sqlite_varint(buf: &mut [u8], x: u64) -> usize {
+    if (x >> 56) != 0 {
+        buf[8] = x as u8;
+        let mut x = x >> 8;
+        for i in (0..8).rev() {
+            buf[i] = (x as u8) | 0x80;
+            x >>= 7;
+        }
+        return MAX_SQLITE_VARINT_LEN;
+    }
+
+    let n = {
+        let bits = (64 - x.leading_zeros() as usize).max(1);
+        (bits + 6) / 7
+    };
+    let mut x = x;
+    for i in (0..n).rev() {
+        buf[i] = (x as u8) | 0x80;
+        x >>= 7;
+    }
+    buf[n - 1] &= 0x7f;
+
+    n
+}
+
+/// Decodes a SQLite varint from `buf` and returns that value and the
+/// number of bytes read (> 0). If `buf` is too small, the value is 0 and
+/// the number of bytes is 0. SQLite varints never overflow.
+pub fn sqlite_varint(buf: &[u8]) -> (u64, isize) {
+    let mut x = 0u64;
+
+    for (i, &b) in buf.iter().enumerate() {
+        if i == MAX_SQLITE_VARINT_LEN - 1 {
+            return ((x << 8) | (b as u64), (i + 1) as isize);
+        }
+
+        x = (x << 7) | ((b & 0x7f) as u64);
+        if b < 0x80 {
+            return (x, (i + 1) as isize);
+        }
+    }
+
+    (0, 0)
+}
+
+/// Encodes a uint64 as a Git offset varint into `buf` and returns the number of bytes written,
+/// which is at most [MAX_VARINT_LEN64]. If the buffer is too small, `put_git_varint` will
+/// panic.
+///
+/// Git offset varints, as used by the `OFS_DELTA` entries of packfiles, are big-endian with the
+/// high bit of each byte set if more bytes follow, and add one to the value before every
+/// continuation so that every value has a single encoding.
+pub fn put_git_varint(buf: &mut [u8], x: u64) -> usize {
+    let mut tmp = [0u8; MAX_VARINT_LEN64];
+    let mut i = tmp.len() - 1;
+
+    let mut x = x;
+    tmp[i] = (x as u8) & 0x7f;
+    x >>= 7;
+    while x != 0 {
+        x -= 1;
+        i -= 1;
+        tmp[i] = (x as u8) | 0x80;
+        x >>= 7;
+    }
+
+    let n = tmp.len() - i;
+    buf[..n].copy_from_slice(&tmp[i..]);
+    n
+}
+
+/// Decodes a Git offset varint from `buf` and returns that value and the
+/// number of bytes read (> 0). If an error occurred, the value is 0
+/// and the number of bytes `n` is <= 0 meaning:
+/// ```text
+/// n == 0: buf too small
+/// n  < 0: value larger than 64 bits (overflow)
+///         and -n is the number of bytes read
+/// ```
+pub fn git_varint(buf: &[u8]) -> (u64, isize) {
+    let mut x = 0u64;
+
+    for (i, &b) in buf.iter().enumerate() {
+        if i > 0 {
+            if x >= (u64::MAX >> 7) {
+                return (0, -((i + 1) as isize));
+            }
+            x = (x + 1) << 7;
+        }
+
+        x |= (b & 0x7f) as u64;
+        if b < 0x80 {
+            return (x, (i + 1) as isize);
+        }
+    }
+
+    (0, 0)
+}
+
 #[cfg(test)]
 mod tests;
@@ -23,6 +23,16 @@ fn test_overflow(b: &[u8], n0: isize) {
 fn test_uvarint(x: u64) {
     let mut buf = vec![0u8; crate::MAX_VARINT_LEN64];
     let n = crate::put_uvarint(buf.as_mut_slice(), x);
+    assert_eq!(n, crate::uvarint_len(x), "uvarint_len({})", x);
+
+    let mut appended = vec![0xffu8];
+    crate::append_uvarint(&mut appended, x);
+    assert_eq!(&buf[..n], &appended[1..], "append_uvarint({})", x);
+
+    let mut written = vec![];
+    let m = crate::write_uvarint(&mut written, x).expect("write_uvarint");
+    assert_eq!(n, m, "write_uvarint({})", x);
+    assert_eq!(&buf[..n], written.as_slice(), "write_uvarint({})", x);
 
     let (y, m) = crate::uvariant(&buf[..n]);
 
@@ -39,6 +49,16 @@ fn test_uvarint(x: u64) {
 fn test_varint(x: i64) {
     let mut buf = vec![0u8; crate::MAX_VARINT_LEN64];
     let n = crate::put_varint(buf.as_mut_slice(), x);
+    assert_eq!(n, crate::varint_len(x), "varint_len({})", x);
+
+    let mut appended = vec![0xffu8];
+    crate::append_varint(&mut appended, x);
+    assert_eq!(&buf[..n], &appended[1..], "append_varint({})", x);
+
+    let mut written = vec![];
+    let m = crate::write_varint(&mut written, x).expect("write_varint");
+    assert_eq!(n, m, "write_varint({})", x);
+    assert_eq!(&buf[..n], written.as_slice(), "write_varint({})", x);
 
     let (y, m) = crate::variant(&buf[..n]);
 
@@ -133,6 +153,22 @@ fn overflow() {
     );
 }
 
+#[test]
+fn write_short_buffer() {
+    for i in 0..3 {
+        let mut buf = [0u8; 2];
+        let mut w = &mut buf[..i];
+        match crate::write_uvarint(&mut w, 1 << 14) {
+            Ok(_) => panic!("should fail"),
+            Err(Error::IO(err, n)) => {
+                assert_eq!(io::ErrorKind::WriteZero, err.kind());
+                assert_eq!(i, n, "expecting {} bytes written, but got {}", i, n);
+            }
+            Err(err) => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
 #[test]
 fn uvarint() {
     let test_vector = test_vector();
@@ -158,3 +194,231 @@ fn varint() {
         test_varint(x.overflowing_neg().0);
     }
 }
+
+#[test]
+fn sleb128() {
+    let vectors: [(i64, &[u8]); 10] = [
+        (0, &[0x00]),
+        (2, &[0x02]),
+        (-2, &[0x7e]),
+        (63, &[0x3f]),
+        (-64, &[0x40]),
+        (127, &[0xff, 0x00]),
+        (-127, &[0x81, 0x7f]),
+        (128, &[0x80, 0x01]),
+        (-128, &[0x80, 0x7f]),
+        (-129, &[0xff, 0x7e]),
+    ];
+
+    for (x, expect) in vectors {
+        let mut buf = [0u8; crate::MAX_VARINT_LEN64];
+        let n = crate::put_sleb128(&mut buf, x);
+        assert_eq!(expect, &buf[..n], "put_sleb128({})", x);
+    }
+
+    for x in test_vector() {
+        for x in [x, x.overflowing_neg().0] {
+            let mut buf = [0u8; crate::MAX_VARINT_LEN64];
+            let n = crate::put_sleb128(&mut buf, x);
+            assert_eq!((x, n as isize), crate::sleb128(&buf[..n]), "sleb128({})", x);
+            assert_eq!((0, 0), crate::sleb128(&buf[..n - 1]), "sleb128({})", x);
+        }
+    }
+
+    // non-canonical padding is fine
+    assert_eq!((-1, 3), crate::sleb128(&[0xff, 0xff, 0x7f]));
+    assert_eq!((0x40, 2), crate::sleb128(&[0xc0, 0x00]));
+
+    let overflows: [&[u8]; 3] = [
+        &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02],
+        &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7e],
+        &[
+            0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00,
+        ],
+    ];
+    for b in overflows {
+        assert_eq!((0, -10), crate::sleb128(b), "sleb128({:?})", b);
+    }
+}
+
+#[test]
+fn sqlite_varint() {
+    let vectors: [(u64, &[u8]); 6] = [
+        (0, &[0x00]),
+        (127, &[0x7f]),
+        (128, &[0x81, 0x00]),
+        (16383, &[0xff, 0x7f]),
+        (
+            (1 << 56) - 1,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+        ),
+        (
+            u64::MAX,
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        ),
+    ];
+
+    for (x, expect) in vectors {
+        let mut buf = [0u8; crate::MAX_SQLITE_VARINT_LEN];
+        let n = crate::put_sqlite_varint(&mut buf, x);
+        assert_eq!(expect, &buf[..n], "put_sqlite_varint({})", x);
+    }
+
+    let mut x = 0x07u64;
+    while x != 0 {
+        let mut buf = [0u8; crate::MAX_SQLITE_VARINT_LEN];
+        let n = crate::put_sqlite_varint(&mut buf, x);
+        assert_eq!(
+            (x, n as isize),
+            crate::sqlite_varint(&buf),
+            "sqlite_varint({})",
+            x
+        );
+        assert_eq!(
+            (0, 0),
+            crate::sqlite_varint(&buf[..n - 1]),
+            "sqlite_varint({})",
+            x
+        );
+        x <<= 1;
+    }
+}
+
+#[test]
+fn git_varint() {
+    let vectors: [(u64, &[u8]); 5] = [
+        (0, &[0x00]),
+        (127, &[0x7f]),
+        (128, &[0x80, 0x00]),
+        (16511, &[0xff, 0x7f]),
+        (16512, &[0x80, 0x80, 0x00]),
+    ];
+
+    for (x, expect) in vectors {
+        let mut buf = [0u8; crate::MAX_VARINT_LEN64];
+        let n = crate::put_git_varint(&mut buf, x);
+        assert_eq!(expect, &buf[..n], "put_git_varint({})", x);
+    }
+
+    for x in test_vector()
+        .into_iter()
+        .map(|v| v as u64)
+        .chain([u64::MAX])
+    {
+        let mut buf = [0u8; crate::MAX_VARINT_LEN64];
+        let n = crate::put_git_varint(&mut buf, x);
+        assert_eq!(
+            (x, n as isize),
+            crate::git_varint(&buf),
+            "git_varint({})",
+            x
+        );
+        assert_eq!(
+            (0, 0),
+            crate::git_varint(&buf[..n - 1]),
+            "git_varint({})",
+            x
+        );
+    }
+
+    assert_eq!((0, -10), crate::git_varint(&[0xff; 11]));
+}
+
+#[test]
+fn contants128() {
+    let mut buf = [0u8; crate::MAX_VARINT_LEN128];
+    assert_eq!(
+        crate::MAX_VARINT_LEN128,
+        crate::put_uvarint128(&mut buf, u128::MAX)
+    );
+    assert_eq!(
+        crate::MAX_VARINT_LEN64,
+        crate::put_uvarint128(&mut buf, u64::MAX as u128)
+    );
+}
+
+#[test]
+fn uvarint128() {
+    let mut x = 0x07u128;
+    while x != 0 {
+        let mut buf = [0u8; crate::MAX_VARINT_LEN128];
+        let n = crate::put_uvarint128(&mut buf, x);
+        assert_eq!(
+            (x, n as isize),
+            crate::uvariant128(&buf),
+            "uvarint128({})",
+            x
+        );
+        assert_eq!(
+            (0, 0),
+            crate::uvariant128(&buf[..n - 1]),
+            "uvarint128({})",
+            x
+        );
+
+        if x <= u64::MAX as u128 {
+            let mut buf64 = [0u8; crate::MAX_VARINT_LEN64];
+            let m = crate::put_uvarint(&mut buf64, x as u64);
+            assert_eq!(&buf64[..m], &buf[..n], "uvarint128({})", x);
+        }
+        x <<= 1;
+    }
+
+    let mut overflow = [0x80u8; crate::MAX_VARINT_LEN128];
+    overflow[crate::MAX_VARINT_LEN128 - 1] = 0x04;
+    assert_eq!((0, -19), crate::uvariant128(&overflow));
+}
+
+#[test]
+fn varint128() {
+    let vectors = [i128::MIN, i128::MIN + 1, -1, 0, 1, 63, 64, i128::MAX]
+        .into_iter()
+        .chain(test_vector().into_iter().map(|v| v as i128));
+    for x in vectors {
+        let mut buf = [0u8; crate::MAX_VARINT_LEN128];
+        let n = crate::put_varint128(&mut buf, x);
+        assert_eq!((x, n as isize), crate::variant128(&buf), "varint128({})", x);
+    }
+}
+
+#[test]
+fn iterators() {
+    let test_vector = test_vector();
+
+    let mut buf = vec![];
+    let mut offsets = vec![];
+    for &x in test_vector.iter() {
+        offsets.push(buf.len());
+        crate::append_varint(&mut buf, x);
+    }
+
+    let got: Vec<_> = crate::varints(&buf).map(Result::unwrap).collect();
+    let expect: Vec<_> = test_vector.iter().copied().zip(offsets).collect();
+    assert_eq!(expect, got);
+
+    let got: Vec<_> = crate::uvarints(&buf).map(|v| v.unwrap().1).collect();
+    let expect: Vec<_> = expect.iter().map(|v| v.1).collect();
+    assert_eq!(expect, got);
+}
+
+#[test]
+fn iterators_error() {
+    let mut it = crate::uvarints(&[0x01, 0x80, 0x80]);
+    assert_eq!(1, it.next().unwrap().unwrap().0);
+    match it.next() {
+        Some(Err(Error::UnexpectedEof(1))) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert!(it.next().is_none());
+
+    let mut buf = vec![0x00];
+    buf.extend_from_slice(&[0xff; 10]);
+    buf.push(0x01);
+    let mut it = crate::varints(&buf);
+    assert_eq!((0, 0), it.next().unwrap().unwrap());
+    match it.next() {
+        Some(Err(Error::VarintOverflow(1))) => {}
+        v => panic!("unexpected result: {:?}", v),
+    }
+    assert!(it.next().is_none());
+}
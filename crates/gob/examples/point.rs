@@ -0,0 +1,47 @@
+use gob::{Decoder, Encoder, Fields, GobDecode, GobEncode, Type, Value};
+
+#[derive(Debug, Default, PartialEq)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl GobEncode for Point {
+    fn gob_type() -> Type {
+        Type::structure("Point", [("X", i64::gob_type()), ("Y", i64::gob_type())])
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Struct(vec![
+            ("X".to_string(), self.x.to_value()),
+            ("Y".to_string(), self.y.to_value()),
+        ])
+    }
+}
+
+impl GobDecode for Point {
+    fn from_value(v: Value) -> Result<Self, gob::Error> {
+        let mut fields = Fields::try_from(v)?;
+        Ok(Self {
+            x: fields.take("X")?,
+            y: fields.take("Y")?,
+        })
+    }
+}
+
+fn main() {
+    let mut stream = vec![];
+    {
+        let mut enc = Encoder::new(&mut stream);
+        enc.encode(&Point { x: 22, y: 33 }).unwrap();
+        enc.encode(&vec![Point { x: 1, y: 2 }, Point::default()])
+            .unwrap();
+    }
+
+    let mut dec = Decoder::new(stream.as_slice());
+    assert_eq!(Point { x: 22, y: 33 }, dec.decode().unwrap());
+    assert_eq!(
+        vec![Point { x: 1, y: 2 }, Point::default()],
+        dec.decode::<Vec<Point>>().unwrap()
+    );
+}
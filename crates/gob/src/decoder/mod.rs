@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::rc::Rc;
+
+use crate::types::{self, WireType};
+use crate::wire::{self, State};
+use crate::{Error, GobDecode, Value};
+
+/// Maximum nesting depth of the values in a message, which bounds the recursion over type
+/// definitions the sender controls, such as a slice type whose elements are of that very type.
+/// It's low enough for decoding to fit in the 2 MiB stack of a spawned thread, even
+/// unoptimized.
+const MAX_NESTING_DEPTH: usize = 256;
+
+/// `Decoder` reads values from a gob stream, as written by [Encoder](crate::Encoder) or Go's
+/// `gob.Encoder`, recording type definitions as they come.
+///
+/// # Example
+/// ```
+/// let stream = [0x03, 0x04, 0x00, 0x0e, 0x05, 0x0c, 0x00, 0x02, b'h', b'i'];
+/// let mut dec = gob::Decoder::new(&stream[..]);
+///
+/// assert_eq!(7, dec.decode::<i64>().unwrap());
+/// assert_eq!("hi", dec.decode::<String>().unwrap());
+/// assert!(matches!(dec.decode::<i64>(), Err(gob::Error::Eof)));
+/// ```
+pub struct Decoder<R>
+where
+    R: Read,
+{
+    r: R,
+    types: HashMap<i64, Rc<WireType>>,
+    buf: Vec<u8>,
+}
+
+impl<R> Decoder<R>
+where
+    R: Read,
+{
+    /// Returns a new decoder reading from `r`.
+    pub fn new(r: R) -> Self {
+        Self {
+            r,
+            types: HashMap::new(),
+            buf: vec![],
+        }
+    }
+
+    /// Decodes the next value into `T`.
+    pub fn decode<T>(&mut self) -> Result<T, Error>
+    where
+        T: GobDecode,
+    {
+        self.decode_value().and_then(T::from_value)
+    }
+
+    /// Decodes the next value without knowing its type in advance.
+    ///
+    /// [Error::Eof] is returned if the stream ends right before the value, and
+    /// [Error::Corrupt] if the value nests more than 256 levels deep.
+    pub fn decode_value(&mut self) -> Result<Value, Error> {
+        let mut buf = std::mem::take(&mut self.buf);
+        let out = loop {
+            if let Err(err) = self.recv_message(&mut buf) {
+                break Err(err);
+            }
+
+            let mut state = State::new(&buf);
+            let id = match state.int() {
+                Ok(v) => v,
+                Err(err) => break Err(err),
+            };
+            if id >= 0 {
+                break decode_top(&mut self.types, &mut state, id, 0);
+            }
+
+            match decode_wire_type(&mut state) {
+                Ok(_) if !state.is_empty() => break Err(Error::Corrupt("extra data in buffer")),
+                Ok(wt) => {
+                    self.types.insert(-id, Rc::new(wt));
+                }
+                Err(err) => break Err(err),
+            }
+        };
+        self.buf = buf;
+
+        out
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.r
+    }
+
+    /// Unwraps the decoder, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.r
+    }
+
+    fn recv_message(&mut self, buf: &mut Vec<u8>) -> Result<(), Error> {
+        let n = wire::read_uint(&mut self.r)?.ok_or(Error::Eof)?;
+        if n >= wire::MAX_MESSAGE_LEN {
+            return Err(Error::Corrupt("message too big"));
+        }
+
+        buf.resize(n as usize, 0);
+        self.r.read_exact(buf).map_err(Error::Io)
+    }
+}
+
+/// Decodes a value sent on its own, which is preceded by a zero delta unless it's a struct.
+fn decode_top(
+    types: &mut HashMap<i64, Rc<WireType>>,
+    state: &mut State,
+    id: i64,
+    depth: usize,
+) -> Result<Value, Error> {
+    let is_struct = matches!(types.get(&id).map(|v| &**v), Some(WireType::Struct { .. }));
+    if !is_struct && state.uint()? != 0 {
+        return Err(Error::Corrupt("non-zero delta for singleton"));
+    }
+
+    decode_data(types, state, id, depth)
+}
+
+/// Decodes a value of the type `id`, nested `depth` levels deep in the message.
+fn decode_data(
+    types: &mut HashMap<i64, Rc<WireType>>,
+    state: &mut State,
+    id: i64,
+    depth: usize,
+) -> Result<Value, Error> {
+    if depth >= MAX_NESTING_DEPTH {
+        return Err(Error::Corrupt("values nested too deeply"));
+    }
+
+    let v = match id {
+        types::BOOL => Value::Bool(state.uint()? != 0),
+        types::INT => Value::Int(state.int()?),
+        types::UINT => Value::Uint(state.uint()?),
+        types::FLOAT => Value::Float(state.float()?),
+        types::BYTES => Value::Bytes(state.bytes()?.to_vec()),
+        types::STRING => Value::String(state.string()?),
+        types::COMPLEX => Value::Complex(state.float()?, state.float()?),
+        types::INTERFACE => decode_interface(types, state, depth + 1)?,
+        _ => {
+            let wt = match types.get(&id) {
+                Some(v) => Rc::clone(v),
+                None => return Err(Error::UnknownType(id)),
+            };
+            decode_wire_data(types, state, &wt, depth + 1)?
+        }
+    };
+
+    Ok(v)
+}
+
+fn decode_wire_data(
+    types: &mut HashMap<i64, Rc<WireType>>,
+    state: &mut State,
+    wt: &WireType,
+    depth: usize,
+) -> Result<Value, Error> {
+    let v = match wt {
+        WireType::Array { elem, len } => {
+            let n = state.len()?;
+            if n as u64 != *len {
+                return Err(Error::Corrupt("array length mismatch"));
+            }
+            Value::Array(decode_elems(types, state, *elem, n, depth)?)
+        }
+        WireType::Slice { elem } => {
+            let n = state.len()?;
+            Value::Slice(decode_elems(types, state, *elem, n, depth)?)
+        }
+        WireType::Map { key, elem } => {
+            let n = state.len()?;
+            let mut entries = Vec::with_capacity(n);
+            for _ in 0..n {
+                let k = decode_data(types, state, *key, depth)?;
+                let v = decode_data(types, state, *elem, depth)?;
+                entries.push((k, v));
+            }
+            Value::Map(entries)
+        }
+        WireType::Struct { fields } => {
+            let mut out = vec![];
+            state.fields(|state, i| {
+                let (name, id) = fields
+                    .get(i)
+                    .ok_or(Error::Corrupt("field number out of range"))?;
+                out.push((name.clone(), decode_data(types, state, *id, depth)?));
+                Ok(())
+            })?;
+            Value::Struct(out)
+        }
+        WireType::Marshaler => Value::Bytes(state.bytes()?.to_vec()),
+    };
+
+    Ok(v)
+}
+
+fn decode_elems(
+    types: &mut HashMap<i64, Rc<WireType>>,
+    state: &mut State,
+    elem: i64,
+    n: usize,
+    depth: usize,
+) -> Result<Vec<Value>, Error> {
+    let mut out = Vec::with_capacity(n);
+    for _ in 0..n {
+        out.push(decode_data(types, state, elem, depth)?);
+    }
+    Ok(out)
+}
+
+/// Decodes an interface value: the name its concrete type is registered under, the definitions
+/// of types not sent yet, the id of the concrete type, the byte count of the value and the
+/// value itself.
+fn decode_interface(
+    types: &mut HashMap<i64, Rc<WireType>>,
+    state: &mut State,
+    depth: usize,
+) -> Result<Value, Error> {
+    let name = state.string()?;
+    if name.is_empty() {
+        return Ok(Value::Nil);
+    }
+
+    let id = loop {
+        let id = state.int()?;
+        if id >= 0 {
+            break id;
+        }
+        let wt = decode_wire_type(state)?;
+        types.insert(-id, Rc::new(wt));
+        if !state.is_empty() {
+            // Byte count of the following message.
+            state.uint()?;
+        }
+    };
+    state.uint()?;
+
+    let v = decode_top(types, state, id, depth)?;
+    Ok(Value::Interface(name, Box::new(v)))
+}
+
+/// Decodes the wireType struct defining a type, which has one field per kind of type.
+fn decode_wire_type(state: &mut State) -> Result<WireType, Error> {
+    let mut out = None;
+    state.fields(|state, i| {
+        let wt = match i {
+            0 => {
+                let (mut elem, mut len) = (0, 0);
+                state.fields(|state, i| match i {
+                    0 => skip_common_type(state),
+                    1 => state.int().map(|v| elem = v),
+                    2 => state.int().map(|v| len = v),
+                    _ => Err(Error::Corrupt("unknown field of arrayType")),
+                })?;
+                let len =
+                    u64::try_from(len).map_err(|_| Error::Corrupt("negative array length"))?;
+                WireType::Array { elem, len }
+            }
+            1 => {
+                let mut elem = 0;
+                state.fields(|state, i| match i {
+                    0 => skip_common_type(state),
+                    1 => state.int().map(|v| elem = v),
+                    _ => Err(Error::Corrupt("unknown field of sliceType")),
+                })?;
+                WireType::Slice { elem }
+            }
+            2 => {
+                let mut fields = vec![];
+                state.fields(|state, i| match i {
+                    0 => skip_common_type(state),
+                    1 => {
+                        for _ in 0..state.len()? {
+                            let (mut name, mut id) = (String::new(), 0);
+                            state.fields(|state, i| match i {
+                                0 => state.string().map(|v| name = v),
+                                1 => state.int().map(|v| id = v),
+                                _ => Err(Error::Corrupt("unknown field of fieldType")),
+                            })?;
+                            fields.push((name, id));
+                        }
+                        Ok(())
+                    }
+                    _ => Err(Error::Corrupt("unknown field of structType")),
+                })?;
+                WireType::Struct { fields }
+            }
+            3 => {
+                let (mut key, mut elem) = (0, 0);
+                state.fields(|state, i| match i {
+                    0 => skip_common_type(state),
+                    1 => state.int().map(|v| key = v),
+                    2 => state.int().map(|v| elem = v),
+                    _ => Err(Error::Corrupt("unknown field of mapType")),
+                })?;
+                WireType::Map { key, elem }
+            }
+            4..=6 => {
+                state.fields(|state, i| match i {
+                    0 => skip_common_type(state),
+                    _ => Err(Error::Corrupt("unknown field of gobEncoderType")),
+                })?;
+                WireType::Marshaler
+            }
+            _ => return Err(Error::Corrupt("unknown field of wireType")),
+        };
+        out = Some(wt);
+        Ok(())
+    })?;
+
+    out.ok_or(Error::Corrupt("empty type definition"))
+}
+
+/// Skips the CommonType struct holding the name and id of a type, neither of which is needed.
+fn skip_common_type(state: &mut State) -> Result<(), Error> {
+    state.fields(|state, i| match i {
+        0 => state.bytes().map(|_| ()),
+        1 => state.int().map(|_| ()),
+        _ => Err(Error::Corrupt("unknown field of CommonType")),
+    })
+}
+
+#[cfg(test)]
+mod tests;
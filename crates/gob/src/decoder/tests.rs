@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::{Encoder, GobEncode, Type};
+
+use super::*;
+
+fn round_trip<T>(v: &T) -> T
+where
+    T: GobEncode + GobDecode,
+{
+    let mut out = vec![];
+    Encoder::new(&mut out).encode(v).unwrap();
+    Decoder::new(out.as_slice()).decode().unwrap()
+}
+
+#[test]
+fn decode_round_trip() {
+    assert_eq!(-12345, round_trip(&-12345i32));
+    assert_eq!(u64::MAX, round_trip(&u64::MAX));
+    assert!(round_trip(&true));
+    assert_eq!(-0.25, round_trip(&-0.25f64));
+    assert_eq!("hello", round_trip(&"hello".to_string()));
+    assert_eq!(vec![0u8, 1, 255], round_trip(&vec![0u8, 1, 255]));
+    assert_eq!([7u8, 8], round_trip(&[7u8, 8]));
+    assert_eq!(
+        vec![vec!["a".to_string()], vec![]],
+        round_trip(&vec![vec!["a".to_string()], vec![]])
+    );
+
+    let m: HashMap<i64, Vec<f64>> = [(1, vec![1.5]), (-2, vec![])].into();
+    assert_eq!(m, round_trip(&m));
+}
+
+#[test]
+fn decode_nested_struct() {
+    let inner = Type::structure("Inner", [("N", Type::Int)]);
+    let outer = Type::structure(
+        "Outer",
+        [
+            ("Name", Type::String),
+            ("In", inner.clone()),
+            ("Ins", Type::Slice(Box::new(inner))),
+        ],
+    );
+    let inner_value = |n| Value::Struct(vec![("N".to_string(), Value::Int(n))]);
+    let v = Value::Struct(vec![
+        ("Name".to_string(), Value::String("x".to_string())),
+        ("In".to_string(), inner_value(0)),
+        (
+            "Ins".to_string(),
+            Value::Slice(vec![inner_value(1), inner_value(0)]),
+        ),
+    ]);
+
+    let mut out = vec![];
+    let mut enc = Encoder::new(&mut out);
+    enc.encode_value(&outer, &v).unwrap();
+    enc.encode_value(&outer, &v).unwrap();
+
+    // Zero fields are missing after decoding.
+    let expect = Value::Struct(vec![
+        ("Name".to_string(), Value::String("x".to_string())),
+        ("In".to_string(), Value::Struct(vec![])),
+        (
+            "Ins".to_string(),
+            Value::Slice(vec![inner_value(1), Value::Struct(vec![])]),
+        ),
+    ]);
+    let mut dec = Decoder::new(out.as_slice());
+    assert_eq!(expect, dec.decode_value().unwrap());
+    assert_eq!(expect, dec.decode_value().unwrap());
+    assert!(matches!(dec.decode_value(), Err(Error::Eof)));
+}
+
+#[test]
+fn decode_interface() {
+    // struct { V interface{} } holding an int registered as "int", then a nil interface.
+    #[rustfmt::skip]
+    let stream = [
+        0x15, 0xff, 0x81, 0x03, 0x01, 0x01, 0x01, b'T', 0x01, 0xff, 0x82, 0x00, 0x01, 0x01, 0x01,
+        0x01, b'V', 0x01, 0x10, 0x00, 0x00, 0x00,
+        0x0c, 0xff, 0x82, 0x01, 0x03, b'i', b'n', b't', 0x04, 0x02, 0x00, 0x0e, 0x00,
+        0x05, 0xff, 0x82, 0x01, 0x00, 0x00,
+    ];
+
+    let mut dec = Decoder::new(&stream[..]);
+    assert_eq!(
+        Value::Struct(vec![(
+            "V".to_string(),
+            Value::Interface("int".to_string(), Box::new(Value::Int(7)))
+        )]),
+        dec.decode_value().unwrap()
+    );
+    assert_eq!(
+        Value::Struct(vec![("V".to_string(), Value::Nil)]),
+        dec.decode_value().unwrap()
+    );
+}
+
+#[test]
+fn decode_marshaler() {
+    // A type implementing BinaryMarshaler in Go.
+    #[rustfmt::skip]
+    let stream = [
+        0x0d, 0xff, 0x81, 0x06, 0x01, 0x01, 0x01, b'M', 0x01, 0xff, 0x82, 0x00, 0x00, 0x00,
+        0x06, 0xff, 0x82, 0x00, 0x02, 0xab, 0xcd,
+    ];
+
+    let mut dec = Decoder::new(&stream[..]);
+    assert_eq!(Value::Bytes(vec![0xab, 0xcd]), dec.decode_value().unwrap());
+}
+
+#[test]
+fn decode_errors() {
+    let tests: &[(&[u8], &str)] = &[
+        (&[0x03, 0x04, 0x01, 0x02], "non-zero delta"),
+        (&[0x03, 0xff, 0x82, 0x00], "unknown type"),
+        (&[0x04, 0x04, 0x00, 0x0e], "truncated message"),
+        (&[0x02, 0x0c, 0x00], "end of message"),
+        (&[0x04, 0x0c, 0x00, 0x01, 0xff], "invalid UTF-8"),
+        (&[0x03, 0x01, 0x0f, 0x00], "unknown wire type"),
+    ];
+
+    for &(input, desc) in tests {
+        let got = Decoder::new(input).decode_value();
+        let ok = match desc {
+            "unknown type" => matches!(got, Err(Error::UnknownType(65))),
+            "truncated message" => matches!(got, Err(Error::Io(_))),
+            _ => matches!(got, Err(Error::Corrupt(_))),
+        };
+        assert!(ok, "{}: unexpected result {:?}", desc, got);
+    }
+}
+
+#[test]
+fn decode_recursive_type() {
+    // type T []T, defined with the id 65.
+    #[rustfmt::skip]
+    let def = [
+        0x0d, 0xff, 0x81, 0x02, 0x01, 0x01, 0x01, b'T', 0x00, 0x01, 0xff, 0x82, 0x00, 0x00,
+    ];
+    let message = |body: &[u8]| {
+        let mut out = def.to_vec();
+        out.push(0xfc);
+        out.extend_from_slice(&(body.len() as u32 + 3).to_be_bytes());
+        out.extend_from_slice(&[0xff, 0x82, 0x00]);
+        out.extend_from_slice(body);
+        out
+    };
+
+    let stream = message(&[0x01, 0x00]);
+    assert_eq!(
+        Value::Slice(vec![Value::Slice(vec![])]),
+        Decoder::new(stream.as_slice()).decode_value().unwrap()
+    );
+
+    let mut body = vec![0x01; MAX_NESTING_DEPTH - 1];
+    body.push(0x00);
+    let stream = message(&body);
+    assert!(Decoder::new(stream.as_slice()).decode_value().is_ok());
+    body.insert(0, 0x01);
+    let stream = message(&body);
+    let got = Decoder::new(stream.as_slice()).decode_value();
+    assert!(matches!(got, Err(Error::Corrupt(_))), "{:?}", got);
+
+    // Every 0x01 starts a slice holding a single slice, which used to overflow the stack.
+    let stream = message(&[0x01; 200 * 1024]);
+    let got = Decoder::new(stream.as_slice()).decode_value();
+    assert!(matches!(got, Err(Error::Corrupt(_))), "{:?}", got);
+}
+
+#[test]
+fn decode_mismatch() {
+    let mut out = vec![];
+    Encoder::new(&mut out).encode(&-1i64).unwrap();
+    assert!(matches!(
+        Decoder::new(out.as_slice()).decode::<u64>(),
+        Err(Error::Mismatch(_))
+    ));
+}
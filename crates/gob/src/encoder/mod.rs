@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::types::FIRST_USER_ID;
+use crate::wire::{self, Fields};
+use crate::{Error, GobEncode, Type, Value};
+
+/// `Encoder` writes a gob stream, sending the definition of every type before its first value.
+///
+/// Types are identified by the encoder, so values sent by another encoder must be decoded by
+/// another decoder.
+///
+/// # Example
+/// ```
+/// let mut out = vec![];
+/// let mut enc = gob::Encoder::new(&mut out);
+/// enc.encode(&7i64).unwrap();
+/// enc.encode("hi").unwrap();
+///
+/// assert_eq!(vec![0x03, 0x04, 0x00, 0x0e, 0x05, 0x0c, 0x00, 0x02, b'h', b'i'], out);
+/// ```
+pub struct Encoder<W>
+where
+    W: Write,
+{
+    w: W,
+    ids: HashMap<Type, i64>,
+    next_id: i64,
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    /// Returns a new encoder writing to `w`.
+    pub fn new(w: W) -> Self {
+        Self {
+            w,
+            ids: HashMap::new(),
+            next_id: FIRST_USER_ID,
+        }
+    }
+
+    /// Encodes `v`, preceded by the definitions of the types involved if they haven't been
+    /// sent yet.
+    pub fn encode<T>(&mut self, v: &T) -> Result<(), Error>
+    where
+        T: GobEncode + ?Sized,
+    {
+        self.encode_value(&T::gob_type(), &v.to_value())
+    }
+
+    /// Encodes `v` as a value of type `ty`, failing with [Error::Mismatch] if they don't
+    /// match. Nothing but type definitions is written on failure.
+    pub fn encode_value(&mut self, ty: &Type, v: &Value) -> Result<(), Error> {
+        let mut pending = vec![];
+        let id = self.type_id(ty, &mut pending);
+
+        let mut msg = vec![];
+        wire::encode_int(&mut msg, id);
+        if !matches!(ty, Type::Struct(_)) {
+            // Non-struct values are sent as if they were the only field of a struct.
+            wire::encode_uint(&mut msg, 0);
+        }
+        let encoded = encode_data(&mut msg, ty, v);
+
+        for ty in pending.iter() {
+            self.send_type(ty)?;
+        }
+        encoded?;
+        self.write_message(&msg)
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.w
+    }
+
+    /// Unwraps the encoder, returning the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.w
+    }
+
+    /// Returns the id of `ty`, assigning ids to it and the types it refers to if they are new.
+    /// Types getting an id are appended to `pending` for their definitions to be sent, parents
+    /// before children as Go does.
+    fn type_id(&mut self, ty: &Type, pending: &mut Vec<Type>) -> i64 {
+        if let Some(id) = ty.builtin_id() {
+            return id;
+        }
+        if let Some(&id) = self.ids.get(ty) {
+            return id;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.ids.insert(ty.clone(), id);
+        pending.push(ty.clone());
+
+        match ty {
+            Type::Slice(elem) | Type::Array(elem, _) => {
+                self.type_id(elem, pending);
+            }
+            Type::Map(key, elem) => {
+                self.type_id(key, pending);
+                self.type_id(elem, pending);
+            }
+            Type::Struct(st) => {
+                for f in st.fields.iter() {
+                    self.type_id(&f.ty, pending);
+                }
+            }
+            _ => {}
+        }
+
+        id
+    }
+
+    /// Sends the definition of `ty` as a wireType struct, whose fields are the kinds of
+    /// types with the common part of name and id first.
+    fn send_type(&mut self, ty: &Type) -> Result<(), Error> {
+        let id = self.ids[ty];
+        let name = ty.to_string();
+        let common = |buf: &mut Vec<u8>| {
+            let mut w = Fields::new();
+            w.field(buf, 0);
+            wire::encode_bytes(buf, name.as_bytes());
+            w.field(buf, 1);
+            wire::encode_int(buf, id);
+            w.end(buf);
+        };
+
+        let mut msg = vec![];
+        wire::encode_int(&mut msg, -id);
+
+        let mut wt = Fields::new();
+        let mut w = Fields::new();
+        match ty {
+            Type::Array(elem, len) => {
+                wt.field(&mut msg, 0);
+                w.field(&mut msg, 0);
+                common(&mut msg);
+                w.field(&mut msg, 1);
+                wire::encode_int(&mut msg, self.ids_of(elem));
+                if *len > 0 {
+                    w.field(&mut msg, 2);
+                    wire::encode_int(&mut msg, *len as i64);
+                }
+            }
+            Type::Slice(elem) => {
+                wt.field(&mut msg, 1);
+                w.field(&mut msg, 0);
+                common(&mut msg);
+                w.field(&mut msg, 1);
+                wire::encode_int(&mut msg, self.ids_of(elem));
+            }
+            Type::Struct(st) => {
+                wt.field(&mut msg, 2);
+                w.field(&mut msg, 0);
+                common(&mut msg);
+                if !st.fields.is_empty() {
+                    w.field(&mut msg, 1);
+                    wire::encode_uint(&mut msg, st.fields.len() as u64);
+                    for f in st.fields.iter() {
+                        let mut fw = Fields::new();
+                        if !f.name.is_empty() {
+                            fw.field(&mut msg, 0);
+                            wire::encode_bytes(&mut msg, f.name.as_bytes());
+                        }
+                        fw.field(&mut msg, 1);
+                        wire::encode_int(&mut msg, self.ids_of(&f.ty));
+                        fw.end(&mut msg);
+                    }
+                }
+            }
+            Type::Map(key, elem) => {
+                wt.field(&mut msg, 3);
+                w.field(&mut msg, 0);
+                common(&mut msg);
+                w.field(&mut msg, 1);
+                wire::encode_int(&mut msg, self.ids_of(key));
+                w.field(&mut msg, 2);
+                wire::encode_int(&mut msg, self.ids_of(elem));
+            }
+            _ => unreachable!("predefined types are never sent"),
+        }
+        w.end(&mut msg);
+        wt.end(&mut msg);
+
+        self.write_message(&msg)
+    }
+
+    /// Returns the id of a type already assigned one.
+    fn ids_of(&self, ty: &Type) -> i64 {
+        ty.builtin_id().unwrap_or_else(|| self.ids[ty])
+    }
+
+    fn write_message(&mut self, msg: &[u8]) -> Result<(), Error> {
+        let mut buf = Vec::with_capacity(msg.len() + 9);
+        wire::encode_uint(&mut buf, msg.len() as u64);
+        buf.extend_from_slice(msg);
+        self.w.write_all(&buf).map_err(Error::Io)
+    }
+}
+
+fn encode_data(buf: &mut Vec<u8>, ty: &Type, v: &Value) -> Result<(), Error> {
+    match (ty, v) {
+        (Type::Bool, Value::Bool(b)) => wire::encode_uint(buf, *b as u64),
+        (Type::Int, Value::Int(i)) => wire::encode_int(buf, *i),
+        (Type::Uint, Value::Uint(x)) => wire::encode_uint(buf, *x),
+        (Type::Byte, Value::Uint(x)) if *x <= u8::MAX as u64 => wire::encode_uint(buf, *x),
+        (Type::Float, Value::Float(f)) => wire::encode_float(buf, *f),
+        (Type::Complex, Value::Complex(re, im)) => {
+            wire::encode_float(buf, *re);
+            wire::encode_float(buf, *im);
+        }
+        (Type::String, Value::String(s)) => wire::encode_bytes(buf, s.as_bytes()),
+        (Type::Slice(elem), Value::Bytes(b)) if **elem == Type::Byte => wire::encode_bytes(buf, b),
+        (Type::Slice(elem), Value::Slice(elems)) if **elem == Type::Byte => {
+            let b = elems
+                .iter()
+                .map(|v| match v {
+                    Value::Uint(x) if *x <= u8::MAX as u64 => Ok(*x as u8),
+                    v => Err(mismatch(v, &Type::Byte)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            wire::encode_bytes(buf, &b);
+        }
+        (Type::Slice(elem), Value::Slice(elems)) => encode_elems(buf, elem, elems)?,
+        (Type::Array(elem, len), Value::Array(elems)) if elems.len() == *len => {
+            encode_elems(buf, elem, elems)?
+        }
+        (Type::Map(key, elem), Value::Map(entries)) => {
+            wire::encode_uint(buf, entries.len() as u64);
+            for (k, v) in entries.iter() {
+                encode_data(buf, key, k)?;
+                encode_data(buf, elem, v)?;
+            }
+        }
+        (Type::Struct(st), Value::Struct(fields)) => {
+            let mut w = Fields::new();
+            for (i, f) in st.fields.iter().enumerate() {
+                let v = match fields.iter().find(|(name, _)| *name == f.name) {
+                    Some((_, v)) if !is_zero(&f.ty, v) => v,
+                    _ => continue,
+                };
+                w.field(buf, i);
+                encode_data(buf, &f.ty, v)?;
+            }
+            w.end(buf);
+        }
+        (ty, v) => return Err(mismatch(v, ty)),
+    }
+
+    Ok(())
+}
+
+fn encode_elems(buf: &mut Vec<u8>, elem: &Type, elems: &[Value]) -> Result<(), Error> {
+    wire::encode_uint(buf, elems.len() as u64);
+    for v in elems.iter() {
+        encode_data(buf, elem, v)?;
+    }
+    Ok(())
+}
+
+/// Tells whether a struct field can be left out, which is what Go does for zero values except
+/// arrays, maps and structs.
+fn is_zero(ty: &Type, v: &Value) -> bool {
+    match v {
+        Value::Bool(b) => !b,
+        Value::Int(i) => *i == 0,
+        Value::Uint(x) => *x == 0,
+        Value::Float(f) => *f == 0.0,
+        Value::Complex(re, im) => *re == 0.0 && *im == 0.0,
+        Value::Bytes(b) => b.is_empty(),
+        Value::String(s) => s.is_empty(),
+        Value::Slice(v) => v.is_empty() && matches!(ty, Type::Slice(_)),
+        _ => false,
+    }
+}
+
+fn mismatch(v: &Value, ty: &Type) -> Error {
+    Error::Mismatch(format!("cannot encode {} as {}", v.kind(), ty))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use super::*;
+
+fn encode<T>(v: &T) -> Vec<u8>
+where
+    T: GobEncode + ?Sized,
+{
+    let mut out = vec![];
+    Encoder::new(&mut out).encode(v).unwrap();
+    out
+}
+
+#[test]
+fn singletons() {
+    let tests: Vec<(Vec<u8>, &[u8])> = vec![
+        (encode(&true), &[0x03, 0x02, 0x00, 0x01]),
+        (encode(&-1i8), &[0x03, 0x04, 0x00, 0x01]),
+        (encode(&300u16), &[0x05, 0x06, 0x00, 0xfe, 0x01, 0x2c]),
+        (encode(&17.0f32), &[0x05, 0x08, 0x00, 0xfe, 0x31, 0x40]),
+        (
+            encode(&b"ab".to_vec()),
+            &[0x05, 0x0a, 0x00, 0x02, b'a', b'b'],
+        ),
+        (encode(""), &[0x03, 0x0c, 0x00, 0x00]),
+    ];
+
+    for (i, (got, expect)) in tests.iter().enumerate() {
+        assert_eq!(expect, got, "#{}", i);
+    }
+}
+
+#[test]
+fn slice_type_sent_once() {
+    let mut out = vec![];
+    let mut enc = Encoder::new(&mut out);
+    enc.encode(&vec![1i64, 2]).unwrap();
+    enc.encode(&vec![3i32]).unwrap();
+
+    #[rustfmt::skip]
+    let expect = [
+        // Definition of []int as id 65.
+        0x13, 0xff, 0x81, 0x02, 0x01, 0x01, 0x05, b'[', b']', b'i', b'n', b't', 0x01, 0xff, 0x82,
+        0x00, 0x01, 0x04, 0x00, 0x00,
+        // Values.
+        0x06, 0xff, 0x82, 0x00, 0x02, 0x02, 0x04,
+        0x05, 0xff, 0x82, 0x00, 0x01, 0x06,
+    ];
+    assert_eq!(&expect[..], out.as_slice());
+}
+
+#[test]
+fn zero_fields_omitted() {
+    let ty = Type::structure(
+        "T",
+        [
+            ("A", Type::Int),
+            ("B", Type::String),
+            ("C", Type::Slice(Box::new(Type::Int))),
+            ("D", Type::Array(Box::new(Type::Int), 0)),
+            ("E", Type::Int),
+        ],
+    );
+    let v = Value::Struct(vec![
+        ("A".to_string(), Value::Int(0)),
+        ("B".to_string(), Value::String(String::new())),
+        ("C".to_string(), Value::Slice(vec![])),
+        ("D".to_string(), Value::Array(vec![])),
+        ("E".to_string(), Value::Int(1)),
+    ]);
+
+    let mut out = vec![];
+    Encoder::new(&mut out).encode_value(&ty, &v).unwrap();
+
+    // The array D is sent even if empty, then E is 1 field later.
+    assert!(out.ends_with(&[0x07, 0xff, 0x82, 0x04, 0x00, 0x01, 0x02, 0x00]));
+}
+
+#[test]
+fn map_entries() {
+    let m: BTreeMap<String, u8> = [("a".to_string(), 1), ("b".to_string(), 2)].into();
+    let out = encode(&m);
+    assert!(out.ends_with(&[0x0a, 0xff, 0x82, 0x00, 0x02, 0x01, b'a', 0x01, 0x01, b'b', 0x02]));
+}
+
+#[test]
+fn mismatch() {
+    let mut out = vec![];
+    let mut enc = Encoder::new(&mut out);
+    let tests = [
+        (Type::Int, Value::Uint(1)),
+        (Type::Byte, Value::Uint(256)),
+        (
+            Type::Array(Box::new(Type::Int), 2),
+            Value::Array(vec![Value::Int(1)]),
+        ),
+        (
+            Type::Slice(Box::new(Type::Byte)),
+            Value::Slice(vec![Value::Int(1)]),
+        ),
+    ];
+
+    for (ty, v) in tests.iter() {
+        match enc.encode_value(ty, v) {
+            Err(Error::Mismatch(_)) => {}
+            v => panic!("{}: unexpected result {:?}", ty, v),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::io;
+
+/// Possible errors during encoding and decoding.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The stream doesn't follow the gob format.
+    #[error("gob: corrupted data: {0}")]
+    Corrupt(&'static str),
+    /// The stream ends cleanly before the next message.
+    #[error("gob: end of stream")]
+    Eof,
+    /// IO error reported by the wrapped reader or writer.
+    #[error("gob: io: {0}")]
+    Io(io::Error),
+    /// A value doesn't match the type it's encoded or decoded as.
+    #[error("gob: type mismatch: {0}")]
+    Mismatch(String),
+    /// A value refers to a type id never defined in the stream.
+    #[error("gob: unknown type id {0}")]
+    UnknownType(i64),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
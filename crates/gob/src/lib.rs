@@ -0,0 +1,35 @@
+//! Implementation of gob streams, the self-describing binary format of Go's `encoding/gob`,
+//! interoperable with Go.
+//!
+//! A stream is a sequence of messages, each prefixed by its length. A message either defines
+//! a type by assigning it an id, or holds a value preceded by the id of its type. An
+//! [Encoder] sends the definition of a type before its first value, and a [Decoder] records
+//! definitions as they come to make sense of the values that follow.
+//!
+//! Rust types map to gob types through the [GobEncode] and [GobDecode] traits, which convert
+//! them to and from dynamically typed [Value]s. They are implemented for booleans, numbers,
+//! strings, vectors, arrays and maps; structs implement them by hand, naming fields as the Go
+//! struct on the other side does. Values of unknown types can be decoded as [Value]s.
+//!
+//! Go interface values can be decoded but not encoded, and the data of Go types implementing
+//! `GobEncoder`, `BinaryMarshaler` or `TextMarshaler` decodes as raw bytes.
+//!
+//! # Example
+//! ```
+#![doc = include_str!("../examples/point.rs")]
+//! ```
+
+#![allow(clippy::needless_doctest_main)]
+
+mod decoder;
+mod encoder;
+mod errors;
+mod types;
+mod value;
+mod wire;
+
+pub use decoder::*;
+pub use encoder::*;
+pub use errors::*;
+pub use types::{Field, StructType, Type};
+pub use value::*;
@@ -0,0 +1,138 @@
+use std::fmt;
+
+/// Ids of the types predefined by gob, which are never sent on the wire.
+pub(crate) const BOOL: i64 = 1;
+pub(crate) const INT: i64 = 2;
+pub(crate) const UINT: i64 = 3;
+pub(crate) const FLOAT: i64 = 4;
+pub(crate) const BYTES: i64 = 5;
+pub(crate) const STRING: i64 = 6;
+pub(crate) const COMPLEX: i64 = 7;
+pub(crate) const INTERFACE: i64 = 8;
+
+/// Lowest id an encoder assigns to the types it defines, matching Go.
+pub(crate) const FIRST_USER_ID: i64 = 65;
+
+/// `Type` describes the gob type a Rust type is sent as.
+///
+/// Integers of all sizes share [Type::Int] or [Type::Uint], and floats share [Type::Float],
+/// as gob only distinguishes them by sign. [Type::Byte] is sent as [Type::Uint] except that a
+/// slice of bytes is sent as gob's predefined byte string type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    Bool,
+    Int,
+    Uint,
+    Byte,
+    Float,
+    Complex,
+    String,
+    Slice(Box<Type>),
+    Array(Box<Type>, usize),
+    Map(Box<Type>, Box<Type>),
+    Struct(StructType),
+}
+
+/// `StructType` describes a struct by its name and fields in declaration order.
+///
+/// Go matches fields by name when decoding, so names should be those of the exported fields of
+/// the Go struct on the other side, which start with an upper-case letter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StructType {
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+/// `Field` is a named field of a [StructType].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+}
+
+/// `WireType` is the definition of a non-predefined type as received from the stream, with
+/// referred types identified by their ids.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum WireType {
+    Array {
+        elem: i64,
+        len: u64,
+    },
+    Slice {
+        elem: i64,
+    },
+    Struct {
+        fields: Vec<(String, i64)>,
+    },
+    Map {
+        key: i64,
+        elem: i64,
+    },
+    /// A type implementing GobEncoder, BinaryMarshaler or TextMarshaler in Go, whose values are
+    /// byte strings.
+    Marshaler,
+}
+
+impl Type {
+    /// Returns a struct type named `name` with the given fields.
+    ///
+    /// # Example
+    /// ```
+    /// use gob::{GobEncode, Type};
+    ///
+    /// let ty = Type::structure("Point", [("X", i64::gob_type()), ("Y", i64::gob_type())]);
+    /// assert_eq!("Point", ty.to_string());
+    /// ```
+    pub fn structure<I, S>(name: &str, fields: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Type)>,
+        S: Into<String>,
+    {
+        let fields = fields
+            .into_iter()
+            .map(|(name, ty)| Field {
+                name: name.into(),
+                ty,
+            })
+            .collect();
+
+        Self::Struct(StructType {
+            name: name.to_string(),
+            fields,
+        })
+    }
+
+    /// Returns the id of a predefined type.
+    pub(crate) fn builtin_id(&self) -> Option<i64> {
+        let id = match self {
+            Self::Bool => BOOL,
+            Self::Int => INT,
+            Self::Uint | Self::Byte => UINT,
+            Self::Float => FLOAT,
+            Self::Complex => COMPLEX,
+            Self::String => STRING,
+            Self::Slice(elem) if **elem == Self::Byte => BYTES,
+            _ => return None,
+        };
+        Some(id)
+    }
+}
+
+/// Formats the type with the syntax of Go, which is also the name sent along its definition.
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool => f.write_str("bool"),
+            Self::Int => f.write_str("int"),
+            Self::Uint => f.write_str("uint"),
+            Self::Byte => f.write_str("uint8"),
+            Self::Float => f.write_str("float64"),
+            Self::Complex => f.write_str("complex128"),
+            Self::String => f.write_str("string"),
+            Self::Slice(elem) => write!(f, "[]{}", elem),
+            Self::Array(elem, len) => write!(f, "[{}]{}", len, elem),
+            Self::Map(key, elem) => write!(f, "map[{}]{}", key, elem),
+            Self::Struct(st) => f.write_str(&st.name),
+        }
+    }
+}
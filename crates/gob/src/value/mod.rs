@@ -0,0 +1,412 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use crate::{Error, Type};
+
+/// `Value` is a gob value decoded without knowing the Rust type it maps to, or about to be
+/// encoded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A nil interface.
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+    Complex(f64, f64),
+    /// A byte slice, or the data of a type marshaling itself in Go.
+    Bytes(Vec<u8>),
+    String(String),
+    Slice(Vec<Value>),
+    Array(Vec<Value>),
+    /// Map entries in the order they are sent.
+    Map(Vec<(Value, Value)>),
+    /// Fields by name. Fields holding zero values aren't sent, hence missing when decoded.
+    Struct(Vec<(String, Value)>),
+    /// A non-nil interface holding a value of the type registered under the name in Go.
+    Interface(String, Box<Value>),
+}
+
+/// `Fields` looks up fields of a struct value by name.
+#[derive(Debug)]
+pub struct Fields(Vec<(String, Value)>);
+
+/// A `GobEncode` type knows the gob type it's sent as and how to convert itself into a
+/// [Value] of that type.
+///
+/// # Example
+/// ```
+/// use gob::{Fields, GobDecode, GobEncode, Type, Value};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// impl GobEncode for Point {
+///     fn gob_type() -> Type {
+///         Type::structure("Point", [("X", i64::gob_type()), ("Y", i64::gob_type())])
+///     }
+///
+///     fn to_value(&self) -> Value {
+///         Value::Struct(vec![
+///             ("X".to_string(), self.x.to_value()),
+///             ("Y".to_string(), self.y.to_value()),
+///         ])
+///     }
+/// }
+///
+/// impl GobDecode for Point {
+///     fn from_value(v: Value) -> Result<Self, gob::Error> {
+///         let mut fields = Fields::try_from(v)?;
+///         Ok(Self {
+///             x: fields.take("X")?,
+///             y: fields.take("Y")?,
+///         })
+///     }
+/// }
+///
+/// let p = Point { x: 22, y: 33 };
+/// assert_eq!(p, Point::from_value(p.to_value()).unwrap());
+/// ```
+pub trait GobEncode {
+    /// Returns the type values are sent as.
+    fn gob_type() -> Type;
+
+    /// Converts `self` into a value of type [GobEncode::gob_type].
+    fn to_value(&self) -> Value;
+}
+
+/// A `GobDecode` type knows how to convert itself from a [Value]. See [GobEncode] for an
+/// example.
+pub trait GobDecode: Sized {
+    /// Converts `v` into `Self`, failing with [Error::Mismatch] if it has an unexpected type or
+    /// doesn't fit.
+    fn from_value(v: Value) -> Result<Self, Error>;
+}
+
+impl Value {
+    /// Returns the name of the kind of the value, as used in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Nil => "nil",
+            Self::Bool(_) => "bool",
+            Self::Int(_) => "int",
+            Self::Uint(_) => "uint",
+            Self::Float(_) => "float",
+            Self::Complex(..) => "complex",
+            Self::Bytes(_) => "bytes",
+            Self::String(_) => "string",
+            Self::Slice(_) => "slice",
+            Self::Array(_) => "array",
+            Self::Map(_) => "map",
+            Self::Struct(_) => "struct",
+            Self::Interface(..) => "interface",
+        }
+    }
+
+    /// Returns the elements of a slice or array value. Bytes count as a slice of uints.
+    fn into_elems(self) -> Result<Vec<Value>, Error> {
+        match self {
+            Self::Slice(v) | Self::Array(v) => Ok(v),
+            Self::Bytes(v) => Ok(v.into_iter().map(|b| Self::Uint(b as u64)).collect()),
+            v => Err(mismatch(&v, "a sequence")),
+        }
+    }
+
+    fn into_entries(self) -> Result<Vec<(Value, Value)>, Error> {
+        match self {
+            Self::Map(v) => Ok(v),
+            v => Err(mismatch(&v, "a map")),
+        }
+    }
+}
+
+impl Fields {
+    /// Removes the field named `name` and converts it into `T`, or returns the default value
+    /// of `T` if the field is missing.
+    pub fn take<T>(&mut self, name: &str) -> Result<T, Error>
+    where
+        T: GobDecode + Default,
+    {
+        match self.0.iter().position(|(n, _)| n == name) {
+            Some(i) => T::from_value(self.0.swap_remove(i).1),
+            None => Ok(T::default()),
+        }
+    }
+}
+
+impl TryFrom<Value> for Fields {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Struct(v) => Ok(Self(v)),
+            v => Err(mismatch(&v, "a struct")),
+        }
+    }
+}
+
+macro_rules! impl_gob_int {
+    ($variant:ident, $ty:ident, $($t:ty),+) => {
+        $(
+            impl GobEncode for $t {
+                fn gob_type() -> Type {
+                    Type::$ty
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::$variant(*self as _)
+                }
+            }
+
+            impl GobDecode for $t {
+                fn from_value(v: Value) -> Result<Self, Error> {
+                    match v {
+                        Value::$variant(x) => <$t>::try_from(x).map_err(|_| {
+                            Error::Mismatch(format!("{} overflows {}", x, stringify!($t)))
+                        }),
+                        v => Err(mismatch(&v, stringify!($t))),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_gob_int!(Int, Int, i8, i16, i32, i64, isize);
+impl_gob_int!(Uint, Uint, u16, u32, u64, usize);
+impl_gob_int!(Uint, Byte, u8);
+
+impl GobEncode for bool {
+    fn gob_type() -> Type {
+        Type::Bool
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl GobDecode for bool {
+    fn from_value(v: Value) -> Result<Self, Error> {
+        match v {
+            Value::Bool(b) => Ok(b),
+            v => Err(mismatch(&v, "bool")),
+        }
+    }
+}
+
+impl GobEncode for f32 {
+    fn gob_type() -> Type {
+        Type::Float
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Float(*self as f64)
+    }
+}
+
+impl GobDecode for f32 {
+    fn from_value(v: Value) -> Result<Self, Error> {
+        f64::from_value(v).map(|f| f as f32)
+    }
+}
+
+impl GobEncode for f64 {
+    fn gob_type() -> Type {
+        Type::Float
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Float(*self)
+    }
+}
+
+impl GobDecode for f64 {
+    fn from_value(v: Value) -> Result<Self, Error> {
+        match v {
+            Value::Float(f) => Ok(f),
+            v => Err(mismatch(&v, "float")),
+        }
+    }
+}
+
+impl GobEncode for str {
+    fn gob_type() -> Type {
+        Type::String
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl GobEncode for String {
+    fn gob_type() -> Type {
+        Type::String
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl GobDecode for String {
+    fn from_value(v: Value) -> Result<Self, Error> {
+        match v {
+            Value::String(s) => Ok(s),
+            v => Err(mismatch(&v, "string")),
+        }
+    }
+}
+
+impl<T> GobEncode for [T]
+where
+    T: GobEncode,
+{
+    fn gob_type() -> Type {
+        Type::Slice(Box::new(T::gob_type()))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Slice(self.iter().map(T::to_value).collect())
+    }
+}
+
+impl<T> GobEncode for Vec<T>
+where
+    T: GobEncode,
+{
+    fn gob_type() -> Type {
+        <[T]>::gob_type()
+    }
+
+    fn to_value(&self) -> Value {
+        self.as_slice().to_value()
+    }
+}
+
+impl<T> GobDecode for Vec<T>
+where
+    T: GobDecode,
+{
+    fn from_value(v: Value) -> Result<Self, Error> {
+        v.into_elems()?.into_iter().map(T::from_value).collect()
+    }
+}
+
+impl<T, const N: usize> GobEncode for [T; N]
+where
+    T: GobEncode,
+{
+    fn gob_type() -> Type {
+        Type::Array(Box::new(T::gob_type()), N)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Array(self.iter().map(T::to_value).collect())
+    }
+}
+
+impl<T, const N: usize> GobDecode for [T; N]
+where
+    T: GobDecode,
+{
+    fn from_value(v: Value) -> Result<Self, Error> {
+        let elems = Vec::<T>::from_value(v)?;
+        let n = elems.len();
+        elems
+            .try_into()
+            .map_err(|_| Error::Mismatch(format!("{} elements for an array of {}", n, N)))
+    }
+}
+
+impl<K, V> GobEncode for HashMap<K, V>
+where
+    K: GobEncode,
+    V: GobEncode,
+{
+    fn gob_type() -> Type {
+        Type::Map(Box::new(K::gob_type()), Box::new(V::gob_type()))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Map(
+            self.iter()
+                .map(|(k, v)| (k.to_value(), v.to_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V> GobDecode for HashMap<K, V>
+where
+    K: GobDecode + Eq + Hash,
+    V: GobDecode,
+{
+    fn from_value(v: Value) -> Result<Self, Error> {
+        v.into_entries()?
+            .into_iter()
+            .map(|(k, v)| Ok((K::from_value(k)?, V::from_value(v)?)))
+            .collect()
+    }
+}
+
+impl<K, V> GobEncode for BTreeMap<K, V>
+where
+    K: GobEncode,
+    V: GobEncode,
+{
+    fn gob_type() -> Type {
+        Type::Map(Box::new(K::gob_type()), Box::new(V::gob_type()))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Map(
+            self.iter()
+                .map(|(k, v)| (k.to_value(), v.to_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V> GobDecode for BTreeMap<K, V>
+where
+    K: GobDecode + Ord,
+    V: GobDecode,
+{
+    fn from_value(v: Value) -> Result<Self, Error> {
+        v.into_entries()?
+            .into_iter()
+            .map(|(k, v)| Ok((K::from_value(k)?, V::from_value(v)?)))
+            .collect()
+    }
+}
+
+impl<T> GobEncode for &T
+where
+    T: GobEncode + ?Sized,
+{
+    fn gob_type() -> Type {
+        T::gob_type()
+    }
+
+    fn to_value(&self) -> Value {
+        (**self).to_value()
+    }
+}
+
+impl GobDecode for Value {
+    fn from_value(v: Value) -> Result<Self, Error> {
+        Ok(v)
+    }
+}
+
+fn mismatch(v: &Value, expect: &str) -> Error {
+    Error::Mismatch(format!("cannot decode {} into {}", v.kind(), expect))
+}
+
+#[cfg(test)]
+mod tests;
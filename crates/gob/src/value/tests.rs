@@ -0,0 +1,57 @@
+use super::*;
+
+#[test]
+fn gob_type() {
+    assert_eq!(Type::Int, i32::gob_type());
+    assert_eq!(Type::Uint, usize::gob_type());
+    assert_eq!(Type::Slice(Box::new(Type::Byte)), Vec::<u8>::gob_type());
+    assert_eq!(
+        Type::Array(Box::new(Type::Float), 3),
+        <[f32; 3]>::gob_type()
+    );
+    assert_eq!(
+        "map[string][]int",
+        HashMap::<String, Vec<i64>>::gob_type().to_string()
+    );
+}
+
+#[test]
+fn int_overflow() {
+    assert_eq!(-128, i8::from_value(Value::Int(-128)).unwrap());
+    assert!(matches!(
+        i8::from_value(Value::Int(128)),
+        Err(Error::Mismatch(_))
+    ));
+    assert!(matches!(
+        u32::from_value(Value::Int(1)),
+        Err(Error::Mismatch(_))
+    ));
+}
+
+#[test]
+fn sequences() {
+    let v = vec![1u8, 2, 3];
+    assert_eq!(v, Vec::<u8>::from_value(Value::Bytes(v.clone())).unwrap());
+    assert_eq!(v, Vec::<u8>::from_value(v.to_value()).unwrap());
+    assert_eq!([1u8, 2, 3], <[u8; 3]>::from_value(Value::Bytes(v)).unwrap());
+
+    assert!(matches!(
+        <[i64; 2]>::from_value(Value::Array(vec![Value::Int(1)])),
+        Err(Error::Mismatch(_))
+    ));
+}
+
+#[test]
+fn fields() {
+    let mut fields = Fields::try_from(Value::Struct(vec![
+        ("A".to_string(), Value::Int(1)),
+        ("B".to_string(), Value::String("b".to_string())),
+    ]))
+    .unwrap();
+
+    assert_eq!("b", fields.take::<String>("B").unwrap());
+    assert_eq!(0, fields.take::<i64>("C").unwrap());
+    assert_eq!(1, fields.take::<i64>("A").unwrap());
+
+    assert!(Fields::try_from(Value::Int(1)).is_err());
+}
@@ -0,0 +1,194 @@
+//! Low-level encoding of the numbers, strings and struct framing gob values are made of.
+//!
+//! Gob integers aren't varints in the protobuf sense: an unsigned integer less than 128 is
+//! sent as a single byte, and any other one as the negated byte count followed by its
+//! big-endian bytes with leading zeros dropped.
+
+use std::io::{self, Read};
+
+use binary::{BigEndian, ByteOrder, ByteReader};
+
+use crate::Error;
+
+/// Upper bound of a message length, the same as Go's on 64-bit platforms.
+pub(crate) const MAX_MESSAGE_LEN: u64 = 1 << 30;
+
+/// Appends the gob encoding of an unsigned integer to `buf`.
+pub(crate) fn encode_uint(buf: &mut Vec<u8>, x: u64) {
+    if x < 0x80 {
+        buf.push(x as u8);
+        return;
+    }
+
+    let mut word = [0u8; 8];
+    BigEndian::put_uint64(&mut word, x);
+    let n = 8 - (x.leading_zeros() / 8) as usize;
+    buf.push((n as u8).wrapping_neg());
+    buf.extend_from_slice(&word[8 - n..]);
+}
+
+/// Appends the gob encoding of a signed integer to `buf`. The sign is moved into the lowest
+/// bit, complementing the remaining bits of negative numbers.
+pub(crate) fn encode_int(buf: &mut Vec<u8>, i: i64) {
+    let x = if i < 0 {
+        (!(i as u64) << 1) | 1
+    } else {
+        (i as u64) << 1
+    };
+    encode_uint(buf, x);
+}
+
+/// Appends the gob encoding of a float to `buf`, which is its bits byte-reversed so that
+/// common values with short mantissas encode in few bytes.
+pub(crate) fn encode_float(buf: &mut Vec<u8>, f: f64) {
+    encode_uint(buf, f.to_bits().swap_bytes());
+}
+
+/// Appends a length-prefixed byte string to `buf`.
+pub(crate) fn encode_bytes(buf: &mut Vec<u8>, b: &[u8]) {
+    encode_uint(buf, b.len() as u64);
+    buf.extend_from_slice(b);
+}
+
+/// Reads an unsigned integer from a stream, which is how message lengths are sent.
+///
+/// Returns `None` if the stream ends before the first byte.
+pub(crate) fn read_uint<R>(r: &mut R) -> Result<Option<u64>, Error>
+where
+    R: Read + ?Sized,
+{
+    let mut b = [0u8; 1];
+    match r.read_exact(&mut b) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(Error::Io(err)),
+    }
+
+    if b[0] < 0x80 {
+        return Ok(Some(b[0] as u64));
+    }
+
+    let n = uint_len(b[0])?;
+    let mut word = [0u8; 8];
+    r.read_exact(&mut word[8 - n..])?;
+    Ok(Some(BigEndian::uint64(&word)))
+}
+
+/// `State` decodes values from a message in memory.
+pub(crate) struct State<'a> {
+    r: ByteReader<'a>,
+}
+
+impl<'a> State<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        Self {
+            r: ByteReader::new(buf),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.r.is_empty()
+    }
+
+    pub(crate) fn uint(&mut self) -> Result<u64, Error> {
+        let b = self.r.read_u8().map_err(end_of_message)?;
+        if b < 0x80 {
+            return Ok(b as u64);
+        }
+
+        let n = uint_len(b)?;
+        let mut word = [0u8; 8];
+        word[8 - n..].copy_from_slice(self.r.read_bytes(n).map_err(end_of_message)?);
+        Ok(BigEndian::uint64(&word))
+    }
+
+    pub(crate) fn int(&mut self) -> Result<i64, Error> {
+        let x = self.uint()?;
+        if x & 1 == 1 {
+            Ok(!(x >> 1) as i64)
+        } else {
+            Ok((x >> 1) as i64)
+        }
+    }
+
+    pub(crate) fn float(&mut self) -> Result<f64, Error> {
+        self.uint().map(|x| f64::from_bits(x.swap_bytes()))
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<&'a [u8], Error> {
+        let n = self.len()?;
+        self.r.read_bytes(n).map_err(end_of_message)
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String, Error> {
+        let b = self.bytes()?;
+        String::from_utf8(b.to_vec()).map_err(|_| Error::Corrupt("invalid UTF-8 string"))
+    }
+
+    /// Reads an element count, which can't exceed the bytes left since every element takes
+    /// at least one byte.
+    pub(crate) fn len(&mut self) -> Result<usize, Error> {
+        let n = self.uint()?;
+        if n > self.r.remaining().len() as u64 {
+            return Err(Error::Corrupt("length exceeds message"));
+        }
+        Ok(n as usize)
+    }
+
+    /// Reads the fields of a struct, calling `f` with the index of every field present until
+    /// the terminating zero delta.
+    pub(crate) fn fields<F>(&mut self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&mut Self, usize) -> Result<(), Error>,
+    {
+        let mut field = -1i64;
+        loop {
+            let delta = self.uint()?;
+            if delta == 0 {
+                return Ok(());
+            }
+            field = match i64::try_from(delta).ok().and_then(|d| field.checked_add(d)) {
+                Some(v) => v,
+                None => return Err(Error::Corrupt("field number out of range")),
+            };
+            f(self, field as usize)?;
+        }
+    }
+}
+
+/// `Fields` writes the framing of a struct: the delta before every field sent and the
+/// terminating zero.
+pub(crate) struct Fields {
+    last: usize,
+}
+
+impl Fields {
+    pub(crate) fn new() -> Self {
+        Self { last: 0 }
+    }
+
+    /// Starts the field at `index`, which must be greater than the previous one.
+    pub(crate) fn field(&mut self, buf: &mut Vec<u8>, index: usize) {
+        encode_uint(buf, (index + 1 - self.last) as u64);
+        self.last = index + 1;
+    }
+
+    pub(crate) fn end(self, buf: &mut Vec<u8>) {
+        encode_uint(buf, 0);
+    }
+}
+
+fn end_of_message(_: binary::Error) -> Error {
+    Error::Corrupt("unexpected end of message")
+}
+
+fn uint_len(b: u8) -> Result<usize, Error> {
+    let n = b.wrapping_neg() as usize;
+    if n > 8 {
+        return Err(Error::Corrupt("invalid uint data length"));
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests;
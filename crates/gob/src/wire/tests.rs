@@ -0,0 +1,114 @@
+use super::*;
+
+// Examples from the documentation of Go's encoding/gob.
+const UINTS: &[(u64, &[u8])] = &[
+    (0, &[0x00]),
+    (7, &[0x07]),
+    (0x7f, &[0x7f]),
+    (0x80, &[0xff, 0x80]),
+    (256, &[0xfe, 0x01, 0x00]),
+    (
+        u64::MAX,
+        &[0xf8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    ),
+];
+
+const INTS: &[(i64, &[u8])] = &[
+    (0, &[0x00]),
+    (-1, &[0x01]),
+    (1, &[0x02]),
+    (22, &[0x2c]),
+    (-129, &[0xfe, 0x01, 0x01]),
+    (
+        i64::MIN,
+        &[0xf8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    ),
+];
+
+#[test]
+fn uint() {
+    for &(x, expect) in UINTS {
+        let mut buf = vec![];
+        encode_uint(&mut buf, x);
+        assert_eq!(expect, buf.as_slice(), "encode_uint({})", x);
+
+        let mut s = State::new(&buf);
+        assert_eq!(x, s.uint().unwrap());
+        assert!(s.is_empty());
+
+        assert_eq!(Some(x), read_uint(&mut buf.as_slice()).unwrap());
+    }
+}
+
+#[test]
+fn int() {
+    for &(i, expect) in INTS {
+        let mut buf = vec![];
+        encode_int(&mut buf, i);
+        assert_eq!(expect, buf.as_slice(), "encode_int({})", i);
+        assert_eq!(i, State::new(&buf).int().unwrap());
+    }
+}
+
+#[test]
+fn float() {
+    // 17.0 is sent as 3 bytes: fe 31 40.
+    let mut buf = vec![];
+    encode_float(&mut buf, 17.0);
+    assert_eq!(vec![0xfe, 0x31, 0x40], buf);
+
+    for f in [0.0, -1.5, 1e300, f64::INFINITY] {
+        let mut buf = vec![];
+        encode_float(&mut buf, f);
+        assert_eq!(f, State::new(&buf).float().unwrap());
+    }
+}
+
+#[test]
+fn corrupt() {
+    let tests: &[&[u8]] = &[&[], &[0xf7], &[0xfe, 0x01], &[0x02, b'a']];
+    for (i, &input) in tests.iter().enumerate() {
+        let mut s = State::new(input);
+        let got = if i == 3 {
+            s.bytes().map(|_| 0)
+        } else {
+            s.uint()
+        };
+        assert!(
+            matches!(got, Err(Error::Corrupt(_))),
+            "#{}: unexpected {:?}",
+            i,
+            got
+        );
+    }
+}
+
+#[test]
+fn read_uint_eof() {
+    assert!(read_uint(&mut &[][..]).unwrap().is_none());
+    assert!(matches!(
+        read_uint(&mut &[0xfe, 0x01][..]),
+        Err(Error::Io(err)) if err.kind() == io::ErrorKind::UnexpectedEof
+    ));
+}
+
+#[test]
+fn fields() {
+    let mut buf = vec![];
+    let mut w = Fields::new();
+    w.field(&mut buf, 0);
+    encode_uint(&mut buf, 10);
+    w.field(&mut buf, 3);
+    encode_uint(&mut buf, 13);
+    w.end(&mut buf);
+    assert_eq!(vec![0x01, 0x0a, 0x03, 0x0d, 0x00], buf);
+
+    let mut got = vec![];
+    State::new(&buf)
+        .fields(|s, i| {
+            got.push((i, s.uint()?));
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(vec![(0, 10), (3, 13)], got);
+}
@@ -31,7 +31,7 @@ pub fn decode(dst: &mut [u8], src: &[u8]) -> Result<usize, (Error, usize)> {
 
     if src.len() % 2 == 1 {
         let j = src.len() - 1;
-        from_hex_char(src[j]).map_err(|_| (Error::InvalidByte(src[j]), j))?;
+        from_hex_char(src[j]).map_err(|_| (Error::InvalidByte(src[j]), i))?;
         return Err((Error::Length, i));
     }
 
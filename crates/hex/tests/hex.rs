@@ -14,6 +14,23 @@ fn decode_string() {
     }
 }
 
+#[test]
+fn decode_err() {
+    for tt in ERR_TESTS.iter() {
+        let mut out = vec![0; hex::decoded_len(tt.input.len())];
+        let written = match hex::decode(&mut out, tt.input.as_bytes()) {
+            Ok(n) => n,
+            Err((_, n)) => n,
+        };
+        assert_eq!(
+            tt.output,
+            &out[..written],
+            "input='{}': bad output",
+            tt.input
+        );
+    }
+}
+
 #[test]
 fn decoder_err() {
     for tt in ERR_TESTS.iter() {
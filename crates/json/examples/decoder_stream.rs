@@ -0,0 +1,39 @@
+use json::{Decoder, Token};
+
+fn main() {
+    const JSON_STREAM: &str = r#"
+    [
+        {"Name": "Ed", "Text": "Knock knock."},
+        {"Name": "Sam", "Text": "Who's there?"},
+        {"Name": "Ed", "Text": "Go fmt."},
+        {"Name": "Sam", "Text": "Go fmt who?"},
+        {"Name": "Ed", "Text": "Go fmt yourself!"}
+    ]
+"#;
+
+    let mut dec = Decoder::new(JSON_STREAM.as_bytes());
+
+    // Read the opening bracket.
+    assert_eq!(Token::ArrayStart, dec.token().unwrap());
+
+    // While the array contains values, decode them one at a time.
+    let mut got = vec![];
+    while dec.more() {
+        let m = dec.decode().unwrap();
+        let name = m.get("Name").and_then(|v| v.as_str()).unwrap();
+        let text = m.get("Text").and_then(|v| v.as_str()).unwrap();
+        got.push(format!("{}: {}", name, text));
+    }
+
+    // Read the closing bracket.
+    assert_eq!(Token::ArrayEnd, dec.token().unwrap());
+
+    let expect = vec![
+        "Ed: Knock knock.",
+        "Sam: Who's there?",
+        "Ed: Go fmt.",
+        "Sam: Go fmt who?",
+        "Ed: Go fmt yourself!",
+    ];
+    assert_eq!(expect, got);
+}
@@ -0,0 +1,37 @@
+use json::{Decoder, Error};
+
+fn main() {
+    const JSON_STREAM: &str = r#"
+    {"Message": "Hello", "Array": [1, 2, 3], "Null": null, "Number": 1.234}
+"#;
+
+    let mut dec = Decoder::new(JSON_STREAM.as_bytes());
+    let mut got = vec![];
+    loop {
+        let t = match dec.token() {
+            Ok(v) => v,
+            Err(Error::Eof) => break,
+            Err(err) => panic!("{}", err),
+        };
+        let more = if dec.more() { " (more)" } else { "" };
+        got.push(format!("{:?}{}", t, more));
+    }
+
+    let expect = vec![
+        "ObjectStart (more)",
+        r#"String("Message") (more)"#,
+        r#"String("Hello") (more)"#,
+        r#"String("Array") (more)"#,
+        "ArrayStart (more)",
+        r#"Number(Number("1")) (more)"#,
+        r#"Number(Number("2")) (more)"#,
+        r#"Number(Number("3"))"#,
+        "ArrayEnd (more)",
+        r#"String("Null") (more)"#,
+        "Null (more)",
+        r#"String("Number") (more)"#,
+        r#"Number(Number("1.234"))"#,
+        "ObjectEnd",
+    ];
+    assert_eq!(expect, got);
+}
@@ -0,0 +1,606 @@
+use std::io::{self, Read};
+
+use crate::{Error, Map, Number, Value};
+
+/// Maximum nesting depth of arrays and objects, as for Go's scanner.
+pub(crate) const MAX_NESTING_DEPTH: usize = 10000;
+
+/// `Token` is a piece of a JSON stream as returned by [Decoder::token]: a delimiter of an
+/// array or object, or a literal. Commas and colons are left out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Token {
+    /// `[`
+    ArrayStart,
+    /// `]`
+    ArrayEnd,
+    /// `{`
+    ObjectStart,
+    /// `}`
+    ObjectEnd,
+    Null,
+    Bool(bool),
+    Number(Number),
+    /// A string, either a value or an object key.
+    String(String),
+}
+
+/// The kind of the token just scanned, whose text for literals is kept in `Decoder::scratch`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Kind {
+    ArrayStart,
+    ArrayEnd,
+    ObjectStart,
+    ObjectEnd,
+    Comma,
+    Colon,
+    Null,
+    True,
+    False,
+    Number,
+    String,
+}
+
+/// An array or object being read by `Decoder::read_value`, along with the key of the value
+/// being read for an object.
+enum Partial {
+    Array(Vec<Value>),
+    Object(Map, String),
+}
+
+/// Where the decoder is within the nesting of arrays and objects, which tells what may come
+/// next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    TopValue,
+    ArrayStart,
+    ArrayValue,
+    ArrayComma,
+    ObjectStart,
+    ObjectKey,
+    ObjectColon,
+    ObjectValue,
+    ObjectComma,
+}
+
+/// A Decoder reads and decodes JSON values from an input stream.
+///
+/// The stream may hold any number of values, optionally separated by whitespace. They can be
+/// read whole by [decode][Self::decode], or piece by piece by [token][Self::token], and both
+/// can be mixed to stream the elements of a large array.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/decoder_token.rs")]
+/// ```
+pub struct Decoder<R>
+where
+    R: Read,
+{
+    r: R,
+    /// Data read from `r` but not consumed yet starts at `buf[pos]`.
+    buf: Vec<u8>,
+    pos: usize,
+    /// The input stream byte offset of `buf[0]`.
+    base: usize,
+    eof: bool,
+    /// The text of the last scanned string, number or literal.
+    pub(crate) scratch: Vec<u8>,
+    state: State,
+    /// The states of the enclosing arrays and objects.
+    stack: Vec<State>,
+}
+
+impl<R> Decoder<R>
+where
+    R: Read,
+{
+    /// Returns a new decoder that reads from `r`.
+    pub fn new(r: R) -> Self {
+        Self {
+            r,
+            buf: Vec::new(),
+            pos: 0,
+            base: 0,
+            eof: false,
+            scratch: Vec::new(),
+            state: State::TopValue,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Returns the data remaining in the decoder's buffer, which has been read from the
+    /// underlying reader but not decoded yet.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Reads the next JSON value from the input.
+    ///
+    /// In the middle of an array or object, as left by [token][Self::token], the value read is
+    /// the next element. [Error::Eof] is returned if the input ends before any value.
+    pub fn decode(&mut self) -> Result<Value, Error> {
+        // Consume the separator left in front of the value by token.
+        let expected = match self.state {
+            State::ArrayComma => Some((b',', State::ArrayValue)),
+            State::ObjectColon => Some((b':', State::ObjectValue)),
+            _ => None,
+        };
+        if let Some((sep, next)) = expected {
+            match self.peek_non_space()? {
+                Some(c) if c == sep => {
+                    self.pos += 1;
+                    self.state = next;
+                }
+                Some(c) => return Err(self.token_error(c)),
+                None => return Err(self.unexpected_eof()),
+            }
+        }
+        if !self.value_allowed() {
+            return match self.peek_non_space()? {
+                Some(c) => Err(self.token_error(c)),
+                None => Err(self.unexpected_eof()),
+            };
+        }
+
+        let first = self.token()?;
+        self.read_value(first)
+    }
+
+    /// Returns the input stream byte offset of the current decoder position. The offset
+    /// gives the location of the end of the most recently returned token and the beginning
+    /// of the next token.
+    pub fn input_offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    /// Tells whether there is another element in the current array or object being parsed.
+    pub fn more(&mut self) -> bool {
+        matches!(self.peek_non_space(), Ok(Some(c)) if c != b']' && c != b'}')
+    }
+
+    /// Returns the next JSON token in the input stream, or [Error::Eof] at the end of the
+    /// input.
+    ///
+    /// It makes sure that the delimiters `[ ] { }` are properly nested and matched: if a
+    /// delimiter is unexpected in the input, an [Error::Syntax] is returned. Commas and
+    /// colons are checked and skipped.
+    pub fn token(&mut self) -> Result<Token, Error> {
+        loop {
+            let token = match self.next_kind()? {
+                Kind::Comma | Kind::Colon => continue,
+                Kind::ArrayStart => Token::ArrayStart,
+                Kind::ArrayEnd => Token::ArrayEnd,
+                Kind::ObjectStart => Token::ObjectStart,
+                Kind::ObjectEnd => Token::ObjectEnd,
+                Kind::Null => Token::Null,
+                Kind::True => Token::Bool(true),
+                Kind::False => Token::Bool(false),
+                Kind::Number => Token::Number(Number::new_unchecked(&self.scratch)),
+                Kind::String => Token::String(unquote(&self.scratch)),
+            };
+            return Ok(token);
+        }
+    }
+
+    /// Scans the next token, checking it may come at this point.
+    pub(crate) fn next_kind(&mut self) -> Result<Kind, Error> {
+        let c = match self.peek_non_space()? {
+            Some(c) => c,
+            None if self.state == State::TopValue => return Err(Error::Eof),
+            None => return Err(self.unexpected_eof()),
+        };
+
+        let kind = match c {
+            b'[' | b'{' if self.value_allowed() => {
+                if self.stack.len() >= MAX_NESTING_DEPTH {
+                    return Err(self.invalid(c, "exceeded max depth"));
+                }
+                self.pos += 1;
+                self.stack.push(self.state);
+                if c == b'[' {
+                    self.state = State::ArrayStart;
+                    Kind::ArrayStart
+                } else {
+                    self.state = State::ObjectStart;
+                    Kind::ObjectStart
+                }
+            }
+            b']' if matches!(self.state, State::ArrayStart | State::ArrayComma) => {
+                self.pos += 1;
+                self.state = self.stack.pop().expect("enclosing state");
+                self.value_end();
+                Kind::ArrayEnd
+            }
+            b'}' if matches!(self.state, State::ObjectStart | State::ObjectComma) => {
+                self.pos += 1;
+                self.state = self.stack.pop().expect("enclosing state");
+                self.value_end();
+                Kind::ObjectEnd
+            }
+            b':' if self.state == State::ObjectColon => {
+                self.pos += 1;
+                self.state = State::ObjectValue;
+                Kind::Colon
+            }
+            b',' if self.state == State::ArrayComma => {
+                self.pos += 1;
+                self.state = State::ArrayValue;
+                Kind::Comma
+            }
+            b',' if self.state == State::ObjectComma => {
+                self.pos += 1;
+                self.state = State::ObjectKey;
+                Kind::Comma
+            }
+            b'"' if matches!(self.state, State::ObjectStart | State::ObjectKey) => {
+                self.scan_string()?;
+                self.state = State::ObjectColon;
+                Kind::String
+            }
+            b'[' | b'{' | b']' | b'}' | b':' | b',' => return Err(self.token_error(c)),
+            _ if self.value_allowed() => {
+                let kind = self.scan_literal(c)?;
+                self.value_end();
+                kind
+            }
+            _ => return Err(self.token_error(c)),
+        };
+
+        Ok(kind)
+    }
+
+    /// Tells whether the decoder is back at the top level, between values.
+    pub(crate) fn at_top_level(&self) -> bool {
+        self.state == State::TopValue
+    }
+
+    /// Returns the next byte after whitespace without consuming it, or `None` at the end of
+    /// the input.
+    pub(crate) fn peek_non_space(&mut self) -> Result<Option<u8>, Error> {
+        loop {
+            match self.peek()? {
+                Some(b' ' | b'\t' | b'\r' | b'\n') => self.pos += 1,
+                c => return Ok(c),
+            }
+        }
+    }
+
+    /// Reads the value starting with the token `first`.
+    ///
+    /// Arrays and objects are read in a loop keeping the enclosing ones open, rather than by
+    /// recursion, so that the nesting depth isn't bounded by the size of the call stack.
+    fn read_value(&mut self, first: Token) -> Result<Value, Error> {
+        let mut open: Vec<Partial> = vec![];
+        let mut token = first;
+        loop {
+            let mut v = match token {
+                Token::Null => Some(Value::Null),
+                Token::Bool(v) => Some(Value::Bool(v)),
+                Token::Number(v) => Some(Value::Number(v)),
+                Token::String(v) => Some(Value::String(v)),
+                Token::ArrayStart => {
+                    open.push(Partial::Array(vec![]));
+                    None
+                }
+                Token::ObjectStart => {
+                    open.push(Partial::Object(Map::new(), String::new()));
+                    None
+                }
+                Token::ArrayEnd | Token::ObjectEnd => {
+                    let c = if token == Token::ArrayEnd { b']' } else { b'}' };
+                    return Err(self.syntax_error(
+                        format!(
+                            "invalid character {} looking for beginning of value",
+                            quote(c)
+                        ),
+                        self.input_offset(),
+                    ));
+                }
+            };
+
+            // Adds the value to the innermost open array or object, closing those without
+            // more elements, until the next element starts.
+            token = loop {
+                let partial = match open.last_mut() {
+                    Some(p) => p,
+                    None => return Ok(v.expect("value read")),
+                };
+                match (partial, v.take()) {
+                    (Partial::Array(elems), Some(v)) => elems.push(v),
+                    (Partial::Object(fields, key), Some(v)) => {
+                        fields.insert(std::mem::take(key), v);
+                    }
+                    (_, None) => {}
+                }
+
+                if self.more() {
+                    if let Some(Partial::Object(_, key)) = open.last_mut() {
+                        *key = match self.token()? {
+                            Token::String(v) => v,
+                            _ => unreachable!("object keys are strings"),
+                        };
+                    }
+                    break self.token()?;
+                }
+
+                self.token()?;
+                v = match open.pop() {
+                    Some(Partial::Array(elems)) => Some(Value::Array(elems)),
+                    Some(Partial::Object(fields, _)) => Some(Value::Object(fields)),
+                    None => unreachable!("open array or object"),
+                };
+            };
+        }
+    }
+
+    fn value_allowed(&self) -> bool {
+        matches!(
+            self.state,
+            State::TopValue | State::ArrayStart | State::ArrayValue | State::ObjectValue
+        )
+    }
+
+    fn value_end(&mut self) {
+        match self.state {
+            State::ArrayStart | State::ArrayValue => self.state = State::ArrayComma,
+            State::ObjectValue => self.state = State::ObjectComma,
+            _ => {}
+        }
+    }
+
+    /// Scans a string, number, `true`, `false` or `null` starting with `c` into `scratch`.
+    fn scan_literal(&mut self, c: u8) -> Result<Kind, Error> {
+        self.scratch.clear();
+        match c {
+            b'"' => self.scan_string().map(|_| Kind::String),
+            b'-' | b'0'..=b'9' => self.scan_number().map(|_| Kind::Number),
+            b't' => self.scan_word(b"true").map(|_| Kind::True),
+            b'f' => self.scan_word(b"false").map(|_| Kind::False),
+            b'n' => self.scan_word(b"null").map(|_| Kind::Null),
+            _ => Err(self.invalid(c, "looking for beginning of value")),
+        }
+    }
+
+    fn scan_string(&mut self) -> Result<(), Error> {
+        self.scratch.clear();
+        self.take();
+        loop {
+            match self.must_peek()? {
+                b'"' => {
+                    self.take();
+                    return Ok(());
+                }
+                b'\\' => {
+                    self.take();
+                    match self.must_peek()? {
+                        b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => self.take(),
+                        b'u' => {
+                            self.take();
+                            for _ in 0..4 {
+                                let c = self.must_peek()?;
+                                if !c.is_ascii_hexdigit() {
+                                    return Err(
+                                        self.invalid(c, "in \\u hexadecimal character escape")
+                                    );
+                                }
+                                self.take();
+                            }
+                        }
+                        c => return Err(self.invalid(c, "in string escape code")),
+                    }
+                }
+                c if c < 0x20 => return Err(self.invalid(c, "in string literal")),
+                _ => self.take(),
+            }
+        }
+    }
+
+    fn scan_number(&mut self) -> Result<(), Error> {
+        if self.peek()? == Some(b'-') {
+            self.take();
+        }
+        match self.must_peek()? {
+            b'0' => self.take(),
+            b'1'..=b'9' => self.scan_digits()?,
+            c => return Err(self.invalid(c, "in numeric literal")),
+        }
+
+        if self.peek()? == Some(b'.') {
+            self.take();
+            match self.must_peek()? {
+                b'0'..=b'9' => self.scan_digits()?,
+                c => return Err(self.invalid(c, "after decimal point in numeric literal")),
+            }
+        }
+
+        if let Some(b'e' | b'E') = self.peek()? {
+            self.take();
+            if let b'+' | b'-' = self.must_peek()? {
+                self.take();
+            }
+            match self.must_peek()? {
+                b'0'..=b'9' => self.scan_digits()?,
+                c => return Err(self.invalid(c, "in exponent of numeric literal")),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn scan_digits(&mut self) -> Result<(), Error> {
+        while let Some(b'0'..=b'9') = self.peek()? {
+            self.take();
+        }
+        Ok(())
+    }
+
+    fn scan_word(&mut self, word: &'static [u8]) -> Result<(), Error> {
+        for &expected in word {
+            let c = self.must_peek()?;
+            if c != expected {
+                let word = std::str::from_utf8(word).expect("ASCII");
+                let msg = format!("in literal {} (expecting {})", word, quote(expected));
+                return Err(self.invalid(c, &msg));
+            }
+            self.take();
+        }
+        Ok(())
+    }
+
+    /// Moves the next byte into `scratch`.
+    fn take(&mut self) {
+        self.scratch.push(self.buf[self.pos]);
+        self.pos += 1;
+    }
+
+    fn must_peek(&mut self) -> Result<u8, Error> {
+        match self.peek()? {
+            Some(c) => Ok(c),
+            None => Err(self.unexpected_eof()),
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Error> {
+        if self.pos == self.buf.len() {
+            self.fill()?;
+        }
+        Ok(self.buf.get(self.pos).copied())
+    }
+
+    /// Replaces the consumed buffer with the next chunk of input.
+    fn fill(&mut self) -> Result<(), Error> {
+        if self.eof {
+            return Ok(());
+        }
+
+        self.base += self.pos;
+        self.pos = 0;
+        self.buf.resize(4096, 0);
+        loop {
+            match self.r.read(&mut self.buf) {
+                Ok(n) => {
+                    self.eof = n == 0;
+                    self.buf.truncate(n);
+                    return Ok(());
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.buf.clear();
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    fn token_error(&self, c: u8) -> Error {
+        let context = match self.state {
+            State::TopValue | State::ArrayStart | State::ArrayValue | State::ObjectValue => {
+                "looking for beginning of value"
+            }
+            State::ArrayComma => "after array element",
+            State::ObjectStart | State::ObjectKey => "looking for beginning of object key string",
+            State::ObjectColon => "after object key",
+            State::ObjectComma => "after object key:value pair",
+        };
+        self.invalid(c, context)
+    }
+
+    /// Reports the unexpected byte `c` peeked at the current position.
+    pub(crate) fn invalid(&self, c: u8, context: &str) -> Error {
+        let msg = format!("invalid character {} {}", quote(c), context);
+        self.syntax_error(msg, self.input_offset() + 1)
+    }
+
+    pub(crate) fn unexpected_eof(&self) -> Error {
+        self.syntax_error(
+            "unexpected end of JSON input".to_string(),
+            self.input_offset(),
+        )
+    }
+
+    fn syntax_error(&self, msg: String, offset: usize) -> Error {
+        Error::Syntax { msg, offset }
+    }
+}
+
+/// Parses the JSON encoded `data`, which must be exactly one value surrounded by optional
+/// whitespace.
+pub fn unmarshal(data: &[u8]) -> Result<Value, Error> {
+    let mut d = Decoder::new(data);
+    let v = match d.decode() {
+        Err(Error::Eof) => return Err(d.unexpected_eof()),
+        v => v?,
+    };
+    match d.peek_non_space()? {
+        Some(c) => Err(d.invalid(c, "after top-level value")),
+        None => Ok(v),
+    }
+}
+
+/// Returns the string quoted by `raw`, which has been checked by [Decoder::scan_string].
+/// Invalid UTF-8 and lone surrogates are replaced by U+FFFD.
+fn unquote(raw: &[u8]) -> String {
+    let raw = &raw[1..raw.len() - 1];
+    let mut out = Vec::with_capacity(raw.len());
+
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] != b'\\' {
+            out.push(raw[i]);
+            i += 1;
+            continue;
+        }
+
+        let c = match raw[i + 1] {
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let r = hex4(&raw[i + 2..]);
+                i += 6;
+                let c = match r {
+                    0xd800..=0xdbff if raw[i..].starts_with(b"\\u") => {
+                        let r2 = hex4(&raw[i + 2..]);
+                        if (0xdc00..=0xdfff).contains(&r2) {
+                            i += 6;
+                            char::from_u32(0x10000 + ((r - 0xd800) << 10) + (r2 - 0xdc00))
+                        } else {
+                            None
+                        }
+                    }
+                    r => char::from_u32(r),
+                };
+                out.extend_from_slice(
+                    c.unwrap_or(char::REPLACEMENT_CHARACTER)
+                        .encode_utf8(&mut [0; 4])
+                        .as_bytes(),
+                );
+                continue;
+            }
+            c => c as char,
+        };
+        out.push(c as u8);
+        i += 2;
+    }
+
+    match String::from_utf8(out) {
+        Ok(v) => v,
+        Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+    }
+}
+
+fn hex4(b: &[u8]) -> u32 {
+    b[..4].iter().fold(0, |acc, &c| {
+        (acc << 4) | (c as char).to_digit(16).expect("hex digit")
+    })
+}
+
+/// Quotes `c` for error messages, as `'x'`.
+fn quote(c: u8) -> String {
+    format!("{:?}", c as char)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,331 @@
+use std::io::{self, Read};
+
+use crate::{Decoder, Error, Map, Number, Token, Value};
+
+use super::MAX_NESTING_DEPTH;
+
+fn syntax(msg: &str, offset: usize) -> String {
+    Error::Syntax {
+        msg: msg.to_string(),
+        offset,
+    }
+    .to_string()
+}
+
+fn number(s: &str) -> Value {
+    Value::Number(s.parse().unwrap())
+}
+
+#[test]
+fn unmarshal() {
+    let test_vector: Vec<(&str, Value)> = vec![
+        ("null", Value::Null),
+        (" true ", Value::Bool(true)),
+        ("false", Value::Bool(false)),
+        ("-0", number("-0")),
+        ("12.5e-3", number("12.5e-3")),
+        ("1E+400", number("1E+400")),
+        (
+            r#""a\"b\\c\/\b\f\n\r\t""#,
+            Value::from("a\"b\\c/\u{8}\u{c}\n\r\t"),
+        ),
+        (r#""\u00e9\ud83d\ude00""#, Value::from("\u{e9}\u{1f600}")),
+        (r#""\ud83d\u0041""#, Value::from("\u{fffd}A")),
+        (r#""\ude00""#, Value::from("\u{fffd}")),
+        ("[]", Value::Array(vec![])),
+        (
+            "[1, [true], {}]",
+            Value::from(vec![
+                number("1"),
+                Value::from(vec![Value::Bool(true)]),
+                Value::Object(Map::new()),
+            ]),
+        ),
+        (
+            r#"{"b": null, "a": "x", "b": 2}"#,
+            Value::Object(Map::from([
+                ("a".to_string(), Value::from("x")),
+                ("b".to_string(), number("2")),
+            ])),
+        ),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        let got = crate::unmarshal(data.as_bytes()).unwrap();
+        assert_eq!(expect, got, "#{}", i);
+    }
+}
+
+#[test]
+fn unmarshal_invalid_utf8() {
+    let got = crate::unmarshal(b"\"a\xffb\"").unwrap();
+    assert_eq!(Value::from("a\u{fffd}b"), got);
+}
+
+#[test]
+fn unmarshal_syntax_error() {
+    let test_vector = vec![
+        ("", syntax("unexpected end of JSON input", 0)),
+        ("[1, 2", syntax("unexpected end of JSON input", 5)),
+        (
+            "x",
+            syntax("invalid character 'x' looking for beginning of value", 1),
+        ),
+        (
+            "[1,]",
+            syntax("invalid character ']' looking for beginning of value", 4),
+        ),
+        (
+            "[1 2]",
+            syntax("invalid character '2' after array element", 4),
+        ),
+        (
+            "{1: 2}",
+            syntax(
+                "invalid character '1' looking for beginning of object key string",
+                2,
+            ),
+        ),
+        (
+            r#"{"a" 1}"#,
+            syntax("invalid character '1' after object key", 6),
+        ),
+        (
+            r#"{"a": 1 "b": 2}"#,
+            syntax("invalid character '\"' after object key:value pair", 9),
+        ),
+        (
+            r#"{"a": 1,}"#,
+            syntax(
+                "invalid character '}' looking for beginning of object key string",
+                9,
+            ),
+        ),
+        (
+            "[1}",
+            syntax("invalid character '}' after array element", 3),
+        ),
+        (
+            "1 2",
+            syntax("invalid character '2' after top-level value", 3),
+        ),
+        (
+            "01",
+            syntax("invalid character '1' after top-level value", 2),
+        ),
+        ("-", syntax("unexpected end of JSON input", 1)),
+        ("-a", syntax("invalid character 'a' in numeric literal", 2)),
+        (
+            "1.e2",
+            syntax(
+                "invalid character 'e' after decimal point in numeric literal",
+                3,
+            ),
+        ),
+        ("1e+", syntax("unexpected end of JSON input", 3)),
+        (
+            "1ex",
+            syntax("invalid character 'x' in exponent of numeric literal", 3),
+        ),
+        ("tru", syntax("unexpected end of JSON input", 3)),
+        (
+            "nul!",
+            syntax("invalid character '!' in literal null (expecting 'l')", 4),
+        ),
+        (
+            "\"a\nb\"",
+            syntax("invalid character '\\n' in string literal", 3),
+        ),
+        (
+            r#""\x""#,
+            syntax("invalid character 'x' in string escape code", 3),
+        ),
+        (
+            r#""\u12g4""#,
+            syntax(
+                "invalid character 'g' in \\u hexadecimal character escape",
+                6,
+            ),
+        ),
+        ("\"abc", syntax("unexpected end of JSON input", 4)),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        let err = crate::unmarshal(data.as_bytes()).unwrap_err();
+        assert_eq!(expect, err.to_string(), "#{} {:?}", i, data);
+    }
+}
+
+#[test]
+fn unmarshal_deep_nesting() {
+    let deep = |open: &str, close: &str, n: usize| open.repeat(n) + "1" + &close.repeat(n);
+
+    let test_vector = [
+        deep("[", "]", MAX_NESTING_DEPTH),
+        deep(r#"{"a":"#, "}", MAX_NESTING_DEPTH),
+        deep(r#"[{"a":"#, "}]", MAX_NESTING_DEPTH / 2),
+    ];
+    // Decoding and dropping mustn't overflow the default stack of a spawned thread.
+    std::thread::spawn(move || {
+        for (i, data) in test_vector.iter().enumerate() {
+            assert!(crate::unmarshal(data.as_bytes()).is_ok(), "#{}", i);
+        }
+    })
+    .join()
+    .unwrap();
+
+    let test_vector = [
+        (deep("[", "]", MAX_NESTING_DEPTH + 1), MAX_NESTING_DEPTH + 1),
+        (
+            deep(r#"{"a":"#, "}", MAX_NESTING_DEPTH + 1),
+            5 * MAX_NESTING_DEPTH + 1,
+        ),
+        ("[".repeat(500_000), MAX_NESTING_DEPTH + 1),
+    ];
+    for (i, (data, offset)) in test_vector.iter().enumerate() {
+        let err = crate::unmarshal(data.as_bytes()).unwrap_err();
+        let c = data.as_bytes()[offset - 1] as char;
+        let expect = syntax(
+            &format!("invalid character '{}' exceeded max depth", c),
+            *offset,
+        );
+        assert_eq!(expect, err.to_string(), "#{}", i);
+    }
+}
+
+#[test]
+fn decode_stream() {
+    let mut d = Decoder::new(&b"1 \"a\"[2]{\"b\":3}\nnull"[..]);
+
+    let expect = vec![
+        number("1"),
+        Value::from("a"),
+        Value::from(vec![number("2")]),
+        Value::Object(Map::from([("b".to_string(), number("3"))])),
+        Value::Null,
+    ];
+    for (i, v) in expect.into_iter().enumerate() {
+        assert_eq!(v, d.decode().unwrap(), "#{}", i);
+    }
+    assert!(matches!(d.decode(), Err(Error::Eof)));
+    assert!(matches!(d.token(), Err(Error::Eof)));
+}
+
+#[test]
+fn token() {
+    let mut d = Decoder::new(&br#" {"a": [1, "x", {}], "b": false} "#[..]);
+
+    let expect = vec![
+        (Token::ObjectStart, 2),
+        (Token::String("a".to_string()), 5),
+        (Token::ArrayStart, 8),
+        (Token::Number(Number::from(1)), 9),
+        (Token::String("x".to_string()), 14),
+        (Token::ObjectStart, 17),
+        (Token::ObjectEnd, 18),
+        (Token::ArrayEnd, 19),
+        (Token::String("b".to_string()), 24),
+        (Token::Bool(false), 31),
+        (Token::ObjectEnd, 32),
+    ];
+    for (i, (token, offset)) in expect.into_iter().enumerate() {
+        assert_eq!(token, d.token().unwrap(), "#{}", i);
+        assert_eq!(offset, d.input_offset(), "#{} offset", i);
+    }
+    assert!(!d.more());
+    assert!(matches!(d.token(), Err(Error::Eof)));
+    assert_eq!(33, d.input_offset());
+}
+
+#[test]
+fn token_mismatch() {
+    let mut d = Decoder::new(&b"[1}"[..]);
+    assert_eq!(Token::ArrayStart, d.token().unwrap());
+    assert_eq!(Token::Number(Number::from(1)), d.token().unwrap());
+
+    let err = d.token().unwrap_err();
+    assert_eq!(
+        syntax("invalid character '}' after array element", 3),
+        err.to_string()
+    );
+}
+
+#[test]
+fn decode_after_token() {
+    let mut d = Decoder::new(&br#"{"a": [1, 2], "b": 3}"#[..]);
+
+    assert_eq!(Token::ObjectStart, d.token().unwrap());
+    assert_eq!(Token::String("a".to_string()), d.token().unwrap());
+    assert_eq!(
+        Value::from(vec![number("1"), number("2")]),
+        d.decode().unwrap()
+    );
+    assert!(d.more());
+    assert_eq!(Token::String("b".to_string()), d.token().unwrap());
+    assert_eq!(number("3"), d.decode().unwrap());
+    assert!(!d.more());
+    assert_eq!(Token::ObjectEnd, d.token().unwrap());
+
+    let mut d = Decoder::new(&b"{}"[..]);
+    assert_eq!(Token::ObjectStart, d.token().unwrap());
+    let err = d.decode().unwrap_err();
+    assert_eq!(
+        syntax(
+            "invalid character '}' looking for beginning of object key string",
+            2
+        ),
+        err.to_string()
+    );
+}
+
+#[test]
+fn buffered() {
+    let mut d = Decoder::new(&b"[1] tail"[..]);
+    d.decode().unwrap();
+    assert_eq!(b" tail", d.buffered());
+    assert_eq!(3, d.input_offset());
+}
+
+/// Reads one byte at a time, failing once the data runs out if `err` is set.
+struct SlowReader {
+    data: &'static [u8],
+    err: bool,
+}
+
+impl Read for SlowReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.data.split_first() {
+            Some((&c, rest)) => {
+                buf[0] = c;
+                self.data = rest;
+                Ok(1)
+            }
+            None if self.err => Err(io::Error::new(io::ErrorKind::Other, "boom")),
+            None => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn slow_reader() {
+    let r = SlowReader {
+        data: br#"{"key": "long string value", "n": -12.5e10}"#,
+        err: false,
+    };
+    let v = Decoder::new(r).decode().unwrap();
+    assert_eq!(
+        Some("long string value"),
+        v.get("key").and_then(Value::as_str)
+    );
+    assert_eq!(
+        Some(-12.5e10),
+        v.get("n").and_then(Value::as_number).map(Number::as_f64)
+    );
+
+    let r = SlowReader {
+        data: b"[1, 2",
+        err: true,
+    };
+    let err = Decoder::new(r).decode().unwrap_err();
+    assert!(matches!(err, Error::Io(_)), "{:?}", err);
+}
@@ -0,0 +1,212 @@
+use std::io::Write;
+
+use crate::{Error, Value};
+
+/// An Encoder writes JSON values to an output stream, each followed by a newline.
+///
+/// # Example
+/// ```
+/// use json::{Encoder, Map, Value};
+///
+/// let mut fields = Map::new();
+/// fields.insert("html".to_string(), Value::from("<b>"));
+/// fields.insert("ids".to_string(), Value::from(vec![Value::from(1), Value::from(2)]));
+///
+/// let mut out = vec![];
+/// let mut e = Encoder::new(&mut out);
+/// e.set_indent("", "  ");
+/// e.set_escape_html(false);
+/// e.encode(&Value::Object(fields)).unwrap();
+///
+/// const EXPECT: &str = r#"{
+///   "html": "<b>",
+///   "ids": [
+///     1,
+///     2
+///   ]
+/// }
+/// "#;
+/// assert_eq!(EXPECT, String::from_utf8(out).unwrap());
+/// ```
+pub struct Encoder<W>
+where
+    W: Write,
+{
+    w: W,
+    escape_html: bool,
+    prefix: String,
+    indent: String,
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    /// Returns a new encoder that writes to `w`, compactly and escaping HTML.
+    pub fn new(w: W) -> Self {
+        Self {
+            w,
+            escape_html: true,
+            prefix: String::new(),
+            indent: String::new(),
+        }
+    }
+
+    /// Writes the JSON encoding of `v` to the stream, followed by a newline character.
+    pub fn encode(&mut self, v: &Value) -> Result<(), Error> {
+        let mut out = vec![];
+        let indent = if self.prefix.is_empty() && self.indent.is_empty() {
+            None
+        } else {
+            Some((self.prefix.as_str(), self.indent.as_str()))
+        };
+        write_value(&mut out, v, self.escape_html, indent);
+        out.push(b'\n');
+
+        self.w.write_all(&out)?;
+        Ok(())
+    }
+
+    /// Sets whether the characters `<`, `>` and `&` in strings are escaped as `\u003c`,
+    /// `\u003e` and `\u0026`, so that the JSON can be embedded safely in HTML. It's on by
+    /// default.
+    pub fn set_escape_html(&mut self, on: bool) {
+        self.escape_html = on;
+    }
+
+    /// Makes the encoder format each value as if indented by [indent][crate::indent]:
+    /// elements of arrays and objects go on their own lines beginning with `prefix`, followed
+    /// by one copy of `indent` per nesting level. Both empty means compact output.
+    pub fn set_indent(&mut self, prefix: &str, indent: &str) {
+        self.prefix = prefix.to_string();
+        self.indent = indent.to_string();
+    }
+}
+
+/// Returns the compact JSON encoding of `v`, with HTML characters escaped as
+/// [Encoder::set_escape_html] describes.
+pub fn marshal(v: &Value) -> Vec<u8> {
+    let mut out = vec![];
+    write_value(&mut out, v, true, None);
+    out
+}
+
+/// Like [marshal] but indents the output as [indent][crate::indent] does, however deep `v`.
+pub fn marshal_indent(v: &Value, prefix: &str, indent: &str) -> Vec<u8> {
+    let mut out = vec![];
+    write_value(&mut out, v, true, Some((prefix, indent)));
+    out
+}
+
+/// An array or object whose elements [write_value] is writing.
+enum Open<'a> {
+    Array(std::slice::Iter<'a, Value>),
+    Object(std::collections::btree_map::Iter<'a, String, Value>),
+}
+
+/// Appends the encoding of `v`, indented as [indent][crate::indent] does with the prefix and
+/// indent of `indent` if any.
+fn write_value(out: &mut Vec<u8>, mut v: &Value, escape_html: bool, indent: Option<(&str, &str)>) {
+    let newline = |out: &mut Vec<u8>, depth: usize| {
+        if let Some((prefix, indent)) = indent {
+            out.push(b'\n');
+            out.extend_from_slice(prefix.as_bytes());
+            if !indent.is_empty() {
+                for _ in 0..depth {
+                    out.extend_from_slice(indent.as_bytes());
+                }
+            }
+        }
+    };
+
+    // Nested arrays and objects are kept on a stack rather than recursed into, so that values
+    // as deep as the decoder accepts can't overflow the call stack.
+    let mut open: Vec<(Open, bool)> = vec![];
+    loop {
+        match v {
+            Value::Null => out.extend_from_slice(b"null"),
+            Value::Bool(true) => out.extend_from_slice(b"true"),
+            Value::Bool(false) => out.extend_from_slice(b"false"),
+            Value::Number(v) => out.extend_from_slice(v.as_str().as_bytes()),
+            Value::String(v) => write_string(out, v, escape_html),
+            Value::Array(v) => {
+                out.push(b'[');
+                open.push((Open::Array(v.iter()), false));
+            }
+            Value::Object(v) => {
+                out.push(b'{');
+                open.push((Open::Object(v.iter()), false));
+            }
+        }
+
+        // Starts the next element of the innermost open array or object, closing those without
+        // more elements.
+        v = loop {
+            let depth = open.len();
+            let (innermost, started) = match open.last_mut() {
+                Some(p) => p,
+                None => return,
+            };
+            let next = match innermost {
+                Open::Array(elems) => elems.next().map(|elem| (None, elem)),
+                Open::Object(fields) => fields.next().map(|(key, elem)| (Some(key), elem)),
+            };
+            match next {
+                Some((key, elem)) => {
+                    if *started {
+                        out.push(b',');
+                    }
+                    *started = true;
+                    newline(out, depth);
+                    if let Some(key) = key {
+                        write_string(out, key, escape_html);
+                        out.extend_from_slice(if indent.is_some() { b": " } else { b":" });
+                    }
+                    break elem;
+                }
+                None => {
+                    if *started {
+                        newline(out, depth - 1);
+                    }
+                    out.push(match innermost {
+                        Open::Array(_) => b']',
+                        Open::Object(_) => b'}',
+                    });
+                    open.pop();
+                }
+            }
+        };
+    }
+}
+
+/// Appends `s` quoted. U+2028 and U+2029 are always escaped, as JavaScript doesn't allow them
+/// in string literals.
+fn write_string(out: &mut Vec<u8>, s: &str, escape_html: bool) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    out.push(b'"');
+    for c in s.chars() {
+        match c {
+            '"' => out.extend_from_slice(b"\\\""),
+            '\\' => out.extend_from_slice(b"\\\\"),
+            '\u{8}' => out.extend_from_slice(b"\\b"),
+            '\u{c}' => out.extend_from_slice(b"\\f"),
+            '\n' => out.extend_from_slice(b"\\n"),
+            '\r' => out.extend_from_slice(b"\\r"),
+            '\t' => out.extend_from_slice(b"\\t"),
+            c if c < ' ' || (escape_html && matches!(c, '<' | '>' | '&')) => {
+                let b = c as u8;
+                out.extend_from_slice(b"\\u00");
+                out.push(HEX[(b >> 4) as usize]);
+                out.push(HEX[(b & 0xf) as usize]);
+            }
+            '\u{2028}' => out.extend_from_slice(b"\\u2028"),
+            '\u{2029}' => out.extend_from_slice(b"\\u2029"),
+            c => out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+    out.push(b'"');
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,120 @@
+use crate::{Encoder, Map, Number, Value};
+
+use crate::decoder::MAX_NESTING_DEPTH;
+
+#[test]
+fn marshal() {
+    let v = Value::Object(Map::from([
+        ("z".to_string(), Value::Null),
+        (
+            "a".to_string(),
+            Value::from(vec![Value::from(true), Value::from(-1)]),
+        ),
+        ("m".to_string(), Value::from("x<y>&\"\\\u{1}\u{2028}")),
+    ]));
+
+    let expect = r#"{"a":[true,-1],"m":"x\u003cy\u003e\u0026\"\\\u0001\u2028","z":null}"#;
+    assert_eq!(expect.as_bytes(), crate::marshal(&v).as_slice());
+    assert_eq!(expect, v.to_string());
+}
+
+#[test]
+fn marshal_indent() {
+    let v = Value::Object(Map::from([
+        (
+            "a".to_string(),
+            Value::from(vec![Value::from(1), Value::Array(vec![])]),
+        ),
+        ("b".to_string(), Value::Object(Map::new())),
+    ]));
+
+    let expect = "{\n> \"a\": [\n>  1,\n>  []\n> ],\n> \"b\": {}\n>}";
+    let got = crate::marshal_indent(&v, ">", " ");
+    assert_eq!(expect, String::from_utf8(got).unwrap());
+}
+
+#[test]
+fn encoder() {
+    let mut out = vec![];
+    let mut e = Encoder::new(&mut out);
+    e.encode(&Value::from("<&>")).unwrap();
+    e.set_escape_html(false);
+    e.encode(&Value::from("<&>")).unwrap();
+    e.set_indent("", "\t");
+    e.encode(&Value::from(vec![Value::Null])).unwrap();
+
+    let expect = "\"\\u003c\\u0026\\u003e\"\n\"<&>\"\n[\n\tnull\n]\n";
+    assert_eq!(expect, String::from_utf8(out).unwrap());
+}
+
+#[test]
+fn round_trip() {
+    let data = r#"{"big":123456789012345678901234567890,"e":1E-7,"s":"caf\u00e9 \ud83d\ude00"}"#;
+    let v = crate::unmarshal(data.as_bytes()).unwrap();
+
+    let expect =
+        "{\"big\":123456789012345678901234567890,\"e\":1E-7,\"s\":\"caf\u{e9} \u{1f600}\"}";
+    assert_eq!(expect, v.to_string());
+    assert_eq!(
+        Some("123456789012345678901234567890"),
+        v.get("big").and_then(Value::as_number).map(Number::as_str)
+    );
+}
+
+#[test]
+fn round_trip_deep_nesting() {
+    let deep = |open: &str, close: &str, n: usize| open.repeat(n) + "1" + &close.repeat(n);
+
+    let test_vector = [
+        deep("[", "]", MAX_NESTING_DEPTH),
+        deep(r#"{"a":"#, "}", MAX_NESTING_DEPTH),
+        deep(r#"[{"a":"#, "}]", MAX_NESTING_DEPTH / 2),
+    ];
+    // Encoding and dropping mustn't overflow the default stack of a spawned thread, as the
+    // test harness uses, however deep the value.
+    std::thread::spawn(move || {
+        for (i, data) in test_vector.iter().enumerate() {
+            let v = crate::unmarshal(data.as_bytes()).unwrap();
+            assert_eq!(*data, v.to_string(), "#{}", i);
+        }
+
+        let v = crate::unmarshal(test_vector[0].as_bytes()).unwrap();
+        let indented = crate::marshal_indent(&v, "", "");
+        let v = crate::unmarshal(&indented).unwrap();
+        assert_eq!(test_vector[0], v.to_string());
+    })
+    .join()
+    .unwrap();
+}
+
+#[test]
+fn marshal_indent_beyond_max_depth() {
+    let n = MAX_NESTING_DEPTH + 1;
+    let mut v = Value::from(1);
+    for _ in 0..n {
+        v = Value::Array(vec![v]);
+    }
+
+    // The decoder's depth limit doesn't apply to values built in memory.
+    std::thread::spawn(move || {
+        let expect = "[\n>".repeat(n) + "1" + &"\n>]".repeat(n);
+        assert_eq!(
+            expect.as_bytes(),
+            crate::marshal_indent(&v, ">", "").as_slice()
+        );
+
+        let mut out = vec![];
+        let mut e = Encoder::new(&mut out);
+        e.set_indent(">", "");
+        e.encode(&v).unwrap();
+        assert_eq!(expect + "\n", String::from_utf8(out).unwrap());
+    })
+    .join()
+    .unwrap();
+
+    let data = br#"{"a":[1,{"b":[],"c":{}},[[2]]],"d":"<x>","e":{"f":null}}"#;
+    let v = crate::unmarshal(data).unwrap();
+    let mut expect = vec![];
+    crate::indent(&mut expect, &crate::marshal(&v), "> ", "\t").unwrap();
+    assert_eq!(expect, crate::marshal_indent(&v, "> ", "\t"));
+}
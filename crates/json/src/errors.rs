@@ -0,0 +1,25 @@
+use std::io;
+
+/// Possible errors during encoding and decoding.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The stream ends cleanly before the next value.
+    #[error("json: end of stream")]
+    Eof,
+    /// IO error reported by the wrapped reader or writer.
+    #[error("json: io: {0}")]
+    Io(io::Error),
+    /// The input isn't valid JSON. `offset` is the number of bytes read before the error was
+    /// detected, including the offending byte.
+    #[error("json: {msg} (offset {offset})")]
+    Syntax { msg: String, offset: usize },
+    /// A number can't be represented in JSON, as NaN and infinities.
+    #[error("json: unsupported value: {0}")]
+    UnsupportedValue(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
@@ -0,0 +1,141 @@
+use crate::decoder::Kind;
+use crate::{Decoder, Error};
+
+/// Appends to `dst` the JSON encoded `src` with insignificant whitespace removed.
+///
+/// `src` must be exactly one JSON value surrounded by optional whitespace. `dst` is left
+/// unchanged on error.
+///
+/// # Example
+/// ```
+/// let mut out = vec![];
+/// json::compact(&mut out, b" { \"a\" : [1, 2] } \n").unwrap();
+/// assert_eq!(br#"{"a":[1,2]}"#, out.as_slice());
+/// ```
+pub fn compact(dst: &mut Vec<u8>, src: &[u8]) -> Result<(), Error> {
+    let n = dst.len();
+    let result = scan(src, |kind, raw| match kind {
+        Kind::ArrayStart => dst.push(b'['),
+        Kind::ArrayEnd => dst.push(b']'),
+        Kind::ObjectStart => dst.push(b'{'),
+        Kind::ObjectEnd => dst.push(b'}'),
+        Kind::Comma => dst.push(b','),
+        Kind::Colon => dst.push(b':'),
+        _ => dst.extend_from_slice(raw),
+    });
+    if result.is_err() {
+        dst.truncate(n);
+    }
+    result.map(|_| ())
+}
+
+/// Appends to `dst` an indented form of the JSON encoded `src`.
+///
+/// Each element of an array or object begins on a new line, beginning with `prefix` followed
+/// by one copy of `indent` per nesting level. The first line isn't prefixed, so that the
+/// output can be embedded in other formatted JSON. Empty arrays and objects stay on one line.
+/// Leading whitespace of `src` is dropped while trailing whitespace is kept.
+///
+/// `src` must be exactly one JSON value surrounded by optional whitespace. `dst` is left
+/// unchanged on error.
+///
+/// # Example
+/// ```
+/// let mut out = vec![];
+/// json::indent(&mut out, br#"{"a":[1,2],"b":{}}"#, "", "\t").unwrap();
+/// assert_eq!("{\n\t\"a\": [\n\t\t1,\n\t\t2\n\t],\n\t\"b\": {}\n}", String::from_utf8(out).unwrap());
+/// ```
+pub fn indent(dst: &mut Vec<u8>, src: &[u8], prefix: &str, indent: &str) -> Result<(), Error> {
+    let newline = |dst: &mut Vec<u8>, depth: usize| {
+        dst.push(b'\n');
+        dst.extend_from_slice(prefix.as_bytes());
+        for _ in 0..depth {
+            dst.extend_from_slice(indent.as_bytes());
+        }
+    };
+
+    let n = dst.len();
+    let mut depth = 0;
+    // Set after the start of an array or object, which is empty if it ends right away.
+    let mut need_indent = false;
+    let result = scan(src, |kind, raw| {
+        if need_indent && !matches!(kind, Kind::ArrayEnd | Kind::ObjectEnd) {
+            need_indent = false;
+            depth += 1;
+            newline(dst, depth);
+        }
+
+        match kind {
+            Kind::ArrayStart | Kind::ObjectStart => {
+                dst.push(if kind == Kind::ArrayStart { b'[' } else { b'{' });
+                need_indent = true;
+            }
+            Kind::ArrayEnd | Kind::ObjectEnd => {
+                if need_indent {
+                    need_indent = false;
+                } else {
+                    depth -= 1;
+                    newline(dst, depth);
+                }
+                dst.push(if kind == Kind::ArrayEnd { b']' } else { b'}' });
+            }
+            Kind::Comma => {
+                dst.push(b',');
+                newline(dst, depth);
+            }
+            Kind::Colon => dst.extend_from_slice(b": "),
+            _ => dst.extend_from_slice(raw),
+        }
+    });
+
+    match result {
+        Ok(trailing) => {
+            dst.extend_from_slice(&src[trailing..]);
+            Ok(())
+        }
+        Err(err) => {
+            dst.truncate(n);
+            Err(err)
+        }
+    }
+}
+
+/// Tells whether `data` is exactly one JSON value surrounded by optional whitespace.
+///
+/// # Example
+/// ```
+/// assert!(json::valid(br#"{"a": [1, 2]}"#));
+/// assert!(!json::valid(br#"{"a": [1, 2]"#));
+/// assert!(!json::valid(b"1 2"));
+/// ```
+pub fn valid(data: &[u8]) -> bool {
+    scan(data, |_, _| {}).is_ok()
+}
+
+/// Calls `f` with every token of the single JSON value in `src` along with its text,
+/// returning the offset of the whitespace trailing the value.
+fn scan<F>(src: &[u8], mut f: F) -> Result<usize, Error>
+where
+    F: FnMut(Kind, &[u8]),
+{
+    let mut d = Decoder::new(src);
+    loop {
+        let kind = match d.next_kind() {
+            Err(Error::Eof) => return Err(d.unexpected_eof()),
+            kind => kind?,
+        };
+        f(kind, &d.scratch);
+        if d.at_top_level() {
+            break;
+        }
+    }
+
+    let trailing = d.input_offset();
+    match d.peek_non_space()? {
+        Some(c) => Err(d.invalid(c, "after top-level value")),
+        None => Ok(trailing),
+    }
+}
+
+#[cfg(test)]
+mod tests;
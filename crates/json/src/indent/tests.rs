@@ -0,0 +1,74 @@
+use crate::Error;
+
+#[test]
+fn compact() {
+    let test_vector = vec![
+        ("1", "1"),
+        ("  \"a b\"\n", "\"a b\""),
+        ("{ }", "{}"),
+        ("[ 1 , \"x\" , { \"k\" : [ ] } ]", "[1,\"x\",{\"k\":[]}]"),
+        ("{\"\\u0041\": 1.50e+3}", "{\"\\u0041\":1.50e+3}"),
+    ];
+
+    for (i, (src, expect)) in test_vector.into_iter().enumerate() {
+        let mut dst = b"prefix:".to_vec();
+        crate::compact(&mut dst, src.as_bytes()).unwrap();
+        assert_eq!(
+            format!("prefix:{}", expect),
+            String::from_utf8(dst).unwrap(),
+            "#{}",
+            i
+        );
+    }
+}
+
+#[test]
+fn indent() {
+    let test_vector = vec![
+        ("1", "1"),
+        ("[]", "[]"),
+        (" [1, 2] \n", "[\n>\t1,\n>\t2\n>] \n"),
+        (
+            r#"{"a":{"b":[{}, [true]]}}"#,
+            "{\n>\t\"a\": {\n>\t\t\"b\": [\n>\t\t\t{},\n>\t\t\t[\n>\t\t\t\ttrue\n>\t\t\t]\n>\t\t]\n>\t}\n>}",
+        ),
+    ];
+
+    for (i, (src, expect)) in test_vector.into_iter().enumerate() {
+        let mut dst = vec![];
+        crate::indent(&mut dst, src.as_bytes(), ">", "\t").unwrap();
+        assert_eq!(expect, String::from_utf8(dst).unwrap(), "#{}", i);
+    }
+}
+
+#[test]
+fn invalid_leaves_dst_unchanged() {
+    let mut dst = b"keep".to_vec();
+
+    let err = crate::compact(&mut dst, b"[1, 2").unwrap_err();
+    assert!(matches!(err, Error::Syntax { offset: 5, .. }), "{:?}", err);
+    assert_eq!(b"keep", dst.as_slice());
+
+    let err = crate::indent(&mut dst, b"[1] x", "", " ").unwrap_err();
+    assert!(matches!(err, Error::Syntax { offset: 5, .. }), "{:?}", err);
+    assert_eq!(b"keep", dst.as_slice());
+}
+
+#[test]
+fn valid() {
+    let test_vector = vec![
+        ("null", true),
+        (" {\"a\": [1, -2.5e3, \"x\"]} ", true),
+        ("", false),
+        ("  ", false),
+        ("[1,]", false),
+        ("{\"a\"}", false),
+        ("1 2", false),
+        ("\"\t\"", false),
+        ("[[[]]", false),
+    ];
+
+    for (i, (data, expect)) in test_vector.into_iter().enumerate() {
+        assert_eq!(expect, crate::valid(data.as_bytes()), "#{} {:?}", i, data);
+    }
+}
@@ -0,0 +1,34 @@
+//! Implementation of JSON as defined in [RFC 7159], after Go's `encoding/json`.
+//!
+//! JSON values map to the dynamically typed [Value], which [unmarshal] parses and [marshal]
+//! encodes. Numbers keep their literal text so that they round-trip exactly, and objects
+//! are marshaled with their keys sorted.
+//!
+//! A [Decoder] reads a stream of values from an [io::Read][std::io::Read], either value by
+//! value or token by token, while an [Encoder] writes values to an
+//! [io::Write][std::io::Write]. [compact], [indent] and [valid] work on JSON text directly
+//! without decoding it.
+//!
+//! Syntax errors carry the byte offset in the input at which they were detected.
+//!
+//! # Example
+//! This example uses a Decoder to decode a streaming array of JSON objects.
+//! ```
+#![doc = include_str!("../examples/decoder_stream.rs")]
+//! ```
+//!
+//! [RFC 7159]: https://rfc-editor.org/rfc/rfc7159.html
+
+#![allow(clippy::needless_doctest_main)]
+
+mod decoder;
+mod encoder;
+mod errors;
+mod indent;
+mod value;
+
+pub use decoder::{unmarshal, Decoder, Token};
+pub use encoder::*;
+pub use errors::*;
+pub use indent::*;
+pub use value::*;
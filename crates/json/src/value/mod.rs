@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Decoder, Error, Token};
+
+/// `Map` holds the fields of a JSON object, sorted by key as they are marshaled.
+pub type Map = BTreeMap<String, Value>;
+
+/// `Value` is any JSON value.
+///
+/// Numbers keep their literal text, so that no precision is lost when a value is decoded and
+/// encoded again. Objects are maps: duplicated keys keep their last value and the field order
+/// isn't preserved.
+///
+/// # Example
+/// ```
+/// use json::Value;
+///
+/// let v: Value = r#"{"name": "gopher", "tags": ["go", "rust"]}"#.parse().unwrap();
+/// assert_eq!(Some("gopher"), v.get("name").and_then(Value::as_str));
+/// assert_eq!(r#"{"name":"gopher","tags":["go","rust"]}"#, v.to_string());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Value {
+    #[default]
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<Value>),
+    Object(Map),
+}
+
+/// `Number` is a JSON number, kept as its literal text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Number(String);
+
+impl Value {
+    /// Returns the boolean if the value is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if the value is one.
+    pub fn as_number(&self) -> Option<&Number> {
+        match self {
+            Self::Number(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if the value is an array.
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Self::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the fields if the value is an object.
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Self::Object(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the field `key` if the value is an object having it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object().and_then(|v| v.get(key))
+    }
+
+    /// Tells whether the value is null.
+    pub fn is_null(&self) -> bool {
+        *self == Self::Null
+    }
+}
+
+/// Dropping a value doesn't recurse, so that values as deep as the decoder accepts can't
+/// overflow the call stack.
+impl Drop for Value {
+    fn drop(&mut self) {
+        // The elements of nested arrays and objects are moved onto a stack on the heap, and each
+        // is dropped once emptied.
+        let mut elems = match self {
+            Self::Array(v) => std::mem::take(v),
+            Self::Object(v) => std::mem::take(v).into_values().collect(),
+            _ => return,
+        };
+        while let Some(mut v) = elems.pop() {
+            match &mut v {
+                Self::Array(v) => elems.append(v),
+                Self::Object(v) => elems.extend(std::mem::take(v).into_values()),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Values are displayed as marshaled by [marshal][crate::marshal].
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let out = crate::marshal(self);
+        f.write_str(std::str::from_utf8(&out).expect("UTF-8"))
+    }
+}
+
+impl FromStr for Value {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::unmarshal(s.as_bytes())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Self::Bool(v)
+    }
+}
+
+impl From<Number> for Value {
+    fn from(v: Number) -> Self {
+        Self::Number(v)
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Self::String(v)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Self::String(v.to_string())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(v: Vec<Value>) -> Self {
+        Self::Array(v)
+    }
+}
+
+impl From<Map> for Value {
+    fn from(v: Map) -> Self {
+        Self::Object(v)
+    }
+}
+
+impl Number {
+    /// Returns the number as an `f64`, rounded to the closest one.
+    pub fn as_f64(&self) -> f64 {
+        self.0.parse().expect("valid number")
+    }
+
+    /// Returns the number as an `i64` if it's an integer in range.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.0.parse().ok()
+    }
+
+    /// Returns the number as a `u64` if it's a non-negative integer in range.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.0.parse().ok()
+    }
+
+    /// Returns the literal text of the number.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the number of text `b`, already known to be a valid literal.
+    pub(crate) fn new_unchecked(b: &[u8]) -> Self {
+        Self(String::from_utf8(b.to_vec()).expect("ASCII"))
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Number {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The decoder skips leading whitespace, which isn't part of a number.
+        let mut d = Decoder::new(s.as_bytes());
+        match d.token() {
+            Ok(Token::Number(v))
+                if !s.starts_with(|c: char| c.is_ascii_whitespace())
+                    && d.input_offset() == s.len() =>
+            {
+                Ok(v)
+            }
+            Err(err @ Error::Syntax { .. }) => Err(err),
+            _ => Err(Error::Syntax {
+                msg: format!("invalid number literal {:?}", s),
+                offset: d.input_offset(),
+            }),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for Number {
+                fn from(v: $t) -> Self {
+                    Self(v.to_string())
+                }
+            }
+
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    Self::Number(v.into())
+                }
+            }
+        )+
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Floats are formatted as Go does, in the shortest representation that parses back to the
+/// same value, with an exponent below 1e-6 and from 1e21 on.
+impl TryFrom<f64> for Number {
+    type Error = Error;
+
+    fn try_from(v: f64) -> Result<Self, Self::Error> {
+        if !v.is_finite() {
+            return Err(Error::UnsupportedValue(v.to_string()));
+        }
+
+        let abs = v.abs();
+        if abs != 0.0 && !(1e-6..1e21).contains(&abs) {
+            let s = format!("{:e}", v);
+            let s = match s.split_once('e') {
+                Some((m, e)) if !e.starts_with('-') => format!("{}e+{}", m, e),
+                _ => s,
+            };
+            return Ok(Self(s));
+        }
+        Ok(Self(v.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+use crate::{Error, Map, Number, Value};
+
+#[test]
+fn number_from_f64() {
+    let test_vector = vec![
+        (0.0, "0"),
+        (-0.0, "-0"),
+        (1.0, "1"),
+        (0.1, "0.1"),
+        (-1.5, "-1.5"),
+        (1e20, "100000000000000000000"),
+        (1e21, "1e+21"),
+        (1.5e300, "1.5e+300"),
+        (0.000001, "0.000001"),
+        (1e-7, "1e-7"),
+        (-2.5e-10, "-2.5e-10"),
+        (f64::MAX, "1.7976931348623157e+308"),
+    ];
+
+    for (i, (v, expect)) in test_vector.into_iter().enumerate() {
+        let n = Number::try_from(v).unwrap();
+        assert_eq!(expect, n.as_str(), "#{}", i);
+        assert_eq!(v, n.as_f64(), "#{}", i);
+    }
+
+    for v in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let err = Number::try_from(v).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedValue(_)), "{:?}", err);
+    }
+}
+
+#[test]
+fn number_parse() {
+    let n: Number = "-12".parse().unwrap();
+    assert_eq!(Some(-12), n.as_i64());
+    assert_eq!(None, n.as_u64());
+    assert_eq!(-12.0, n.as_f64());
+
+    let n: Number = "18446744073709551615".parse().unwrap();
+    assert_eq!(None, n.as_i64());
+    assert_eq!(Some(u64::MAX), n.as_u64());
+
+    let n: Number = "1.0".parse().unwrap();
+    assert_eq!(None, n.as_i64());
+
+    for s in [
+        "", " 1", "\n1", "1 ", "01", "+1", "1.", ".5", "0x10", "\"1\"", "NaN",
+    ] {
+        assert!(s.parse::<Number>().is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn accessors() {
+    let v: Value = r#"{"a": [true, null], "n": 3, "s": "x"}"#.parse().unwrap();
+
+    let a = v.get("a").and_then(Value::as_array).unwrap();
+    assert_eq!(Some(true), a[0].as_bool());
+    assert!(a[1].is_null());
+    assert_eq!(
+        Some(3),
+        v.get("n")
+            .and_then(Value::as_number)
+            .and_then(Number::as_i64)
+    );
+    assert_eq!(Some("x"), v.get("s").and_then(Value::as_str));
+    assert_eq!(None, v.get("missing"));
+    assert_eq!(None, a[0].get("a"));
+    assert_eq!(3, v.as_object().map(Map::len).unwrap());
+    assert_eq!(Value::Null, Value::default());
+}
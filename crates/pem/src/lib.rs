@@ -197,7 +197,13 @@ where
         out.write_all(NL)?;
     }
 
-    base64::new_encoder(*base64::STD_ENCODING, line_breaker::new(out)).write_all(&b.bytes)?;
+    {
+        let mut w =
+            base64::new_wrapping_encoder(*base64::STD_ENCODING, base64::LineWrap::PEM, &mut *out);
+        w.write_all(&b.bytes)?;
+        // Flushing ends the last line, and reports any error of writing it, unlike dropping.
+        w.flush()?;
+    }
 
     out.write_all(&PEM_END[1..])?;
     out.write_all((b.type_.clone() + "-----\n").as_bytes())?;
@@ -264,7 +270,6 @@ where
 
 mod bytes;
 mod errors;
-mod line_breaker;
 
 pub use errors::*;
 
@@ -0,0 +1,29 @@
+use xml::{Attr, Encoder, Name, StartElement, Token};
+
+fn main() {
+    let mut out = vec![];
+    let mut enc = Encoder::new(&mut out);
+    enc.indent("", "  ");
+
+    let mut person = StartElement::new(Name::new("", "person"));
+    person.attr.push(Attr::new("id", "13"));
+    let name = StartElement::new(Name::new("", "name"));
+
+    let tokens = [
+        Token::StartElement(person.clone()),
+        Token::StartElement(name.clone()),
+        Token::CharData("John & \"Jo\"".to_string()),
+        Token::EndElement(name.end()),
+        Token::Comment(" Need more details. ".to_string()),
+        Token::EndElement(person.end()),
+    ];
+    for t in tokens.iter() {
+        enc.encode_token(t).unwrap();
+    }
+    enc.close().unwrap();
+
+    const EXPECT: &str = r#"<person id="13">
+  <name>John &amp; &#34;Jo&#34;</name><!-- Need more details. -->
+</person>"#;
+    assert_eq!(EXPECT, String::from_utf8(out).unwrap());
+}
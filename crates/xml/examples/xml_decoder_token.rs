@@ -0,0 +1,51 @@
+use xml::{Decoder, Error, Token};
+
+fn main() {
+    const DOC: &str = r#"<?xml version="1.0"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:g="urn:gopher">
+  <title g:lang="en">Go &amp; Rust</title>
+  <!-- entries follow -->
+</feed>"#;
+
+    let mut dec = Decoder::new(DOC.as_bytes());
+    let mut got = vec![];
+    loop {
+        let t = match dec.token() {
+            Ok(v) => v,
+            Err(Error::Eof) => break,
+            Err(err) => panic!("{}", err),
+        };
+        let (line, col) = dec.input_pos();
+        match t {
+            Token::StartElement(t) => {
+                let attr: Vec<_> = t
+                    .attr
+                    .iter()
+                    .map(|a| format!(" {{{}}}{}={:?}", a.name.space, a.name.local, a.value))
+                    .collect();
+                got.push(format!(
+                    "{}:{} start {{{}}}{}{}",
+                    line,
+                    col,
+                    t.name.space,
+                    t.name.local,
+                    attr.concat()
+                ));
+            }
+            Token::EndElement(t) => got.push(format!("{}:{} end {}", line, col, t.name.local)),
+            Token::CharData(t) if t.trim().is_empty() => {}
+            t => got.push(format!("{}:{} {:?}", line, col, t)),
+        }
+    }
+
+    let expect = vec![
+        r#"1:22 ProcInst(ProcInst { target: "xml", inst: "version=\"1.0\"" })"#,
+        r#"2:64 start {http://www.w3.org/2005/Atom}feed {}xmlns="http://www.w3.org/2005/Atom" {xmlns}g="urn:gopher""#,
+        r#"3:22 start {http://www.w3.org/2005/Atom}title {urn:gopher}lang="en""#,
+        r#"3:35 CharData("Go & Rust")"#,
+        "3:43 end title",
+        r#"4:26 Comment(" entries follow ")"#,
+        "5:8 end feed",
+    ];
+    assert_eq!(expect, got);
+}
@@ -0,0 +1,810 @@
+use std::collections::HashMap;
+use std::io::{self, Read};
+
+use crate::token::{is_in_character_range, is_name, is_name_byte};
+use crate::{Attr, EndElement, Error, Name, ProcInst, StartElement, Token, XML_NAMESPACE};
+
+/// Elements that conventionally lack an end tag in HTML, to be set as [Decoder::auto_close]
+/// when parsing HTML leniently.
+pub const HTML_AUTO_CLOSE: &[&str] = &[
+    "basefont", "br", "area", "link", "img", "param", "hr", "input", "col", "frame", "isindex",
+    "base", "meta",
+];
+
+/// An open element, along with the name space bindings its attributes shadowed.
+struct Frame {
+    /// The name as read, with its prefix.
+    name: Name,
+    saved: Vec<(String, Option<String>)>,
+}
+
+/// A Decoder is a pull parser reading an XML document token by token from an input stream.
+///
+/// In strict mode, which is the default, the decoder requires the input to be well-formed.
+/// Otherwise, it accepts common mistakes of HTML documents:
+/// - unquoted attribute values, and attributes without a value which default to their name;
+/// - unknown or malformed entities, which are kept as is;
+/// - mismatched end tags, which close the elements left open;
+/// - elements listed in [auto_close][Self::auto_close] without an end tag.
+///
+/// The input must be UTF-8 encoded.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/xml_decoder_token.rs")]
+/// ```
+pub struct Decoder<R>
+where
+    R: Read,
+{
+    /// Whether the input must be well-formed. It's set by [Decoder::new].
+    pub strict: bool,
+    /// Names of elements to close right after their start in lenient mode, unless the next
+    /// token is their end. Case is ignored.
+    pub auto_close: Vec<String>,
+    /// Entities known besides the predefined `lt`, `gt`, `amp`, `apos` and `quot`, mapped to
+    /// their replacement text.
+    pub entity: HashMap<String, String>,
+    /// The name space of unprefixed element names outside of any `xmlns` declaration.
+    pub default_space: String,
+
+    r: R,
+    /// Data read from `r` starts at `buf[0]`, and isn't consumed yet from `buf[pos]`. The
+    /// byte before `pos` is kept across reads so that it can be pushed back.
+    buf: Vec<u8>,
+    pos: usize,
+    /// The input stream byte offset of `buf[0]`.
+    base: usize,
+    eof: bool,
+    line: usize,
+    /// The input stream byte offset of the start of the current line.
+    line_start: usize,
+    /// The start of the line before, restored if the newline ending it is pushed back.
+    prev_line_start: usize,
+
+    /// Open elements, innermost last.
+    stack: Vec<Frame>,
+    /// Name space URLs by prefix, with the empty prefix for the default name space.
+    ns: HashMap<String, String>,
+    /// An end element to return from the next call to `raw_token`.
+    to_close: Option<Name>,
+    /// A token to return from the next call to `token`, delayed by an auto-closed element.
+    next_token: Option<Token>,
+}
+
+impl<R> Decoder<R>
+where
+    R: Read,
+{
+    /// Returns a new strict decoder that reads from `r`.
+    pub fn new(r: R) -> Self {
+        Self {
+            strict: true,
+            auto_close: vec![],
+            entity: HashMap::new(),
+            default_space: String::new(),
+            r,
+            buf: Vec::new(),
+            pos: 0,
+            base: 0,
+            eof: false,
+            line: 1,
+            line_start: 0,
+            prev_line_start: 0,
+            stack: vec![],
+            ns: HashMap::new(),
+            to_close: None,
+            next_token: None,
+        }
+    }
+
+    /// Returns the input stream byte offset of the current decoder position. The offset gives
+    /// the location of the end of the most recently returned token and the beginning of the
+    /// next token.
+    pub fn input_offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    /// Returns the line and column of the current decoder position, both 1-based. The column
+    /// counts bytes.
+    pub fn input_pos(&self) -> (usize, usize) {
+        (self.line, self.input_offset() - self.line_start + 1)
+    }
+
+    /// Returns the next token in the input stream, or [Error::Eof] once all of it has been
+    /// read.
+    ///
+    /// Prefixes of element and attribute names are resolved to the URLs of the name spaces
+    /// declared by `xmlns` attributes. Unknown prefixes are kept as the name space. Start and
+    /// end elements are checked to match, and self-closing elements are reported as a start
+    /// element followed by an end element.
+    pub fn token(&mut self) -> Result<Token, Error> {
+        let t = match self.next_token.take() {
+            Some(t) => t,
+            None => {
+                let t = match self.raw_token() {
+                    Err(Error::Eof) if !self.stack.is_empty() => {
+                        return Err(self.syntax_error("unexpected EOF"))
+                    }
+                    t => t?,
+                };
+                match self.auto_close(&t) {
+                    Some(end) => {
+                        self.next_token = Some(t);
+                        end
+                    }
+                    None => t,
+                }
+            }
+        };
+
+        match t {
+            Token::StartElement(mut t) => {
+                // The bindings declared by the attributes apply to the element and the other
+                // attributes, so they go first.
+                let mut saved = vec![];
+                for a in t.attr.iter() {
+                    let prefix = match (a.name.space.as_str(), a.name.local.as_str()) {
+                        ("xmlns", local) => local,
+                        ("", "xmlns") => "",
+                        _ => continue,
+                    };
+                    let old = self.ns.insert(prefix.to_string(), a.value.clone());
+                    saved.push((prefix.to_string(), old));
+                }
+                self.stack.push(Frame {
+                    name: t.name.clone(),
+                    saved,
+                });
+
+                self.translate(&mut t.name, true);
+                for a in t.attr.iter_mut() {
+                    self.translate(&mut a.name, false);
+                }
+                Ok(Token::StartElement(t))
+            }
+            Token::EndElement(mut t) => {
+                self.pop_element(&mut t)?;
+                Ok(Token::EndElement(t))
+            }
+            t => Ok(t),
+        }
+    }
+
+    /// Like [token][Self::token] but without resolving name spaces nor checking that start
+    /// and end elements match. Self-closing elements are still reported as a start element
+    /// followed by an end element.
+    pub fn raw_token(&mut self) -> Result<Token, Error> {
+        if let Some(name) = self.to_close.take() {
+            return Ok(Token::EndElement(EndElement { name }));
+        }
+
+        let b = match self.getc()? {
+            Some(b) => b,
+            None => return Err(Error::Eof),
+        };
+        if b != b'<' {
+            self.ungetc();
+            let data = self.text(None, false)?;
+            return Ok(Token::CharData(data));
+        }
+
+        match self.mustgetc()? {
+            b'/' => {
+                let name = match self.nsname()? {
+                    Some(v) => v,
+                    None => return Err(self.syntax_error("expected element name after </")),
+                };
+                self.space()?;
+                if self.mustgetc()? != b'>' {
+                    let msg = format!("invalid characters between </{} and >", name.local);
+                    return Err(self.syntax_error(msg));
+                }
+                Ok(Token::EndElement(EndElement { name }))
+            }
+            b'?' => self.proc_inst(),
+            b'!' => self.comment_or_directive(),
+            _ => {
+                self.ungetc();
+                self.start_element()
+            }
+        }
+    }
+
+    /// Reads tokens until it has consumed the end element matching the most recent start
+    /// element already consumed, skipping nested elements.
+    pub fn skip(&mut self) -> Result<(), Error> {
+        let mut depth = 0;
+        loop {
+            match self.token()? {
+                Token::StartElement(_) => depth += 1,
+                Token::EndElement(_) if depth == 0 => return Ok(()),
+                Token::EndElement(_) => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the end element of the innermost open element if `t` implicitly closes it.
+    fn auto_close(&self, t: &Token) -> Option<Token> {
+        if self.strict {
+            return None;
+        }
+        let open = &self.stack.last()?.name;
+        if !self
+            .auto_close
+            .iter()
+            .any(|v| v.eq_ignore_ascii_case(&open.local))
+        {
+            return None;
+        }
+
+        match t {
+            Token::EndElement(t) if t.name.local.eq_ignore_ascii_case(&open.local) => None,
+            _ => Some(Token::EndElement(EndElement { name: open.clone() })),
+        }
+    }
+
+    /// Checks `t` closes the innermost open element, then resolves its name and drops the
+    /// bindings of the element.
+    fn pop_element(&mut self, t: &mut EndElement) -> Result<(), Error> {
+        let frame = match self.stack.pop() {
+            Some(v) => v,
+            None => {
+                let msg = format!("unexpected end element </{}>", t.name.local);
+                return Err(self.syntax_error(msg));
+            }
+        };
+
+        if frame.name.local != t.name.local {
+            if !self.strict {
+                // Close the open element, and try the end element again on the next one.
+                self.to_close = Some(std::mem::replace(&mut t.name, frame.name.clone()));
+            } else {
+                let msg = format!(
+                    "element <{}> closed by </{}>",
+                    frame.name.local, t.name.local
+                );
+                self.stack.push(frame);
+                return Err(self.syntax_error(msg));
+            }
+        } else if frame.name.space != t.name.space {
+            let space = if t.name.space.is_empty() {
+                "\"\""
+            } else {
+                &t.name.space
+            };
+            let msg = format!(
+                "element <{}> in space {} closed by </{}> in space {}",
+                frame.name.local, frame.name.space, t.name.local, space
+            );
+            self.stack.push(frame);
+            return Err(self.syntax_error(msg));
+        }
+
+        self.translate(&mut t.name, true);
+        for (prefix, old) in frame.saved.into_iter().rev() {
+            match old {
+                Some(v) => self.ns.insert(prefix, v),
+                None => self.ns.remove(&prefix),
+            };
+        }
+        Ok(())
+    }
+
+    /// Replaces the prefix of `n` by the URL of its name space. Unprefixed attributes have
+    /// no name space.
+    fn translate(&self, n: &mut Name, is_element_name: bool) {
+        match n.space.as_str() {
+            "xmlns" => return,
+            "" if !is_element_name => return,
+            "xml" => {
+                n.space = XML_NAMESPACE.to_string();
+                return;
+            }
+            "" if n.local == "xmlns" => return,
+            _ => {}
+        }
+
+        if let Some(v) = self.ns.get(&n.space) {
+            n.space = v.clone();
+        } else if n.space.is_empty() {
+            n.space = self.default_space.clone();
+        }
+    }
+
+    /// Reads a processing instruction after `<?`.
+    fn proc_inst(&mut self) -> Result<Token, Error> {
+        let target = match self.name()? {
+            Some(v) => v,
+            None => return Err(self.syntax_error("expected target name after <?")),
+        };
+
+        // The space separating the target from the instruction isn't part of it.
+        self.space()?;
+        let mut data = vec![];
+        let mut b0 = 0;
+        loop {
+            let b = self.mustgetc()?;
+            data.push(b);
+            if b0 == b'?' && b == b'>' {
+                break;
+            }
+            b0 = b;
+        }
+        data.truncate(data.len() - 2);
+        let inst = self.to_string(data)?;
+
+        if target == "xml" {
+            match proc_inst_param("version", &inst) {
+                "" | "1.0" => {}
+                v => {
+                    return Err(Error::Unsupported(format!(
+                        "unsupported version {:?}; only version 1.0 is supported",
+                        v
+                    )))
+                }
+            }
+            match proc_inst_param("encoding", &inst) {
+                "" => {}
+                v if v.eq_ignore_ascii_case("utf-8") => {}
+                v => {
+                    return Err(Error::Unsupported(format!(
+                        "encoding {:?} declared but only UTF-8 is supported",
+                        v
+                    )))
+                }
+            }
+        }
+
+        Ok(Token::ProcInst(ProcInst { target, inst }))
+    }
+
+    /// Reads a comment, CDATA section or directive after `<!`.
+    fn comment_or_directive(&mut self) -> Result<Token, Error> {
+        match self.mustgetc()? {
+            b'-' => {
+                if self.mustgetc()? != b'-' {
+                    return Err(self.syntax_error("invalid sequence <!- not part of <!--"));
+                }
+
+                let mut data = vec![];
+                let (mut b0, mut b1) = (0, 0);
+                loop {
+                    let b = self.mustgetc()?;
+                    data.push(b);
+                    if b0 == b'-' && b1 == b'-' {
+                        if b != b'>' {
+                            return Err(self
+                                .syntax_error("invalid sequence \"--\" not allowed in comments"));
+                        }
+                        break;
+                    }
+                    (b0, b1) = (b1, b);
+                }
+                data.truncate(data.len() - 3);
+                Ok(Token::Comment(self.to_string(data)?))
+            }
+            b'[' => {
+                for &expected in b"CDATA[" {
+                    if self.mustgetc()? != expected {
+                        return Err(self.syntax_error("invalid <![ sequence"));
+                    }
+                }
+                let data = self.text(None, true)?;
+                Ok(Token::CharData(data))
+            }
+            _ => {
+                self.ungetc();
+                self.directive()
+            }
+        }
+    }
+
+    /// Reads a directive, as `<!DOCTYPE ...>`, after `<!`. Angle brackets nest unless
+    /// quoted, and comments are replaced by a space.
+    fn directive(&mut self) -> Result<Token, Error> {
+        let mut data = vec![];
+        let mut in_quote = None;
+        let mut depth = 0;
+        let mut pending = None;
+        loop {
+            let b = match pending.take() {
+                Some(b) => b,
+                None => self.mustgetc()?,
+            };
+            if in_quote.is_none() && b == b'>' && depth == 0 {
+                break;
+            }
+
+            data.push(b);
+            match b {
+                _ if Some(b) == in_quote => in_quote = None,
+                _ if in_quote.is_some() => {}
+                b'\'' | b'"' => in_quote = Some(b),
+                b'>' => depth -= 1,
+                b'<' => {
+                    // Look for <!-- to begin a comment.
+                    let mut matched = 0;
+                    for &expected in b"!--" {
+                        let b = self.mustgetc()?;
+                        if b != expected {
+                            pending = Some(b);
+                            break;
+                        }
+                        matched += 1;
+                    }
+                    if matched < 3 {
+                        data.extend_from_slice(&b"!--"[..matched]);
+                        depth += 1;
+                        continue;
+                    }
+
+                    data.pop();
+                    let (mut b0, mut b1) = (0, 0);
+                    loop {
+                        let b = self.mustgetc()?;
+                        if b0 == b'-' && b1 == b'-' && b == b'>' {
+                            break;
+                        }
+                        (b0, b1) = (b1, b);
+                    }
+                    // Markup separated by the comment mustn't get joined.
+                    data.push(b' ');
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Token::Directive(self.to_string(data)?))
+    }
+
+    /// Reads a start element such as `<a href="x">`, after `<`.
+    fn start_element(&mut self) -> Result<Token, Error> {
+        let name = match self.nsname()? {
+            Some(v) => v,
+            None => return Err(self.syntax_error("expected element name after <")),
+        };
+
+        let mut attr = vec![];
+        loop {
+            self.space()?;
+            match self.mustgetc()? {
+                b'/' => {
+                    if self.mustgetc()? != b'>' {
+                        return Err(self.syntax_error("expected /> in element"));
+                    }
+                    self.to_close = Some(name.clone());
+                    break;
+                }
+                b'>' => break,
+                _ => self.ungetc(),
+            }
+
+            let name = match self.nsname()? {
+                Some(v) => v,
+                None => return Err(self.syntax_error("expected attribute name in element")),
+            };
+            self.space()?;
+            let value = if self.mustgetc()? == b'=' {
+                self.space()?;
+                self.attr_value()?
+            } else if self.strict {
+                return Err(self.syntax_error("attribute name without = in element"));
+            } else {
+                self.ungetc();
+                name.local.clone()
+            };
+            attr.push(Attr { name, value });
+        }
+
+        Ok(Token::StartElement(StartElement { name, attr }))
+    }
+
+    fn attr_value(&mut self) -> Result<String, Error> {
+        let b = self.mustgetc()?;
+        if b == b'"' || b == b'\'' {
+            return self.text(Some(b), false);
+        }
+        if self.strict {
+            return Err(self.syntax_error("unquoted or missing attribute value in element"));
+        }
+
+        self.ungetc();
+        let mut data = vec![];
+        loop {
+            let b = self.mustgetc()?;
+            if !(b.is_ascii_alphanumeric() || matches!(b, b'_' | b':' | b'-')) {
+                self.ungetc();
+                break;
+            }
+            data.push(b);
+        }
+        self.to_string(data)
+    }
+
+    /// Reads text up to `quote`, or up to the next `<` if none, replacing entities and
+    /// normalizing line endings. CDATA sections end at `]]>` and are taken literally.
+    fn text(&mut self, quote: Option<u8>, cdata: bool) -> Result<String, Error> {
+        let mut data = vec![];
+        let (mut b0, mut b1) = (0, 0);
+        loop {
+            let b = match self.getc()? {
+                Some(b) => b,
+                None if cdata => return Err(self.syntax_error("unexpected EOF in CDATA section")),
+                None => break,
+            };
+
+            if b0 == b']' && b1 == b']' && b == b'>' {
+                if cdata {
+                    data.truncate(data.len() - 2);
+                    break;
+                }
+                return Err(self.syntax_error("unescaped ]]> not in CDATA section"));
+            }
+            if b == b'<' && !cdata {
+                if quote.is_some() {
+                    return Err(self.syntax_error("unescaped < inside quoted string"));
+                }
+                self.ungetc();
+                break;
+            }
+            if Some(b) == quote {
+                break;
+            }
+            if b == b'&' && !cdata {
+                self.entity(&mut data)?;
+                (b0, b1) = (0, 0);
+                continue;
+            }
+
+            // Line endings are normalized to \n.
+            if b == b'\r' {
+                data.push(b'\n');
+            } else if !(b1 == b'\r' && b == b'\n') {
+                data.push(b);
+            }
+            (b0, b1) = (b1, b);
+        }
+
+        let s = self.to_string(data)?;
+        if let Some(c) = s.chars().find(|&c| !is_in_character_range(c)) {
+            let msg = format!("illegal character code U+{:04X}", c as u32);
+            return Err(self.syntax_error(msg));
+        }
+        Ok(s)
+    }
+
+    /// Reads a character or entity reference after `&`, appending its replacement text to
+    /// `data`, or the reference itself if unknown in lenient mode.
+    fn entity(&mut self, data: &mut Vec<u8>) -> Result<(), Error> {
+        let before = data.len();
+        data.push(b'&');
+
+        let mut text = None;
+        let mut b = self.mustgetc()?;
+        if b == b'#' {
+            data.push(b);
+            b = self.mustgetc()?;
+            let radix = if b == b'x' {
+                data.push(b);
+                b = self.mustgetc()?;
+                16
+            } else {
+                10
+            };
+
+            let start = data.len();
+            while (b as char).is_digit(radix) {
+                data.push(b);
+                b = self.mustgetc()?;
+            }
+            if b != b';' {
+                self.ungetc();
+            } else {
+                let s = std::str::from_utf8(&data[start..]).expect("digits");
+                text = u32::from_str_radix(s, radix)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(String::from);
+                data.push(b';');
+            }
+        } else {
+            self.ungetc();
+            self.read_name(data)?;
+            if self.mustgetc()? != b';' {
+                self.ungetc();
+            } else {
+                let name = &data[before + 1..];
+                if is_name(name) {
+                    let name = std::str::from_utf8(name).expect("UTF-8");
+                    text = match name {
+                        "lt" => Some("<".to_string()),
+                        "gt" => Some(">".to_string()),
+                        "amp" => Some("&".to_string()),
+                        "apos" => Some("'".to_string()),
+                        "quot" => Some("\"".to_string()),
+                        _ => self.entity.get(name).cloned(),
+                    };
+                }
+                data.push(b';');
+            }
+        }
+
+        if let Some(text) = text {
+            data.truncate(before);
+            data.extend_from_slice(text.as_bytes());
+            return Ok(());
+        }
+        if !self.strict {
+            return Ok(());
+        }
+
+        let mut ent = String::from_utf8_lossy(&data[before..]).into_owned();
+        if !ent.ends_with(';') {
+            ent.push_str(" (no semicolon)");
+        }
+        Err(self.syntax_error(format!("invalid character entity {}", ent)))
+    }
+
+    /// Reads a name, splitting it at its first colon into a prefix and a local part.
+    fn nsname(&mut self) -> Result<Option<Name>, Error> {
+        let s = match self.name()? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let name = match s.split_once(':') {
+            Some((space, local))
+                if !space.is_empty() && !local.is_empty() && !local.contains(':') =>
+            {
+                Name::new(space, local)
+            }
+            _ => Name::new("", &s),
+        };
+        Ok(Some(name))
+    }
+
+    /// Reads a name, returning `None` if the next byte can't start one.
+    fn name(&mut self) -> Result<Option<String>, Error> {
+        let mut data = vec![];
+        if !self.read_name(&mut data)? {
+            return Ok(None);
+        }
+        if !is_name(&data) {
+            let msg = format!("invalid XML name: {}", String::from_utf8_lossy(&data));
+            return Err(self.syntax_error(msg));
+        }
+        self.to_string(data).map(Some)
+    }
+
+    /// Appends the bytes of a name to `data`, returning false if there are none.
+    fn read_name(&mut self, data: &mut Vec<u8>) -> Result<bool, Error> {
+        let start = data.len();
+        loop {
+            let b = self.mustgetc()?;
+            if b < 0x80 && !is_name_byte(b) {
+                self.ungetc();
+                return Ok(data.len() > start);
+            }
+            data.push(b);
+        }
+    }
+
+    /// Skips whitespace.
+    fn space(&mut self) -> Result<(), Error> {
+        loop {
+            match self.getc()? {
+                Some(b' ' | b'\r' | b'\n' | b'\t') => {}
+                Some(_) => {
+                    self.ungetc();
+                    return Ok(());
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+
+    fn mustgetc(&mut self) -> Result<u8, Error> {
+        match self.getc()? {
+            Some(b) => Ok(b),
+            None => Err(self.syntax_error("unexpected EOF")),
+        }
+    }
+
+    fn getc(&mut self) -> Result<Option<u8>, Error> {
+        if self.pos == self.buf.len() {
+            self.fill()?;
+        }
+
+        let b = match self.buf.get(self.pos) {
+            Some(&b) => b,
+            None => return Ok(None),
+        };
+        self.pos += 1;
+        if b == b'\n' {
+            self.line += 1;
+            self.prev_line_start = self.line_start;
+            self.line_start = self.input_offset();
+        }
+        Ok(Some(b))
+    }
+
+    /// Pushes back the byte just read by `getc`.
+    fn ungetc(&mut self) {
+        self.pos -= 1;
+        if self.buf[self.pos] == b'\n' {
+            self.line -= 1;
+            self.line_start = self.prev_line_start;
+        }
+    }
+
+    /// Replaces the consumed buffer with the next chunk of input, but for its last byte.
+    fn fill(&mut self) -> Result<(), Error> {
+        if self.eof {
+            return Ok(());
+        }
+
+        let keep = self.pos.min(1);
+        self.buf.drain(..self.pos - keep);
+        self.base += self.pos - keep;
+        self.pos = keep;
+
+        let n = self.buf.len();
+        self.buf.resize(n + 4096, 0);
+        loop {
+            match self.r.read(&mut self.buf[n..]) {
+                Ok(k) => {
+                    self.eof = k == 0;
+                    self.buf.truncate(n + k);
+                    return Ok(());
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.buf.truncate(n);
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    fn to_string(&self, data: Vec<u8>) -> Result<String, Error> {
+        String::from_utf8(data).map_err(|_| self.syntax_error("invalid UTF-8"))
+    }
+
+    fn syntax_error<S>(&self, msg: S) -> Error
+    where
+        S: Into<String>,
+    {
+        Error::Syntax {
+            msg: msg.into(),
+            line: self.line,
+        }
+    }
+}
+
+/// Returns the value of the parameter `param` of a processing instruction such as
+/// `version="1.0" encoding="UTF-8"`, or an empty string if it's missing.
+fn proc_inst_param<'a>(param: &str, s: &'a str) -> &'a str {
+    let mut s = s;
+    while let Some(i) = s.find(param) {
+        let rest = s[i + param.len()..].trim_start();
+        s = &s[i + param.len()..];
+        let rest = match rest.strip_prefix('=') {
+            Some(v) => v.trim_start(),
+            None => continue,
+        };
+        let quote = match rest.chars().next() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return "",
+        };
+        return match rest[1..].find(quote) {
+            Some(j) => &rest[1..j + 1],
+            None => "",
+        };
+    }
+    ""
+}
+
+#[cfg(test)]
+mod tests;
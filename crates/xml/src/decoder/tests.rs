@@ -0,0 +1,481 @@
+use std::io::{self, Read};
+
+use crate::{
+    Attr, Decoder, EndElement, Error, Name, ProcInst, StartElement, Token, HTML_AUTO_CLOSE,
+    XML_NAMESPACE,
+};
+
+fn syntax(msg: &str, line: usize) -> String {
+    Error::Syntax {
+        msg: msg.to_string(),
+        line,
+    }
+    .to_string()
+}
+
+fn start(space: &str, local: &str, attr: Vec<Attr>) -> Token {
+    Token::StartElement(StartElement {
+        name: Name::new(space, local),
+        attr,
+    })
+}
+
+fn end(space: &str, local: &str) -> Token {
+    Token::EndElement(EndElement {
+        name: Name::new(space, local),
+    })
+}
+
+fn attr(space: &str, local: &str, value: &str) -> Attr {
+    Attr {
+        name: Name::new(space, local),
+        value: value.to_string(),
+    }
+}
+
+fn chardata(s: &str) -> Token {
+    Token::CharData(s.to_string())
+}
+
+fn tokens<R: Read>(d: &mut Decoder<R>, raw: bool) -> Result<Vec<Token>, Error> {
+    let mut out = vec![];
+    loop {
+        let t = if raw { d.raw_token() } else { d.token() };
+        match t {
+            Ok(t) => out.push(t),
+            Err(Error::Eof) => return Ok(out),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Reads one byte at a time, to exercise the refilling of the buffer.
+struct OneByteReader<'a>(&'a [u8]);
+
+impl<'a> Read for OneByteReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+const TEST_INPUT: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \
+\"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+<body xmlns:foo=\"ns1\" xmlns=\"ns2\" xmlns:tag=\"ns3\" \r\n\t  >
+  <hello lang=\"en\">World &lt;&gt;&apos;&quot; &#x767d;&#40300;\u{7fd4}</hello>
+  <query>&\u{4f55}; &is-it;</query>
+  <goodbye />
+  <outer foo:attr=\"value\" xmlns:tag=\"ns4\">
+    <inner/>
+  </outer>
+  <tag:name>
+    <![CDATA[Some text here.]]>
+  </tag:name>
+</body><!-- missing final newline -->";
+
+fn raw_tokens() -> Vec<Token> {
+    vec![
+        chardata("\n"),
+        Token::ProcInst(ProcInst {
+            target: "xml".to_string(),
+            inst: "version=\"1.0\" encoding=\"UTF-8\"".to_string(),
+        }),
+        chardata("\n"),
+        Token::Directive(
+            "DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \
+             \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\""
+                .to_string(),
+        ),
+        chardata("\n"),
+        start(
+            "",
+            "body",
+            vec![
+                attr("xmlns", "foo", "ns1"),
+                attr("", "xmlns", "ns2"),
+                attr("xmlns", "tag", "ns3"),
+            ],
+        ),
+        chardata("\n  "),
+        start("", "hello", vec![attr("", "lang", "en")]),
+        chardata("World <>'\" \u{767d}\u{9d6c}\u{7fd4}"),
+        end("", "hello"),
+        chardata("\n  "),
+        start("", "query", vec![]),
+        chardata("\u{4f55} is-it"),
+        end("", "query"),
+        chardata("\n  "),
+        start("", "goodbye", vec![]),
+        end("", "goodbye"),
+        chardata("\n  "),
+        start(
+            "",
+            "outer",
+            vec![attr("foo", "attr", "value"), attr("xmlns", "tag", "ns4")],
+        ),
+        chardata("\n    "),
+        start("", "inner", vec![]),
+        end("", "inner"),
+        chardata("\n  "),
+        end("", "outer"),
+        chardata("\n  "),
+        start("tag", "name", vec![]),
+        chardata("\n    "),
+        chardata("Some text here."),
+        chardata("\n  "),
+        end("tag", "name"),
+        chardata("\n"),
+        end("", "body"),
+        Token::Comment(" missing final newline ".to_string()),
+    ]
+}
+
+fn new_test_decoder<R: Read>(r: R) -> Decoder<R> {
+    let mut d = Decoder::new(r);
+    d.entity
+        .insert("\u{4f55}".to_string(), "\u{4f55}".to_string());
+    d.entity.insert("is-it".to_string(), "is-it".to_string());
+    d
+}
+
+#[test]
+fn raw_token() {
+    let input = format!("\n{}", TEST_INPUT);
+
+    let mut d = new_test_decoder(input.as_bytes());
+    assert_eq!(raw_tokens(), tokens(&mut d, true).unwrap());
+
+    let mut d = new_test_decoder(OneByteReader(input.as_bytes()));
+    assert_eq!(raw_tokens(), tokens(&mut d, true).unwrap());
+}
+
+#[test]
+fn token() {
+    let input = format!("\n{}", TEST_INPUT);
+    let mut d = new_test_decoder(input.as_bytes());
+    let got = tokens(&mut d, false).unwrap();
+
+    let mut expect = raw_tokens();
+    expect[5] = start(
+        "ns2",
+        "body",
+        vec![
+            attr("xmlns", "foo", "ns1"),
+            attr("", "xmlns", "ns2"),
+            attr("xmlns", "tag", "ns3"),
+        ],
+    );
+    expect[7] = start("ns2", "hello", vec![attr("", "lang", "en")]);
+    expect[9] = end("ns2", "hello");
+    expect[11] = start("ns2", "query", vec![]);
+    expect[13] = end("ns2", "query");
+    expect[15] = start("ns2", "goodbye", vec![]);
+    expect[16] = end("ns2", "goodbye");
+    expect[18] = start(
+        "ns2",
+        "outer",
+        vec![attr("ns1", "attr", "value"), attr("xmlns", "tag", "ns4")],
+    );
+    expect[20] = start("ns2", "inner", vec![]);
+    expect[21] = end("ns2", "inner");
+    expect[23] = end("ns2", "outer");
+    expect[25] = start("ns3", "name", vec![]);
+    expect[29] = end("ns3", "name");
+    expect[31] = end("ns2", "body");
+    assert_eq!(expect, got);
+}
+
+#[test]
+fn token_namespaces() {
+    let test_vector = vec![
+        (
+            r#"<a:b xmlns:a="x" xml:lang="en"/>"#,
+            vec![
+                start(
+                    "x",
+                    "b",
+                    vec![attr("xmlns", "a", "x"), attr(XML_NAMESPACE, "lang", "en")],
+                ),
+                end("x", "b"),
+            ],
+        ),
+        (
+            r#"<a xmlns="x"><b xmlns=""/><c/></a>"#,
+            vec![
+                start("x", "a", vec![attr("", "xmlns", "x")]),
+                start("", "b", vec![attr("", "xmlns", "")]),
+                end("", "b"),
+                start("x", "c", vec![]),
+                end("x", "c"),
+                end("x", "a"),
+            ],
+        ),
+        (
+            "<p:a><p:b/></p:a>",
+            vec![
+                start("p", "a", vec![]),
+                start("p", "b", vec![]),
+                end("p", "b"),
+                end("p", "a"),
+            ],
+        ),
+    ];
+
+    for (i, (input, expect)) in test_vector.into_iter().enumerate() {
+        let mut d = Decoder::new(input.as_bytes());
+        assert_eq!(expect, tokens(&mut d, false).unwrap(), "#{}", i);
+    }
+}
+
+#[test]
+fn default_space() {
+    let mut d = Decoder::new(r#"<a><b xmlns="x"/></a>"#.as_bytes());
+    d.default_space = "def".to_string();
+
+    let expect = vec![
+        start("def", "a", vec![]),
+        start("x", "b", vec![attr("", "xmlns", "x")]),
+        end("x", "b"),
+        end("def", "a"),
+    ];
+    assert_eq!(expect, tokens(&mut d, false).unwrap());
+}
+
+#[test]
+fn line_endings() {
+    let mut d = Decoder::new("<a x=\"1\r\n2\">3\r4\r\n5\n</a>".as_bytes());
+    let expect = vec![
+        start("", "a", vec![attr("", "x", "1\n2")]),
+        chardata("3\n4\n5\n"),
+        end("", "a"),
+    ];
+    assert_eq!(expect, tokens(&mut d, false).unwrap());
+}
+
+#[test]
+fn syntax_error() {
+    let test_vector = vec![
+        ("<a>", syntax("unexpected EOF", 1)),
+        ("<a></b>", syntax("element <a> closed by </b>", 1)),
+        ("\n</a>", syntax("unexpected end element </a>", 2)),
+        ("<a x>", syntax("attribute name without = in element", 1)),
+        (
+            "<a x=1>",
+            syntax("unquoted or missing attribute value in element", 1),
+        ),
+        ("<a x=\"<\">", syntax("unescaped < inside quoted string", 1)),
+        ("<a/ >", syntax("expected /> in element", 1)),
+        ("<>", syntax("expected element name after <", 1)),
+        ("</>", syntax("expected element name after </", 1)),
+        (
+            "<a></a x>",
+            syntax("invalid characters between </a and >", 1),
+        ),
+        ("<1a/>", syntax("invalid XML name: 1a", 1)),
+        ("<a>&foo;</a>", syntax("invalid character entity &foo;", 1)),
+        (
+            "<a>&amp</a>",
+            syntax("invalid character entity &amp (no semicolon)", 1),
+        ),
+        (
+            "<a>&#xzz;</a>",
+            syntax("invalid character entity &#x (no semicolon)", 1),
+        ),
+        (
+            "<a>]]></a>",
+            syntax("unescaped ]]> not in CDATA section", 1),
+        ),
+        (
+            "<!-- a -- b -->",
+            syntax("invalid sequence \"--\" not allowed in comments", 1),
+        ),
+        ("<!-x>", syntax("invalid sequence <!- not part of <!--", 1)),
+        ("<![CDAT[x]]>", syntax("invalid <![ sequence", 1)),
+        (
+            "<a>\n\n<![CDATA[x",
+            syntax("unexpected EOF in CDATA section", 3),
+        ),
+        ("<a>\u{1}</a>", syntax("illegal character code U+0001", 1)),
+        ("<? x?>", syntax("expected target name after <?", 1)),
+    ];
+
+    for (i, (input, expect)) in test_vector.into_iter().enumerate() {
+        let mut d = Decoder::new(input.as_bytes());
+        let got = tokens(&mut d, false).unwrap_err();
+        assert_eq!(expect, got.to_string(), "#{}", i);
+    }
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut d = Decoder::new(&b"<a>\n\xff</a>"[..]);
+    let got = tokens(&mut d, false).unwrap_err();
+    assert_eq!(syntax("invalid UTF-8", 2), got.to_string());
+}
+
+#[test]
+fn unsupported_declaration() {
+    let test_vector = vec![
+        (
+            r#"<?xml version="1.1"?>"#,
+            r#"xml: unsupported version "1.1"; only version 1.0 is supported"#,
+        ),
+        (
+            r#"<?xml version="1.0" encoding="ISO-8859-1"?>"#,
+            r#"xml: encoding "ISO-8859-1" declared but only UTF-8 is supported"#,
+        ),
+    ];
+
+    for (i, (input, expect)) in test_vector.into_iter().enumerate() {
+        let mut d = Decoder::new(input.as_bytes());
+        let got = d.token().unwrap_err();
+        assert!(matches!(got, Error::Unsupported(_)), "#{}", i);
+        assert_eq!(expect, got.to_string(), "#{}", i);
+    }
+
+    let mut d = Decoder::new(r#"<?xml version='1.0' encoding='utf-8'?>"#.as_bytes());
+    assert!(d.token().is_ok());
+}
+
+#[test]
+fn directive() {
+    let test_vector = vec![
+        ("<!DOCTYPE a>", "DOCTYPE a"),
+        (
+            "<!DOCTYPE a [<!ENTITY b \"c>\">]>",
+            "DOCTYPE a [<!ENTITY b \"c>\">]",
+        ),
+        (
+            "<!DOCTYPE a [<!-- x > y --><!ELEMENT b ANY>]>",
+            "DOCTYPE a [ <!ELEMENT b ANY>]",
+        ),
+    ];
+
+    for (i, (input, expect)) in test_vector.into_iter().enumerate() {
+        let mut d = Decoder::new(input.as_bytes());
+        let got = d.raw_token().unwrap();
+        assert_eq!(Token::Directive(expect.to_string()), got, "#{}", i);
+    }
+}
+
+#[test]
+fn lenient() {
+    const HTML: &str = "<HTML><p class=x selected>a &nbsp; b<BR>c<img src='i.png'></p><hr></HTML>";
+
+    let mut d = Decoder::new(HTML.as_bytes());
+    d.strict = false;
+    d.auto_close = HTML_AUTO_CLOSE.iter().map(|v| v.to_string()).collect();
+
+    let expect = vec![
+        start("", "HTML", vec![]),
+        start(
+            "",
+            "p",
+            vec![attr("", "class", "x"), attr("", "selected", "selected")],
+        ),
+        chardata("a &nbsp; b"),
+        start("", "BR", vec![]),
+        end("", "BR"),
+        chardata("c"),
+        start("", "img", vec![attr("", "src", "i.png")]),
+        end("", "img"),
+        end("", "p"),
+        start("", "hr", vec![]),
+        end("", "hr"),
+        end("", "HTML"),
+    ];
+    assert_eq!(expect, tokens(&mut d, false).unwrap());
+}
+
+#[test]
+fn lenient_auto_close_with_end() {
+    let mut d = Decoder::new("<br></br><p/>".as_bytes());
+    d.strict = false;
+    d.auto_close = vec!["br".to_string()];
+
+    let expect = vec![
+        start("", "br", vec![]),
+        end("", "br"),
+        start("", "p", vec![]),
+        end("", "p"),
+    ];
+    assert_eq!(expect, tokens(&mut d, false).unwrap());
+}
+
+#[test]
+fn skip() {
+    let mut d = Decoder::new("<a><b><c/>x</b><d/></a>".as_bytes());
+    assert_eq!(start("", "a", vec![]), d.token().unwrap());
+    assert_eq!(start("", "b", vec![]), d.token().unwrap());
+    d.skip().unwrap();
+    assert_eq!(start("", "d", vec![]), d.token().unwrap());
+}
+
+#[test]
+fn input_pos() {
+    const INPUT: &str = "<a>\n  <b x=\"1\n2\"/>\n</a>";
+
+    let mut d = Decoder::new(INPUT.as_bytes());
+    let mut got = vec![];
+    loop {
+        match d.token() {
+            Ok(_) => got.push((d.input_offset(), d.input_pos())),
+            Err(Error::Eof) => break,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    let expect = vec![
+        (3, (1, 4)),
+        (6, (2, 3)),
+        (18, (3, 5)),
+        (18, (3, 5)),
+        (19, (4, 1)),
+        (23, (4, 5)),
+    ];
+    assert_eq!(expect, got);
+
+    // The newline ending the invalid name is pushed back.
+    let mut d = Decoder::new("<1\n".as_bytes());
+    assert!(d.token().is_err());
+    assert_eq!((1, 3), d.input_pos());
+    let mut d = Decoder::new("ab\n<1\n".as_bytes());
+    assert!(d.token().is_ok());
+    assert!(d.token().is_err());
+    assert_eq!((2, 3), d.input_pos());
+}
+
+#[test]
+fn io_error() {
+    struct ErrReader;
+
+    impl Read for ErrReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "boom"))
+        }
+    }
+
+    let mut d = Decoder::new(ErrReader);
+    assert!(matches!(d.token(), Err(Error::Io(_))));
+}
+
+#[test]
+fn lenient_mismatched_end() {
+    let mut d = Decoder::new("<a><b><c></a>".as_bytes());
+    d.strict = false;
+
+    let expect = vec![
+        start("", "a", vec![]),
+        start("", "b", vec![]),
+        start("", "c", vec![]),
+        end("", "c"),
+        end("", "b"),
+        end("", "a"),
+    ];
+    assert_eq!(expect, tokens(&mut d, false).unwrap());
+}
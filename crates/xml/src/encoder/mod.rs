@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::token::{is_in_character_range, is_name};
+use crate::{Error, Name, ProcInst, StartElement, Token, XML_NAMESPACE};
+
+/// An Encoder writes XML tokens to an output stream, escaping text and checking that start
+/// and end elements match.
+///
+/// The name space of an element is declared by an `xmlns` attribute on it. Name spaces of
+/// attributes are bound to prefixes derived from their URLs.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/encoder_indent.rs")]
+/// ```
+pub struct Encoder<W>
+where
+    W: Write,
+{
+    w: W,
+    prefix: String,
+    indent: String,
+    /// Whether anything has been written yet.
+    written: bool,
+
+    depth: usize,
+    /// Whether the last indented line opened an element, so that closing it right away
+    /// doesn't need a new line.
+    indented_in: bool,
+    put_newline: bool,
+
+    /// Open elements, innermost last.
+    tags: Vec<Name>,
+    /// Prefixes bound to attribute name spaces and the other way around.
+    attr_prefix: HashMap<String, String>,
+    attr_ns: HashMap<String, String>,
+    /// Prefixes bound by each open element.
+    prefixes: Vec<Vec<String>>,
+    seq: usize,
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    /// Returns a new encoder that writes to `w`, without indentation.
+    pub fn new(w: W) -> Self {
+        Self {
+            w,
+            prefix: String::new(),
+            indent: String::new(),
+            written: false,
+            depth: 0,
+            indented_in: false,
+            put_newline: false,
+            tags: vec![],
+            attr_prefix: HashMap::new(),
+            attr_ns: HashMap::new(),
+            prefixes: vec![],
+            seq: 0,
+        }
+    }
+
+    /// Checks that all elements have been closed, and flushes the underlying writer.
+    pub fn close(&mut self) -> Result<(), Error> {
+        if let Some(name) = self.tags.last() {
+            return Err(Error::Invalid(format!("unclosed tag <{}>", name.local)));
+        }
+        self.flush()
+    }
+
+    /// Writes the XML encoding of `t` to the stream.
+    ///
+    /// Start and end elements must match. Comments can't contain `-->`, processing
+    /// instructions can't contain `?>` and only the first token may be an XML declaration,
+    /// a processing instruction of target `xml`.
+    pub fn encode_token(&mut self, t: &Token) -> Result<(), Error> {
+        let mut out = vec![];
+        match t {
+            Token::StartElement(t) => self.write_start(&mut out, t)?,
+            Token::EndElement(t) => self.write_end(&mut out, &t.name)?,
+            Token::CharData(t) => escape_text(&mut out, t, false),
+            Token::Comment(t) => {
+                if t.contains("-->") {
+                    return Err(Error::Invalid(
+                        "EncodeToken of Comment containing --> marker".to_string(),
+                    ));
+                }
+                out.extend_from_slice(b"<!--");
+                out.extend_from_slice(t.as_bytes());
+                out.extend_from_slice(b"-->");
+            }
+            Token::ProcInst(ProcInst { target, inst }) => {
+                // An XML declaration must come first.
+                if target == "xml" && self.written {
+                    return Err(Error::Invalid(
+                        "EncodeToken of ProcInst xml target only valid for xml declaration, \
+                         first token encoded"
+                            .to_string(),
+                    ));
+                }
+                if !is_name(target.as_bytes()) {
+                    return Err(Error::Invalid(
+                        "EncodeToken of ProcInst with invalid Target".to_string(),
+                    ));
+                }
+                if inst.contains("?>") {
+                    return Err(Error::Invalid(
+                        "EncodeToken of ProcInst containing ?> marker".to_string(),
+                    ));
+                }
+                out.extend_from_slice(b"<?");
+                out.extend_from_slice(target.as_bytes());
+                if !inst.is_empty() {
+                    out.push(b' ');
+                    out.extend_from_slice(inst.as_bytes());
+                }
+                out.extend_from_slice(b"?>");
+            }
+            Token::Directive(t) => {
+                if !is_valid_directive(t.as_bytes()) {
+                    return Err(Error::Invalid(
+                        "EncodeToken of Directive containing wrong < or > markers".to_string(),
+                    ));
+                }
+                out.extend_from_slice(b"<!");
+                out.extend_from_slice(t.as_bytes());
+                out.push(b'>');
+            }
+        }
+
+        self.written = true;
+        self.w.write_all(&out)?;
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.w.flush().map_err(Error::Io)
+    }
+
+    /// Makes the encoder start each element, comment aside, on a new line beginning with
+    /// `prefix` followed by one copy of `indent` per nesting level.
+    pub fn indent(&mut self, prefix: &str, indent: &str) {
+        self.prefix = prefix.to_string();
+        self.indent = indent.to_string();
+    }
+
+    fn write_start(&mut self, out: &mut Vec<u8>, start: &StartElement) -> Result<(), Error> {
+        if start.name.local.is_empty() {
+            return Err(Error::Invalid("start tag with no name".to_string()));
+        }
+
+        self.tags.push(start.name.clone());
+        self.prefixes.push(vec![]);
+        self.write_indent(out, 1);
+        out.push(b'<');
+        out.extend_from_slice(start.name.local.as_bytes());
+        if !start.name.space.is_empty() {
+            out.extend_from_slice(b" xmlns=\"");
+            escape_text(out, &start.name.space, true);
+            out.push(b'"');
+        }
+
+        for attr in start.attr.iter().filter(|v| !v.name.local.is_empty()) {
+            out.push(b' ');
+            if !attr.name.space.is_empty() {
+                let prefix = self.create_attr_prefix(out, &attr.name.space);
+                out.extend_from_slice(prefix.as_bytes());
+                out.push(b':');
+            }
+            out.extend_from_slice(attr.name.local.as_bytes());
+            out.extend_from_slice(b"=\"");
+            escape_text(out, &attr.value, true);
+            out.push(b'"');
+        }
+        out.push(b'>');
+        Ok(())
+    }
+
+    fn write_end(&mut self, out: &mut Vec<u8>, name: &Name) -> Result<(), Error> {
+        if name.local.is_empty() {
+            return Err(Error::Invalid("end tag with no name".to_string()));
+        }
+        let top = match self.tags.last() {
+            Some(v) => v,
+            None => {
+                return Err(Error::Invalid(format!(
+                    "end tag </{}> without start tag",
+                    name.local
+                )))
+            }
+        };
+        if top.local != name.local {
+            return Err(Error::Invalid(format!(
+                "end tag </{}> does not match start tag <{}>",
+                name.local, top.local
+            )));
+        }
+        if top.space != name.space {
+            return Err(Error::Invalid(format!(
+                "end tag </{}> in namespace {} does not match start tag <{}> in namespace {}",
+                name.local, name.space, top.local, top.space
+            )));
+        }
+
+        self.tags.pop();
+        self.write_indent(out, -1);
+        out.extend_from_slice(b"</");
+        out.extend_from_slice(name.local.as_bytes());
+        out.push(b'>');
+
+        for prefix in self.prefixes.pop().unwrap_or_default() {
+            if let Some(url) = self.attr_ns.remove(&prefix) {
+                self.attr_prefix.remove(&url);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the prefix bound to the name space `url` of attributes, binding one by an
+    /// `xmlns` attribute written to `out` if needed.
+    fn create_attr_prefix(&mut self, out: &mut Vec<u8>, url: &str) -> String {
+        if let Some(v) = self.attr_prefix.get(url) {
+            return v.clone();
+        }
+        match url {
+            XML_NAMESPACE => return "xml".to_string(),
+            "xmlns" => return "xmlns".to_string(),
+            _ => {}
+        }
+
+        // Try the last element of the path, falling back to _.
+        let mut prefix = url.trim_end_matches('/');
+        if let Some(i) = prefix.rfind('/') {
+            prefix = &prefix[i + 1..];
+        }
+        let mut prefix = if prefix.is_empty() || !is_name(prefix.as_bytes()) || prefix.contains(':')
+        {
+            "_".to_string()
+        } else {
+            prefix.to_string()
+        };
+        // Names starting with xml are reserved, whatever the case.
+        if prefix.len() >= 3 && prefix[..3].eq_ignore_ascii_case("xml") {
+            prefix.insert(0, '_');
+        }
+        if self.attr_ns.contains_key(&prefix) {
+            loop {
+                self.seq += 1;
+                let id = format!("{}_{}", prefix, self.seq);
+                if !self.attr_ns.contains_key(&id) {
+                    prefix = id;
+                    break;
+                }
+            }
+        }
+
+        self.attr_prefix.insert(url.to_string(), prefix.clone());
+        self.attr_ns.insert(prefix.clone(), url.to_string());
+        if let Some(v) = self.prefixes.last_mut() {
+            v.push(prefix.clone());
+        }
+
+        out.extend_from_slice(b"xmlns:");
+        out.extend_from_slice(prefix.as_bytes());
+        out.extend_from_slice(b"=\"");
+        escape_text(out, url, true);
+        out.extend_from_slice(b"\" ");
+        prefix
+    }
+
+    fn write_indent(&mut self, out: &mut Vec<u8>, depth_delta: i32) {
+        if self.prefix.is_empty() && self.indent.is_empty() {
+            // The depth is kept for indentation turned on within an element.
+            if depth_delta < 0 {
+                self.depth -= 1;
+            } else if depth_delta > 0 {
+                self.depth += 1;
+            }
+            self.indented_in = false;
+            return;
+        }
+        if depth_delta < 0 {
+            self.depth -= 1;
+            if self.indented_in {
+                self.indented_in = false;
+                return;
+            }
+        }
+        self.indented_in = false;
+
+        if self.put_newline {
+            out.push(b'\n');
+        } else {
+            self.put_newline = true;
+        }
+        out.extend_from_slice(self.prefix.as_bytes());
+        for _ in 0..self.depth {
+            out.extend_from_slice(self.indent.as_bytes());
+        }
+        if depth_delta > 0 {
+            self.depth += 1;
+            self.indented_in = true;
+        }
+    }
+}
+
+/// Returns `s` with the characters special to XML escaped, so that it can be used as text or
+/// as an attribute value.
+///
+/// # Example
+/// ```
+/// assert_eq!("a &lt; b &amp;&amp; &#34;c&#34;&#xA;", xml::escape_string("a < b && \"c\"\n"));
+/// ```
+pub fn escape_string(s: &str) -> String {
+    let mut out = Vec::with_capacity(s.len());
+    escape_text(&mut out, s, true);
+    String::from_utf8(out).expect("UTF-8")
+}
+
+/// Appends `s` to `out` with the characters special to XML escaped. Characters outside of
+/// the XML character range are replaced by U+FFFD.
+fn escape_text(out: &mut Vec<u8>, s: &str, escape_newline: bool) {
+    for c in s.chars() {
+        let esc: &[u8] = match c {
+            '"' => b"&#34;",
+            '\'' => b"&#39;",
+            '&' => b"&amp;",
+            '<' => b"&lt;",
+            '>' => b"&gt;",
+            '\t' => b"&#x9;",
+            '\n' if escape_newline => b"&#xA;",
+            '\r' => b"&#xD;",
+            c if !is_in_character_range(c) => "\u{fffd}".as_bytes(),
+            c => {
+                out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                continue;
+            }
+        };
+        out.extend_from_slice(esc);
+    }
+}
+
+/// Tells whether `d` can be encoded as a directive: its angle brackets must be balanced
+/// outside of quotes and comments, and its quotes and comments closed.
+fn is_valid_directive(d: &[u8]) -> bool {
+    let mut depth = 0i32;
+    let mut in_quote = None;
+    let mut in_comment = false;
+    let mut i = 0;
+    while i < d.len() {
+        let c = d[i];
+        if in_comment {
+            if d[i..].starts_with(b"-->") {
+                in_comment = false;
+                i += 3;
+                continue;
+            }
+        } else if let Some(q) = in_quote {
+            if c == q {
+                in_quote = None;
+            }
+        } else {
+            match c {
+                b'\'' | b'"' => in_quote = Some(c),
+                b'<' if d[i..].starts_with(b"<!--") => {
+                    in_comment = true;
+                    i += 4;
+                    continue;
+                }
+                b'<' => depth += 1,
+                b'>' if depth == 0 => return false,
+                b'>' => depth -= 1,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    depth == 0 && in_quote.is_none() && !in_comment
+}
+
+#[cfg(test)]
+mod tests;
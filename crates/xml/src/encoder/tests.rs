@@ -0,0 +1,206 @@
+use crate::{Attr, Encoder, EndElement, Name, ProcInst, StartElement, Token, XML_NAMESPACE};
+
+fn start(space: &str, local: &str, attr: Vec<Attr>) -> Token {
+    Token::StartElement(StartElement {
+        name: Name::new(space, local),
+        attr,
+    })
+}
+
+fn end(space: &str, local: &str) -> Token {
+    Token::EndElement(EndElement {
+        name: Name::new(space, local),
+    })
+}
+
+fn attr(space: &str, local: &str, value: &str) -> Attr {
+    Attr {
+        name: Name::new(space, local),
+        value: value.to_string(),
+    }
+}
+
+fn proc_inst(target: &str, inst: &str) -> Token {
+    Token::ProcInst(ProcInst {
+        target: target.to_string(),
+        inst: inst.to_string(),
+    })
+}
+
+fn encode(tokens: &[Token], prefix: &str, indent: &str) -> Result<String, String> {
+    let mut out = vec![];
+    let mut e = Encoder::new(&mut out);
+    e.indent(prefix, indent);
+    for t in tokens {
+        e.encode_token(t).map_err(|err| err.to_string())?;
+    }
+    e.close().map_err(|err| err.to_string())?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn encode_token() {
+    let test_vector = vec![
+        (
+            vec![proc_inst("xml", r#"version="1.0""#), start("", "a", vec![])],
+            r#"<?xml version="1.0"?><a>"#,
+        ),
+        (
+            vec![
+                start("", "a", vec![attr("", "x", "\"'&<>\t\n\r")]),
+                Token::CharData("\"'&<>\t\n\r\u{1}".to_string()),
+            ],
+            "<a x=\"&#34;&#39;&amp;&lt;&gt;&#x9;&#xA;&#xD;\">&#34;&#39;&amp;&lt;&gt;&#x9;\n&#xD;\u{fffd}",
+        ),
+        (
+            vec![start("space", "local", vec![])],
+            r#"<local xmlns="space">"#,
+        ),
+        (
+            vec![start(
+                "",
+                "a",
+                vec![
+                    attr("http://example.com/ns/", "x", "1"),
+                    attr(XML_NAMESPACE, "lang", "en"),
+                    attr("xmlns", "p", "q"),
+                    attr("urn:x", "y", "2"),
+                    attr("http://example.com/xmlfoo", "z", "3"),
+                    attr("http://other.org/ns", "w", "4"),
+                ],
+            )],
+            concat!(
+                r#"<a xmlns:ns="http://example.com/ns/" ns:x="1" xml:lang="en" xmlns:p="q" "#,
+                r#"xmlns:_="urn:x" _:y="2" xmlns:_xmlfoo="http://example.com/xmlfoo" _xmlfoo:z="3" "#,
+                r#"xmlns:ns_1="http://other.org/ns" ns_1:w="4">"#,
+            ),
+        ),
+        (
+            vec![
+                Token::Comment(" c ".to_string()),
+                Token::Directive("DOCTYPE a [<!ENTITY b \"c>\">]".to_string()),
+                proc_inst("pi", ""),
+            ],
+            "<!-- c --><!DOCTYPE a [<!ENTITY b \"c>\">]><?pi?>",
+        ),
+    ];
+
+    for (i, (tokens, expect)) in test_vector.into_iter().enumerate() {
+        let mut out = vec![];
+        let mut e = Encoder::new(&mut out);
+        for t in tokens.iter() {
+            e.encode_token(t).unwrap();
+        }
+        assert_eq!(expect, String::from_utf8(out).unwrap(), "#{}", i);
+    }
+}
+
+#[test]
+fn encode_token_indent() {
+    let tokens = vec![
+        start("", "a", vec![]),
+        start("", "b", vec![]),
+        end("", "b"),
+        start("", "c", vec![]),
+        Token::CharData("x".to_string()),
+        start("", "d", vec![]),
+        end("", "d"),
+        end("", "c"),
+        end("", "a"),
+    ];
+
+    let expect = "> <a>\n>   <b></b>\n>   <c>x\n>     <d></d>\n>   </c>\n> </a>";
+    assert_eq!(expect, encode(&tokens, "> ", "  ").unwrap());
+}
+
+#[test]
+fn encode_token_toggle_indent() {
+    let mut out = vec![];
+    let mut e = Encoder::new(&mut out);
+    e.encode_token(&start("", "a", vec![])).unwrap();
+    e.encode_token(&start("", "b", vec![])).unwrap();
+    e.indent("", "  ");
+    e.encode_token(&start("", "c", vec![])).unwrap();
+    e.encode_token(&end("", "c")).unwrap();
+    e.encode_token(&end("", "b")).unwrap();
+    e.indent("", "");
+    e.encode_token(&start("", "d", vec![])).unwrap();
+    e.indent("", "  ");
+    e.encode_token(&end("", "d")).unwrap();
+    e.encode_token(&end("", "a")).unwrap();
+    e.close().unwrap();
+
+    // As in Go, the first indented element doesn't start a new line.
+    let expect = "<a><b>    <c></c>\n  </b><d>\n  </d>\n</a>";
+    assert_eq!(expect, String::from_utf8(out).unwrap());
+}
+
+#[test]
+fn encode_token_error() {
+    let test_vector = vec![
+        (vec![start("", "", vec![])], "xml: start tag with no name"),
+        (vec![end("", "a")], "xml: end tag </a> without start tag"),
+        (
+            vec![start("", "a", vec![]), end("", "b")],
+            "xml: end tag </b> does not match start tag <a>",
+        ),
+        (
+            vec![start("x", "a", vec![]), end("y", "a")],
+            "xml: end tag </a> in namespace y does not match start tag <a> in namespace x",
+        ),
+        (vec![start("", "a", vec![])], "xml: unclosed tag <a>"),
+        (
+            vec![Token::Comment("a-->".to_string())],
+            "xml: EncodeToken of Comment containing --> marker",
+        ),
+        (
+            vec![start("", "a", vec![]), proc_inst("xml", "")],
+            "xml: EncodeToken of ProcInst xml target only valid for xml declaration, first token encoded",
+        ),
+        (
+            vec![proc_inst("1", "")],
+            "xml: EncodeToken of ProcInst with invalid Target",
+        ),
+        (
+            vec![proc_inst("a", "?>")],
+            "xml: EncodeToken of ProcInst containing ?> marker",
+        ),
+        (
+            vec![Token::Directive("a>".to_string())],
+            "xml: EncodeToken of Directive containing wrong < or > markers",
+        ),
+        (
+            vec![Token::Directive("<a".to_string())],
+            "xml: EncodeToken of Directive containing wrong < or > markers",
+        ),
+        (
+            vec![Token::Directive("<!-- a".to_string())],
+            "xml: EncodeToken of Directive containing wrong < or > markers",
+        ),
+    ];
+
+    for (i, (tokens, expect)) in test_vector.into_iter().enumerate() {
+        let got = encode(&tokens, "", "").unwrap_err();
+        assert_eq!(expect, got, "#{}", i);
+    }
+}
+
+#[test]
+fn round_trip() {
+    const INPUT: &str = r#"<a xmlns="ns" x="1 &amp; 2"><!--c--><b>t&lt;</b></a>"#;
+
+    let mut d = crate::Decoder::new(INPUT.as_bytes());
+    let mut out = vec![];
+    let mut e = Encoder::new(&mut out);
+    loop {
+        match d.raw_token() {
+            Ok(t) => e.encode_token(&t).unwrap(),
+            Err(crate::Error::Eof) => break,
+            Err(err) => panic!("{}", err),
+        }
+    }
+    e.close().unwrap();
+
+    let expect = r#"<a xmlns="ns" x="1 &amp; 2"><!--c--><b>t&lt;</b></a>"#;
+    assert_eq!(expect, String::from_utf8(out).unwrap());
+}
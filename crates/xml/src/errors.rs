@@ -0,0 +1,27 @@
+use std::io;
+
+/// Possible errors during encoding and decoding.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The stream ends cleanly, with all elements closed.
+    #[error("xml: end of stream")]
+    Eof,
+    /// A token can't be encoded at this point, as an end tag not matching the open element.
+    #[error("xml: {0}")]
+    Invalid(String),
+    /// IO error reported by the wrapped reader or writer.
+    #[error("xml: io: {0}")]
+    Io(io::Error),
+    /// The input isn't well-formed XML.
+    #[error("XML syntax error on line {line}: {msg}")]
+    Syntax { msg: String, line: usize },
+    /// The input declares a version other than 1.0 or an encoding other than UTF-8.
+    #[error("xml: {0}")]
+    Unsupported(String),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
@@ -0,0 +1,28 @@
+//! Implementation of a simple XML 1.0 parser and writer that understands XML name spaces,
+//! after Go's `encoding/xml`.
+//!
+//! A [Decoder] is a pull parser reading a document from an [io::Read][std::io::Read] as a
+//! stream of [Token]s: start and end elements, character data, comments, processing
+//! instructions and directives. Name space prefixes are resolved to their URLs, and syntax
+//! errors report the line at which they were detected. Decoders are strict by default, and
+//! can be relaxed to parse HTML-like documents.
+//!
+//! An [Encoder] writes tokens to an [io::Write][std::io::Write], escaping text and
+//! optionally indenting elements.
+//!
+//! # Example
+//! ```
+#![doc = include_str!("../examples/xml_decoder_token.rs")]
+//! ```
+
+#![allow(clippy::needless_doctest_main)]
+
+mod decoder;
+mod encoder;
+mod errors;
+mod token;
+
+pub use decoder::*;
+pub use encoder::*;
+pub use errors::*;
+pub use token::{Attr, EndElement, Name, ProcInst, StartElement, Token, XML_NAMESPACE};
@@ -0,0 +1,111 @@
+/// The name space bound to the `xml` prefix.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// `Name` is an XML name annotated with a name space identifier.
+///
+/// The decoder resolves prefixes to name space URLs, so `space` is a URL after
+/// [Decoder::token][crate::Decoder::token] and a prefix after
+/// [Decoder::raw_token][crate::Decoder::raw_token].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Name {
+    pub space: String,
+    pub local: String,
+}
+
+/// `Attr` is an attribute of an XML element.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Attr {
+    pub name: Name,
+    pub value: String,
+}
+
+/// `StartElement` is an XML start element, as `<a href="x">`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StartElement {
+    pub name: Name,
+    pub attr: Vec<Attr>,
+}
+
+/// `EndElement` is an XML end element, as `</a>`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EndElement {
+    pub name: Name,
+}
+
+/// `ProcInst` is an XML processing instruction of the form `<?target inst?>`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ProcInst {
+    pub target: String,
+    pub inst: String,
+}
+
+/// `Token` is a piece of an XML document.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Token {
+    StartElement(StartElement),
+    EndElement(EndElement),
+    /// Text with entities and character references replaced by the characters they stand
+    /// for, or the contents of a CDATA section.
+    CharData(String),
+    /// A comment of the form `<!--comment-->`, without the markers.
+    Comment(String),
+    ProcInst(ProcInst),
+    /// A directive of the form `<!text>`, as `<!DOCTYPE html>`, without the markers.
+    Directive(String),
+}
+
+impl Name {
+    /// Returns the name `local` in the name space `space`.
+    pub fn new(space: &str, local: &str) -> Self {
+        Self {
+            space: space.to_string(),
+            local: local.to_string(),
+        }
+    }
+}
+
+impl Attr {
+    /// Returns the attribute named `local`, in no name space, of value `value`.
+    pub fn new(local: &str, value: &str) -> Self {
+        Self {
+            name: Name::new("", local),
+            value: value.to_string(),
+        }
+    }
+}
+
+impl StartElement {
+    /// Returns the element named `name` without attributes.
+    pub fn new(name: Name) -> Self {
+        Self { name, attr: vec![] }
+    }
+
+    /// Returns the end element closing `self`.
+    pub fn end(&self) -> EndElement {
+        EndElement {
+            name: self.name.clone(),
+        }
+    }
+}
+
+/// Tells whether `s` is a valid XML name. Non-ASCII characters are all allowed.
+pub(crate) fn is_name(s: &[u8]) -> bool {
+    match s.first() {
+        None => false,
+        Some(&c) if c < 0x80 && !(c.is_ascii_alphabetic() || c == b'_' || c == b':') => false,
+        _ => s.iter().all(|&c| c >= 0x80 || is_name_byte(c)),
+    }
+}
+
+pub(crate) fn is_name_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b':' | b'.' | b'-')
+}
+
+/// Tells whether `c` may appear in an XML document, as defined in section 2.2 of the spec.
+pub(crate) fn is_in_character_range(c: char) -> bool {
+    matches!(c,
+        '\u{9}' | '\u{a}' | '\u{d}'
+        | '\u{20}'..='\u{d7ff}'
+        | '\u{e000}'..='\u{fffd}'
+        | '\u{10000}'..='\u{10ffff}')
+}
@@ -0,0 +1,22 @@
+fn main() {
+    let data = b"Hello, Gophers!";
+
+    let mut got = vec![];
+    for name in ["hex", "base32", "base64url", "ascii85"] {
+        let codec = encoding::codec_by_name(name).expect("known codec");
+        let encoded = codec.encode_to_string(data);
+        assert_eq!(
+            data.to_vec(),
+            codec.decode_to_vec(encoded.as_bytes()).unwrap()
+        );
+        got.push(encoded);
+    }
+
+    let expect = vec![
+        "48656c6c6f2c20476f706865727321",
+        "JBSWY3DPFQQEO33QNBSXE4ZB",
+        "SGVsbG8sIEdvcGhlcnMh",
+        "87cURD_*#$DfBB$EcWB",
+    ];
+    assert_eq!(expect, got);
+}
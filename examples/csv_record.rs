@@ -0,0 +1,29 @@
+use csv::{Reader, Writer};
+use encoding::TextMarshaler;
+
+fn main() {
+    let (name, port, tls) = ("gopher".to_string(), 8443u16, true);
+    let url = *base64::URL_ENCODING;
+
+    let mut out = vec![];
+    {
+        let mut w = Writer::new(&mut out);
+        let record = encoding::marshal_record(&[&name, &port, &tls, &url]).unwrap();
+        w.write(record).expect("writing record to csv");
+        w.flush().unwrap();
+    }
+
+    const EXPECT: &str =
+        "gopher,8443,true,ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_=\n";
+    assert_eq!(EXPECT, std::str::from_utf8(&out).unwrap());
+
+    let (mut name, mut port, mut tls) = (String::new(), 0u16, false);
+    let mut enc = *base64::STD_ENCODING;
+
+    let mut r = Reader::new(out.as_slice());
+    let record = r.read().unwrap();
+    encoding::unmarshal_record(record, &mut [&mut name, &mut port, &mut tls, &mut enc]).unwrap();
+
+    assert_eq!(("gopher", 8443, true), (name.as_str(), port, tls));
+    assert_eq!(url.marshal_text().unwrap(), enc.marshal_text().unwrap());
+}
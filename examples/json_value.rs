@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+use json::{Map, Value};
+use pem::Block;
+
+fn main() {
+    let block = Block {
+        type_: "MESSAGE".to_string(),
+        headers: HashMap::new(),
+        bytes: b"test".to_vec(),
+    };
+
+    let mut fields = Map::new();
+    fields.insert("cert".to_string(), encoding::marshal_json(&block).unwrap());
+    let v = Value::Object(fields);
+
+    const EXPECT: &str = r#"{"cert":"-----BEGIN MESSAGE-----\ndGVzdA==\n-----END MESSAGE-----\n"}"#;
+    assert_eq!(EXPECT, v.to_string());
+
+    let mut got = Block::default();
+    encoding::unmarshal_json(v.get("cert").unwrap(), &mut got).unwrap();
+    assert_eq!(block, got);
+}
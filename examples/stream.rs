@@ -0,0 +1,22 @@
+use std::io::{Read, Write};
+
+use encoding::{Decoder, Encoder};
+
+fn main() {
+    let codec = encoding::codec_by_name("base32").unwrap();
+
+    let mut encoded = vec![];
+    {
+        let mut w = Encoder::new(&codec, &mut encoded);
+        w.write_all(b"Hello, ").unwrap();
+        w.write_all(b"Gophers!").unwrap();
+        // Encodes the final partial block.
+        w.flush().unwrap();
+    }
+    assert_eq!(b"JBSWY3DPFQQEO33QNBSXE4ZB", encoded.as_slice());
+
+    let mut decoded = vec![];
+    let mut r = Decoder::new(&codec, encoded.as_slice());
+    r.read_to_end(&mut decoded).unwrap();
+    assert_eq!(b"Hello, Gophers!", decoded.as_slice());
+}
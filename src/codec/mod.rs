@@ -0,0 +1,307 @@
+use crate::{DecodeError, Error};
+
+/// Names of the codecs [codec_by_name] knows, in lowercase.
+pub const CODEC_NAMES: &[&str] = &[
+    "hex",
+    "base16",
+    "base32",
+    "base32hex",
+    "base64",
+    "base64url",
+    "rawbase64",
+    "rawbase64url",
+    "ascii85",
+    "base85",
+];
+
+/// `Codec` is a binary-to-text encoding, converting bytes to and from text block by block.
+///
+/// It's implemented by [Hex], [base32::Encoding], [base64::Encoding] and [Ascii85], so that
+/// they can be used interchangeably, as by the streaming [Encoder][crate::Encoder] and
+/// [Decoder][crate::Decoder].
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/codec_by_name.rs")]
+/// ```
+pub trait Codec {
+    /// Returns the number of bytes encoded together. Encoding a whole number of blocks gives
+    /// the same result as encoding each of them in turn.
+    fn block_len(&self) -> usize;
+
+    /// Returns the maximum length of the encoding of `n` bytes.
+    fn encoded_len(&self, n: usize) -> usize;
+
+    /// Encodes `src` into at most [encoded_len(src.len())][Self::encoded_len] bytes of `dst`,
+    /// returning the number of bytes written.
+    fn encode(&self, dst: &mut [u8], src: &[u8]) -> usize;
+
+    /// Returns the maximum length of the decoding of `n` bytes.
+    fn decoded_len(&self, n: usize) -> usize;
+
+    /// Decodes `src` into at most [decoded_len(src.len())][Self::decoded_len] bytes of `dst`,
+    /// returning the number of bytes written and the number consumed from `src`.
+    ///
+    /// If `flush` is false, `src` may not be the end of the input, and a trailing incomplete
+    /// block is left unconsumed. Otherwise, `src` is decoded completely.
+    fn decode(&self, dst: &mut [u8], src: &[u8], flush: bool) -> Result<(usize, usize), Error>;
+
+    /// Returns the encoding of `src` as a string, in which each byte above ASCII is the rune
+    /// of the same value.
+    fn encode_to_string(&self, src: &[u8]) -> String {
+        let mut dst = vec![0; self.encoded_len(src.len())];
+        let n = self.encode(&mut dst, src);
+        dst.truncate(n);
+        dst.into_iter().map(char::from).collect()
+    }
+
+    /// Returns the bytes represented by `src`, which is the whole input.
+    fn decode_to_vec(&self, src: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut dst = vec![0; self.decoded_len(src.len())];
+        let (n, _) = self.decode(&mut dst, src, true)?;
+        dst.truncate(n);
+        Ok(dst)
+    }
+}
+
+/// Returns the codec named `name`, ignoring case, or `None` if unknown.
+///
+/// The names are those of [CODEC_NAMES]:
+/// - `hex` or `base16` for [Hex];
+/// - `base32` and `base32hex` for [base32::STD_ENCODING] and [base32::HEX_ENCODING];
+/// - `base64`, `base64url`, `rawbase64` and `rawbase64url` for [base64::STD_ENCODING],
+///   [base64::URL_ENCODING], [base64::RAW_STD_ENCODING] and [base64::RAW_URL_ENCODING];
+/// - `ascii85` or `base85` for [Ascii85].
+pub fn codec_by_name(name: &str) -> Option<Box<dyn Codec>> {
+    let codec: Box<dyn Codec> = match name.to_ascii_lowercase().as_str() {
+        "hex" | "base16" => Box::new(Hex),
+        "base32" => Box::new(*base32::STD_ENCODING),
+        "base32hex" => Box::new(*base32::HEX_ENCODING),
+        "base64" => Box::new(*base64::STD_ENCODING),
+        "base64url" => Box::new(*base64::URL_ENCODING),
+        "rawbase64" => Box::new(*base64::RAW_STD_ENCODING),
+        "rawbase64url" => Box::new(*base64::RAW_URL_ENCODING),
+        "ascii85" | "base85" => Box::new(Ascii85),
+        _ => return None,
+    };
+    Some(codec)
+}
+
+/// `Hex` is the [Codec] of the [hex] encoding, with lowercase digits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Hex;
+
+/// `Ascii85` is the [Codec] of the [ascii85] encoding, without the `<~` and `~>` markers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ascii85;
+
+impl Codec for Hex {
+    fn block_len(&self) -> usize {
+        1
+    }
+
+    fn encoded_len(&self, n: usize) -> usize {
+        hex::encoded_len(n)
+    }
+
+    fn encode(&self, dst: &mut [u8], src: &[u8]) -> usize {
+        hex::encode(dst, src)
+    }
+
+    fn encode_to_string(&self, src: &[u8]) -> String {
+        hex::encode_to_string(src)
+    }
+
+    fn decoded_len(&self, n: usize) -> usize {
+        hex::decoded_len(n)
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], flush: bool) -> Result<(usize, usize), Error> {
+        let n = if flush { src.len() } else { src.len() / 2 * 2 };
+        match hex::decode(dst, &src[..n]) {
+            Ok(written) => Ok((written, n)),
+            Err((err, written)) => {
+                // Pairs of digits are decoded in turn, so the error is at the first byte that
+                // isn't a digit or else at the unpaired last one.
+                let idx = match err {
+                    hex::Error::InvalidByte(_) => src[..n]
+                        .iter()
+                        .position(|c| !c.is_ascii_hexdigit())
+                        .unwrap_or(n),
+                    hex::Error::Length => n - 1,
+                };
+                Err(Error::Decode(DecodeError::Hex { err, idx, written }))
+            }
+        }
+    }
+}
+
+impl Codec for base32::Encoding {
+    fn block_len(&self) -> usize {
+        5
+    }
+
+    fn encoded_len(&self, n: usize) -> usize {
+        base32::Encoding::encoded_len(self, n)
+    }
+
+    fn encode(&self, dst: &mut [u8], src: &[u8]) -> usize {
+        base32::Encoding::encode(self, dst, src);
+        base32::Encoding::encoded_len(self, src.len())
+    }
+
+    fn encode_to_string(&self, src: &[u8]) -> String {
+        base32::Encoding::encode_to_string(self, src)
+    }
+
+    fn decoded_len(&self, n: usize) -> usize {
+        base32::Encoding::decoded_len(self, n)
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], flush: bool) -> Result<(usize, usize), Error> {
+        let n = if flush {
+            src.len()
+        } else {
+            complete_quanta_len(src, 8)
+        };
+        match base32::Encoding::decode(self, dst, &src[..n]) {
+            Ok(written) => Ok((written, n)),
+            Err(err) => Err(Error::Decode(DecodeError::Base32(err))),
+        }
+    }
+}
+
+impl Codec for base64::Encoding {
+    fn block_len(&self) -> usize {
+        3
+    }
+
+    fn encoded_len(&self, n: usize) -> usize {
+        base64::Encoding::encoded_len(self, n)
+    }
+
+    fn encode(&self, dst: &mut [u8], src: &[u8]) -> usize {
+        base64::Encoding::encode(self, dst, src);
+        base64::Encoding::encoded_len(self, src.len())
+    }
+
+    fn encode_to_string(&self, src: &[u8]) -> String {
+        base64::Encoding::encode_to_string(self, src)
+    }
+
+    fn decoded_len(&self, n: usize) -> usize {
+        base64::Encoding::decoded_len(self, n)
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], flush: bool) -> Result<(usize, usize), Error> {
+        let n = if flush {
+            src.len()
+        } else {
+            complete_quanta_len(src, 4)
+        };
+        match base64::Encoding::decode(self, dst, &src[..n]) {
+            Ok(written) => Ok((written, n)),
+            Err(err) => Err(Error::Decode(DecodeError::Base64(err))),
+        }
+    }
+}
+
+impl Codec for Ascii85 {
+    fn block_len(&self) -> usize {
+        4
+    }
+
+    fn encoded_len(&self, n: usize) -> usize {
+        ascii85::max_encoded_len(n)
+    }
+
+    fn encode(&self, dst: &mut [u8], src: &[u8]) -> usize {
+        ascii85::encode(dst, src)
+    }
+
+    fn decoded_len(&self, n: usize) -> usize {
+        // Every `z` stands for 4 zero bytes.
+        n * 4
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], flush: bool) -> Result<(usize, usize), Error> {
+        ascii85::decode(dst, src, flush).map_err(|err| Error::Decode(DecodeError::Ascii85(err)))
+    }
+}
+
+impl<C> Codec for &C
+where
+    C: Codec + ?Sized,
+{
+    fn block_len(&self) -> usize {
+        (**self).block_len()
+    }
+
+    fn encoded_len(&self, n: usize) -> usize {
+        (**self).encoded_len(n)
+    }
+
+    fn encode(&self, dst: &mut [u8], src: &[u8]) -> usize {
+        (**self).encode(dst, src)
+    }
+
+    fn encode_to_string(&self, src: &[u8]) -> String {
+        (**self).encode_to_string(src)
+    }
+
+    fn decoded_len(&self, n: usize) -> usize {
+        (**self).decoded_len(n)
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], flush: bool) -> Result<(usize, usize), Error> {
+        (**self).decode(dst, src, flush)
+    }
+}
+
+impl<C> Codec for Box<C>
+where
+    C: Codec + ?Sized,
+{
+    fn block_len(&self) -> usize {
+        (**self).block_len()
+    }
+
+    fn encoded_len(&self, n: usize) -> usize {
+        (**self).encoded_len(n)
+    }
+
+    fn encode(&self, dst: &mut [u8], src: &[u8]) -> usize {
+        (**self).encode(dst, src)
+    }
+
+    fn encode_to_string(&self, src: &[u8]) -> String {
+        (**self).encode_to_string(src)
+    }
+
+    fn decoded_len(&self, n: usize) -> usize {
+        (**self).decoded_len(n)
+    }
+
+    fn decode(&self, dst: &mut [u8], src: &[u8], flush: bool) -> Result<(usize, usize), Error> {
+        (**self).decode(dst, src, flush)
+    }
+}
+
+/// Returns the length of the longest prefix of `src` holding a whole number of quanta of
+/// `size` symbols, newlines aside.
+fn complete_quanta_len(src: &[u8], size: usize) -> usize {
+    let (mut n, mut symbols) = (0, 0);
+    for (i, &c) in src.iter().enumerate() {
+        if c == b'\r' || c == b'\n' {
+            continue;
+        }
+        symbols += 1;
+        if symbols % size == 0 {
+            n = i + 1;
+        }
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,112 @@
+use crate::{Ascii85, Codec, Hex, TextUnmarshaler};
+
+#[test]
+fn codec_by_name() {
+    let test_vector = vec![
+        ("hex", "666f6f"),
+        ("BASE16", "666f6f"),
+        ("base32", "MZXW6==="),
+        ("base32hex", "CPNMU==="),
+        ("base64", "+/8="),
+        ("base64url", "-_8="),
+        ("rawbase64", "+/8"),
+        ("RawBase64URL", "-_8"),
+        ("ascii85", "AoDS"),
+        ("base85", "AoDS"),
+    ];
+
+    for (i, (name, expect)) in test_vector.into_iter().enumerate() {
+        let codec = crate::codec_by_name(name).unwrap();
+        let data: &[u8] = if name.to_lowercase().contains("64") {
+            &[0xfb, 0xff]
+        } else {
+            b"foo"
+        };
+        assert_eq!(expect, codec.encode_to_string(data), "#{}", i);
+        assert_eq!(
+            data,
+            codec.decode_to_vec(expect.as_bytes()).unwrap(),
+            "#{}",
+            i
+        );
+    }
+
+    assert!(crate::codec_by_name("base58").is_none());
+    for name in crate::CODEC_NAMES {
+        assert!(crate::codec_by_name(name).is_some(), "{}", name);
+    }
+}
+
+#[test]
+fn encode_to_string_non_ascii_padding() {
+    let mut e = *base32::STD_ENCODING;
+    e.unmarshal_text("ABCDEFGHIJKLMNOPQRSTUVWXYZ234567\u{e9}".as_bytes())
+        .unwrap();
+    assert_eq!(Some(0xe9), e.padding());
+
+    let expect = "MY\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}";
+    assert_eq!(expect, Codec::encode_to_string(&e, b"f"));
+    assert_eq!(expect, Codec::encode_to_string(&Box::new(e), b"f"));
+    assert_eq!(expect, e.encode_to_string(b"f"));
+}
+
+#[test]
+fn decode_partial() {
+    type Case = (Box<dyn Codec>, &'static [u8], usize, &'static [u8]);
+
+    let test_vector: Vec<Case> = vec![
+        (Box::new(Hex), b"666f6", 4, b"fo"),
+        (Box::new(*base32::STD_ENCODING), b"MZXW6===MZ", 8, b"foo"),
+        (Box::new(*base64::STD_ENCODING), b"Zm9v\r\nYm", 4, b"foo"),
+        (Box::new(*base64::STD_ENCODING), b"Zm\r\n9vYm", 6, b"foo"),
+        (Box::new(Ascii85), b"AoDTsz@", 6, b"foob\0\0\0\0"),
+    ];
+
+    for (i, (codec, src, consumed, expect)) in test_vector.into_iter().enumerate() {
+        let mut dst = vec![0; codec.decoded_len(src.len())];
+        let (n, m) = codec.decode(&mut dst, src, false).unwrap();
+        assert_eq!(consumed, m, "#{}", i);
+        assert_eq!(expect, &dst[..n], "#{}", i);
+    }
+}
+
+#[test]
+fn decode_error() {
+    let test_vector: Vec<(Box<dyn Codec>, &[u8], &str)> = vec![
+        (
+            Box::new(Hex),
+            b"6g",
+            "encoding: hex: invalid byte: g at input byte 1 after writing 0 bytes",
+        ),
+        (
+            Box::new(Hex),
+            b"666fz",
+            "encoding: hex: invalid byte: z at input byte 4 after writing 2 bytes",
+        ),
+        (
+            Box::new(Hex),
+            b"666",
+            "encoding: hex: odd length of hex string at input byte 2 after writing 1 bytes",
+        ),
+        (
+            Box::new(*base64::STD_ENCODING),
+            b"Zm9",
+            "encoding: truncated base64 data at input byte 0 after writing 0 bytes",
+        ),
+        (
+            Box::new(*base32::STD_ENCODING),
+            b"MZXW6==!",
+            "encoding: illegal base32 data at input byte 6 after writing 0 bytes",
+        ),
+        (
+            Box::new(Ascii85),
+            b"Ao~S",
+            "encoding: illegal ascii85 data '~' at input byte 2 after writing 0 bytes",
+        ),
+    ];
+
+    for (i, (codec, src, expect)) in test_vector.into_iter().enumerate() {
+        let err = codec.decode_to_vec(src).unwrap_err();
+        assert_eq!(expect, err.to_string(), "#{}", i);
+    }
+}
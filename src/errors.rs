@@ -0,0 +1,70 @@
+/// Possible errors during marshaling and unmarshaling.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The input of a [Codec][crate::Codec] is malformed.
+    #[error("encoding: {0}")]
+    Decode(DecodeError),
+    /// The data can't be unmarshaled into the value, or the value can't be marshaled.
+    #[error("encoding: {0}")]
+    Invalid(String),
+    /// The PEM block can't be encoded.
+    #[error("encoding: {0}")]
+    Pem(pem::Error),
+}
+
+impl From<pem::Error> for Error {
+    fn from(value: pem::Error) -> Self {
+        Self::Pem(value)
+    }
+}
+
+/// The error of the encoding underlying a [Codec][crate::Codec], whose offsets are those in
+/// the input of the failed decoding.
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum DecodeError {
+    /// The input of [Hex][crate::Hex] is malformed, at input byte `idx` after writing `written`
+    /// bytes.
+    #[error("hex: {err} at input byte {idx} after writing {written} bytes")]
+    Hex {
+        err: hex::Error,
+        idx: usize,
+        written: usize,
+    },
+    /// The input of a [base32::Encoding] is malformed.
+    #[error("{0}")]
+    Base32(base32::CorruptInputError),
+    /// The input of a [base64::Encoding] is malformed.
+    #[error("{0}")]
+    Base64(base64::CorruptInputError),
+    /// The input of [Ascii85][crate::Ascii85] is malformed.
+    #[error("{0}")]
+    Ascii85(ascii85::CorruptInputError),
+}
+
+impl DecodeError {
+    /// Shifts the offsets in the input and output by `consumed` and `written` bytes, for an
+    /// error in a part of a larger input.
+    pub(crate) fn offset_by(mut self, consumed: usize, written: usize) -> Self {
+        match &mut self {
+            Self::Hex {
+                idx, written: w, ..
+            } => {
+                *idx += consumed;
+                *w += written;
+            }
+            Self::Base32(err) => {
+                err.idx += consumed;
+                err.written += written;
+            }
+            Self::Base64(err) => {
+                err.idx += consumed;
+                err.written += written;
+            }
+            Self::Ascii85(err) => {
+                err.idx += consumed;
+                err.written += written;
+            }
+        }
+        self
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{Error, TextMarshaler, TextUnmarshaler};
+
+/// Returns the textual forms of `fields` as a CSV record, ready to be written by a
+/// [csv::Writer].
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/csv_record.rs")]
+/// ```
+pub fn marshal_record(fields: &[&dyn TextMarshaler]) -> Result<Vec<String>, Error> {
+    fields.iter().map(|v| marshal_string(*v)).collect()
+}
+
+/// Unmarshals the fields of a CSV `record`, as read by a [csv::Reader], into `fields` in
+/// order. The record must have as many fields as `fields`.
+pub fn unmarshal_record<S>(
+    record: &[S],
+    fields: &mut [&mut dyn TextUnmarshaler],
+) -> Result<(), Error>
+where
+    S: AsRef<str>,
+{
+    if record.len() != fields.len() {
+        return Err(Error::Invalid(format!(
+            "record has {} fields, expected {}",
+            record.len(),
+            fields.len()
+        )));
+    }
+
+    for (i, (text, v)) in record.iter().zip(fields.iter_mut()).enumerate() {
+        v.unmarshal_text(text.as_ref().as_bytes())
+            .map_err(|err| Error::Invalid(format!("field {}: {}", i, strip_prefix(&err))))?;
+    }
+    Ok(())
+}
+
+/// Returns the JSON string of the textual form of `v`, as Go's `encoding/json` marshals
+/// `TextMarshaler`s.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/json_value.rs")]
+/// ```
+pub fn marshal_json<T>(v: &T) -> Result<json::Value, Error>
+where
+    T: TextMarshaler + ?Sized,
+{
+    marshal_string(v).map(json::Value::String)
+}
+
+/// Unmarshals the JSON string `v` into `dst`, as Go's `encoding/json` unmarshals
+/// `TextUnmarshaler`s. `dst` is left unchanged if `v` is null.
+pub fn unmarshal_json<T>(v: &json::Value, dst: &mut T) -> Result<(), Error>
+where
+    T: TextUnmarshaler + ?Sized,
+{
+    match v {
+        json::Value::Null => Ok(()),
+        json::Value::String(s) => dst.unmarshal_text(s.as_bytes()),
+        v => Err(Error::Invalid(format!(
+            "cannot unmarshal JSON {} into a TextUnmarshaler",
+            kind(v)
+        ))),
+    }
+}
+
+fn marshal_string<T>(v: &T) -> Result<String, Error>
+where
+    T: TextMarshaler + ?Sized,
+{
+    String::from_utf8(v.marshal_text()?)
+        .map_err(|_| Error::Invalid("marshaled text isn't UTF-8".to_string()))
+}
+
+fn kind(v: &json::Value) -> &'static str {
+    match v {
+        json::Value::Null => "null",
+        json::Value::Bool(_) => "bool",
+        json::Value::Number(_) => "number",
+        json::Value::String(_) => "string",
+        json::Value::Array(_) => "array",
+        json::Value::Object(_) => "object",
+    }
+}
+
+/// Returns the message of `err` without the `encoding: ` prefix, to be wrapped in another one.
+fn strip_prefix(err: &Error) -> String {
+    match err {
+        Error::Decode(err) => err.to_string(),
+        Error::Invalid(msg) => msg.clone(),
+        Error::Pem(err) => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,57 @@
+use crate::{TextMarshaler, TextUnmarshaler};
+
+#[test]
+fn marshal_record() {
+    let fields: [&dyn TextMarshaler; 4] = [
+        &"a,b".to_string(),
+        &-1i64,
+        &false,
+        &*base64::RAW_URL_ENCODING,
+    ];
+    let got = crate::marshal_record(&fields).unwrap();
+
+    let expect = vec![
+        "a,b",
+        "-1",
+        "false",
+        "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+    ];
+    assert_eq!(expect, got);
+}
+
+#[test]
+fn unmarshal_record() {
+    let (mut name, mut n) = (String::new(), 0u32);
+    crate::unmarshal_record(&["gopher", "42"], &mut [&mut name, &mut n]).unwrap();
+    assert_eq!(("gopher", 42), (name.as_str(), n));
+
+    let test_vector: Vec<(Vec<&str>, &str)> = vec![
+        (vec!["x"], "encoding: record has 1 fields, expected 2"),
+        (vec!["x", "y"], "encoding: field 1: invalid u32 text \"y\""),
+    ];
+    for (i, (record, expect)) in test_vector.into_iter().enumerate() {
+        let fields: &mut [&mut dyn TextUnmarshaler] = &mut [&mut name, &mut n];
+        let err = crate::unmarshal_record(&record, fields).unwrap_err();
+        assert_eq!(expect, err.to_string(), "#{}", i);
+    }
+}
+
+#[test]
+fn json() {
+    assert_eq!(
+        json::Value::from("1.25"),
+        crate::marshal_json(&1.25f32).unwrap()
+    );
+
+    let mut v = 7u8;
+    crate::unmarshal_json(&json::Value::Null, &mut v).unwrap();
+    assert_eq!(7, v);
+    crate::unmarshal_json(&json::Value::from("9"), &mut v).unwrap();
+    assert_eq!(9, v);
+
+    let err = crate::unmarshal_json(&json::Value::from(9), &mut v).unwrap_err();
+    assert_eq!(
+        "encoding: cannot unmarshal JSON number into a TextUnmarshaler",
+        err.to_string()
+    );
+}
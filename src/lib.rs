@@ -1,10 +1,39 @@
 //! encoding defines interfaces shared by other modules that convert data to and from byte-level and textual
 //! representations.
+//!
+//! Values convert themselves with the [BinaryMarshaler], [BinaryUnmarshaler], [TextMarshaler]
+//! and [TextUnmarshaler] traits, after Go's `encoding` package, and their appending variants
+//! [BinaryAppender] and [TextAppender]. They're implemented for [pem::Block], the
+//! [base32::Encoding] and [base64::Encoding] configurations, strings and primitives, so that
+//! such values can be stored as [csv] fields by [marshal_record] and [unmarshal_record], or as
+//! [json] strings by [marshal_json] and [unmarshal_json].
+//!
+//! The [Codec] trait unifies the binary-to-text encodings [hex], [base32], [base64] and
+//! [ascii85], which can then be selected at runtime by [codec_by_name] and streamed through
+//! the generic [Encoder] and [Decoder].
+
+#![allow(clippy::needless_doctest_main)]
+
+mod codec;
+mod errors;
+mod fields;
+mod marshaler;
+mod stream;
+
+pub use codec::*;
+pub use errors::*;
+pub use fields::*;
+pub use marshaler::*;
+pub use stream::*;
 
 pub use ascii85;
+pub use asn1;
 pub use base32;
 pub use base64;
 pub use binary;
 pub use csv;
+pub use gob;
 pub use hex;
+pub use json;
 pub use pem;
+pub use xml;
@@ -0,0 +1,318 @@
+use crate::Error;
+
+/// `BinaryMarshaler` is implemented by values that can marshal themselves into a binary form.
+///
+/// It's implemented by all [BinaryAppender]s.
+pub trait BinaryMarshaler {
+    /// Returns the binary form of `self`.
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error>;
+}
+
+/// `BinaryAppender` is implemented by values that can append their binary form to a buffer,
+/// saving the allocation [BinaryMarshaler::marshal_binary] makes.
+pub trait BinaryAppender {
+    /// Appends the binary form of `self` to `b`. `b` is left with unspecified contents
+    /// appended on error.
+    fn append_binary(&self, b: &mut Vec<u8>) -> Result<(), Error>;
+}
+
+/// `BinaryUnmarshaler` is implemented by values that can unmarshal a binary form of
+/// themselves.
+pub trait BinaryUnmarshaler {
+    /// Replaces `self` by the value of binary form `data`, which must at least accept the
+    /// form [BinaryMarshaler::marshal_binary] returns.
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error>;
+}
+
+/// `TextMarshaler` is implemented by values that can marshal themselves into a textual form,
+/// which is UTF-8 encoded.
+///
+/// It's implemented by all [TextAppender]s.
+pub trait TextMarshaler {
+    /// Returns the textual form of `self`.
+    fn marshal_text(&self) -> Result<Vec<u8>, Error>;
+}
+
+/// `TextAppender` is implemented by values that can append their textual form to a buffer,
+/// saving the allocation [TextMarshaler::marshal_text] makes.
+pub trait TextAppender {
+    /// Appends the textual form of `self` to `b`. `b` is left with unspecified contents
+    /// appended on error.
+    fn append_text(&self, b: &mut Vec<u8>) -> Result<(), Error>;
+}
+
+/// `TextUnmarshaler` is implemented by values that can unmarshal a textual form of themselves.
+pub trait TextUnmarshaler {
+    /// Replaces `self` by the value of textual form `text`, which must at least accept the
+    /// form [TextMarshaler::marshal_text] returns.
+    fn unmarshal_text(&mut self, text: &[u8]) -> Result<(), Error>;
+}
+
+impl<T> BinaryMarshaler for T
+where
+    T: BinaryAppender + ?Sized,
+{
+    fn marshal_binary(&self) -> Result<Vec<u8>, Error> {
+        let mut b = vec![];
+        self.append_binary(&mut b)?;
+        Ok(b)
+    }
+}
+
+impl<T> TextMarshaler for T
+where
+    T: TextAppender + ?Sized,
+{
+    fn marshal_text(&self) -> Result<Vec<u8>, Error> {
+        let mut b = vec![];
+        self.append_text(&mut b)?;
+        Ok(b)
+    }
+}
+
+/// The textual form of a block is its PEM encoding.
+impl TextAppender for pem::Block {
+    fn append_text(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+        pem::encode(b, self)?;
+        Ok(())
+    }
+}
+
+/// Only whitespace may surround the PEM block.
+impl TextUnmarshaler for pem::Block {
+    fn unmarshal_text(&mut self, text: &[u8]) -> Result<(), Error> {
+        let (block, rest) = match pem::decode(text) {
+            Ok(v) => v,
+            Err(_) => return Err(Error::Invalid("no PEM block found".to_string())),
+        };
+        if !rest.iter().all(u8::is_ascii_whitespace) {
+            return Err(Error::Invalid(
+                "unexpected data after PEM block".to_string(),
+            ));
+        }
+
+        *self = block;
+        Ok(())
+    }
+}
+
+/// The binary form of an encoding is its alphabet followed by its padding character, if any.
+impl BinaryAppender for base32::Encoding {
+    fn append_binary(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+        b.extend_from_slice(self.alphabet());
+        b.extend(self.padding());
+        Ok(())
+    }
+}
+
+impl BinaryUnmarshaler for base32::Encoding {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        let (alphabet, padding) = split_alphabet_binary("base32", data, 32)?;
+        *self = new_base32_encoding(alphabet, padding)?;
+        Ok(())
+    }
+}
+
+/// The textual form of an encoding is its alphabet followed by its padding character, if any.
+/// The padding character is UTF-8 encoded, so that the text stays valid.
+impl TextAppender for base32::Encoding {
+    fn append_text(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+        b.extend_from_slice(self.alphabet());
+        if let Some(c) = self.padding() {
+            b.extend_from_slice((c as char).encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        Ok(())
+    }
+}
+
+impl TextUnmarshaler for base32::Encoding {
+    fn unmarshal_text(&mut self, text: &[u8]) -> Result<(), Error> {
+        let (alphabet, padding) = split_alphabet_text("base32", text, 32)?;
+        let padding = match padding.map(u8::try_from) {
+            None => None,
+            Some(Ok(c)) => Some(c),
+            Some(Err(_)) => {
+                return Err(Error::Invalid(
+                    "base32 padding character above '\\xff'".to_string(),
+                ))
+            }
+        };
+        *self = new_base32_encoding(alphabet, padding)?;
+        Ok(())
+    }
+}
+
+/// The binary form of an encoding is its alphabet followed by its padding character, if any.
+/// Strictness isn't part of it, and is kept by unmarshaling.
+impl BinaryAppender for base64::Encoding {
+    fn append_binary(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+        b.extend_from_slice(self.alphabet());
+        b.extend(self.padding());
+        Ok(())
+    }
+}
+
+impl BinaryUnmarshaler for base64::Encoding {
+    fn unmarshal_binary(&mut self, data: &[u8]) -> Result<(), Error> {
+        let (alphabet, padding) = split_alphabet_binary("base64", data, 64)?;
+        *self = new_base64_encoding(alphabet, padding, self.is_strict())?;
+        Ok(())
+    }
+}
+
+/// The textual form of an encoding is the same as its binary form, as the padding character
+/// is ASCII.
+impl TextAppender for base64::Encoding {
+    fn append_text(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+        self.append_binary(b)
+    }
+}
+
+impl TextUnmarshaler for base64::Encoding {
+    fn unmarshal_text(&mut self, text: &[u8]) -> Result<(), Error> {
+        let (alphabet, padding) = split_alphabet_text("base64", text, 64)?;
+        let padding = match padding {
+            None => None,
+            Some(c) if c.is_ascii() => Some(c as u8),
+            Some(_) => {
+                return Err(Error::Invalid(
+                    "base64 padding character isn't ASCII".to_string(),
+                ))
+            }
+        };
+        *self = new_base64_encoding(alphabet, padding, self.is_strict())?;
+        Ok(())
+    }
+}
+
+impl TextAppender for str {
+    fn append_text(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+        b.extend_from_slice(self.as_bytes());
+        Ok(())
+    }
+}
+
+impl TextAppender for String {
+    fn append_text(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+        self.as_str().append_text(b)
+    }
+}
+
+impl TextUnmarshaler for String {
+    fn unmarshal_text(&mut self, text: &[u8]) -> Result<(), Error> {
+        *self = to_str(text)?.to_string();
+        Ok(())
+    }
+}
+
+macro_rules! impl_text_for_primitive {
+    ($($t:ty),+) => {
+        $(
+            /// The textual form is the one of [Display][std::fmt::Display] and
+            /// [FromStr][std::str::FromStr].
+            impl TextAppender for $t {
+                fn append_text(&self, b: &mut Vec<u8>) -> Result<(), Error> {
+                    b.extend_from_slice(self.to_string().as_bytes());
+                    Ok(())
+                }
+            }
+
+            impl TextUnmarshaler for $t {
+                fn unmarshal_text(&mut self, text: &[u8]) -> Result<(), Error> {
+                    let s = to_str(text)?;
+                    *self = s.parse().map_err(|_| {
+                        Error::Invalid(format!("invalid {} text {:?}", stringify!($t), s))
+                    })?;
+                    Ok(())
+                }
+            }
+        )+
+    };
+}
+
+impl_text_for_primitive!(
+    bool, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+fn to_str(text: &[u8]) -> Result<&str, Error> {
+    std::str::from_utf8(text).map_err(|_| Error::Invalid("text isn't UTF-8".to_string()))
+}
+
+/// Splits `data` into an alphabet of `size` bytes and an optional padding byte.
+fn split_alphabet_binary<'a>(
+    name: &str,
+    data: &'a [u8],
+    size: usize,
+) -> Result<(&'a str, Option<u8>), Error> {
+    if data.len() != size && data.len() != size + 1 {
+        return Err(Error::Invalid(format!(
+            "{} encoding of {} bytes, expected {} or {}",
+            name,
+            data.len(),
+            size,
+            size + 1
+        )));
+    }
+
+    let alphabet = std::str::from_utf8(&data[..size])
+        .map_err(|_| Error::Invalid(format!("{} alphabet isn't UTF-8", name)))?;
+    Ok((alphabet, data.get(size).copied()))
+}
+
+/// Splits `text` into an alphabet of `size` bytes and an optional padding character.
+fn split_alphabet_text<'a>(
+    name: &str,
+    text: &'a [u8],
+    size: usize,
+) -> Result<(&'a str, Option<char>), Error> {
+    let text = to_str(text)?;
+    let invalid = || {
+        Error::Invalid(format!(
+            "{} encoding must be a {}-byte alphabet optionally followed by a padding character",
+            name, size
+        ))
+    };
+
+    if !text.is_char_boundary(size) {
+        return Err(invalid());
+    }
+    let (alphabet, padding) = text.split_at(size);
+    let mut padding = padding.chars();
+    match (padding.next(), padding.next()) {
+        (c, None) => Ok((alphabet, c)),
+        _ => Err(invalid()),
+    }
+}
+
+fn new_base32_encoding(alphabet: &str, padding: Option<u8>) -> Result<base32::Encoding, Error> {
+    let invalid = |err: base32::AlphabetError| Error::Invalid(format!("base32 {}", err));
+
+    let mut e = base32::Encoding::try_new(alphabet).map_err(invalid)?;
+    e.try_with_padding(padding).map_err(invalid)?;
+    Ok(e)
+}
+
+fn new_base64_encoding(
+    alphabet: &str,
+    padding: Option<u8>,
+    strict: bool,
+) -> Result<base64::Encoding, Error> {
+    let invalid = |err: base64::AlphabetError| Error::Invalid(format!("base64 {}", err));
+
+    let mut e = base64::Encoding::try_new(alphabet).map_err(invalid)?;
+    match padding {
+        Some(c) => {
+            e.try_with_padding(c as char).map_err(invalid)?;
+        }
+        None => {
+            e.without_padding();
+        }
+    };
+    if strict {
+        e.strict();
+    }
+    Ok(e)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use crate::{BinaryMarshaler, BinaryUnmarshaler, TextAppender, TextMarshaler, TextUnmarshaler};
+
+const ALPHABET_32: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const ALPHABET_64: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[test]
+fn pem_block() {
+    let mut headers = HashMap::new();
+    headers.insert("Animal".to_string(), "Gopher".to_string());
+    let block = pem::Block {
+        type_: "MESSAGE".to_string(),
+        headers,
+        bytes: b"test".to_vec(),
+    };
+
+    let text = block.marshal_text().unwrap();
+    const EXPECT: &str =
+        "-----BEGIN MESSAGE-----\nAnimal: Gopher\n\ndGVzdA==\n-----END MESSAGE-----\n";
+    assert_eq!(EXPECT, String::from_utf8(text.clone()).unwrap());
+
+    let mut got = pem::Block::default();
+    got.unmarshal_text(&text).unwrap();
+    assert_eq!(block, got);
+
+    let mut b = b"prefix:".to_vec();
+    block.append_text(&mut b).unwrap();
+    assert_eq!([&b"prefix:"[..], &text].concat(), b);
+}
+
+#[test]
+fn pem_block_error() {
+    let mut bad = pem::Block::default();
+    bad.headers.insert("a:b".to_string(), "c".to_string());
+    let err = bad.marshal_text().unwrap_err();
+    assert_eq!(
+        "encoding: cannot encode a header key that contains a colon",
+        err.to_string()
+    );
+
+    let test_vector = vec![
+        (&b"no block"[..], "encoding: no PEM block found"),
+        (
+            &b"-----BEGIN A-----\n-----END A-----\ntrailing"[..],
+            "encoding: unexpected data after PEM block",
+        ),
+    ];
+    for (i, (text, expect)) in test_vector.into_iter().enumerate() {
+        let mut b = pem::Block::default();
+        let err = b.unmarshal_text(text).unwrap_err();
+        assert_eq!(expect, err.to_string(), "#{}", i);
+    }
+}
+
+#[test]
+fn base32_encoding() {
+    let text = base32::STD_ENCODING.marshal_text().unwrap();
+    assert_eq!(format!("{}=", ALPHABET_32).as_bytes(), text.as_slice());
+
+    let mut e = *base32::HEX_ENCODING;
+    e.unmarshal_text(&text).unwrap();
+    assert_eq!(ALPHABET_32.as_bytes(), e.alphabet());
+    assert_eq!(Some(b'='), e.padding());
+
+    // Paddings above ASCII are UTF-8 encoded in text, but not in binary.
+    let mut e = *base32::STD_ENCODING;
+    e.with_padding(Some(0xe9));
+    let text = e.marshal_text().unwrap();
+    assert_eq!(format!("{}\u{e9}", ALPHABET_32).as_bytes(), text.as_slice());
+    let binary = e.marshal_binary().unwrap();
+    assert_eq!([ALPHABET_32.as_bytes(), &[0xe9]].concat(), binary);
+
+    let mut got = *base32::STD_ENCODING;
+    got.unmarshal_text(&text).unwrap();
+    assert_eq!(Some(0xe9), got.padding());
+    let mut got = *base32::STD_ENCODING;
+    got.unmarshal_binary(&binary).unwrap();
+    assert_eq!(Some(0xe9), got.padding());
+
+    let mut got = *base32::STD_ENCODING;
+    got.unmarshal_binary(ALPHABET_32.as_bytes()).unwrap();
+    assert_eq!(None, got.padding());
+    assert_eq!("MZXW6", got.encode_to_string(b"foo"));
+}
+
+#[test]
+fn base64_encoding() {
+    let mut e = *base64::RAW_URL_ENCODING;
+    e.strict();
+
+    let text = base64::STD_ENCODING.marshal_text().unwrap();
+    assert_eq!(format!("{}=", ALPHABET_64).as_bytes(), text.as_slice());
+    e.unmarshal_text(&text).unwrap();
+    assert_eq!(ALPHABET_64.as_bytes(), e.alphabet());
+    assert_eq!(Some(b'='), e.padding());
+    assert!(e.is_strict());
+
+    let binary = base64::RAW_STD_ENCODING.marshal_binary().unwrap();
+    assert_eq!(ALPHABET_64.as_bytes(), binary.as_slice());
+    e.unmarshal_binary(&binary).unwrap();
+    assert_eq!(None, e.padding());
+    assert_eq!("Zm9v", e.encode_to_string(b"foo"));
+}
+
+#[test]
+fn encoding_error() {
+    let dup = format!("A{}", &ALPHABET_64[1..]).replace('B', "A");
+    let test_vector = vec![
+        ("AB".to_string(), "encoding: base64 encoding must be a 64-byte alphabet optionally followed by a padding character"),
+        (format!("{}==", ALPHABET_64), "encoding: base64 encoding must be a 64-byte alphabet optionally followed by a padding character"),
+        (format!("{}\u{e9}", ALPHABET_64), "encoding: base64 padding character isn't ASCII"),
+        (format!("{}+", ALPHABET_64), "encoding: base64 padding character '+' contained in alphabet"),
+        (format!("{}\n", ALPHABET_64), "encoding: base64 padding character is a newline"),
+        (format!("\n{}", &ALPHABET_64[1..]), "encoding: base64 encoding alphabet has newline character at byte 0"),
+        (format!("{}\u{e9}", &ALPHABET_64[2..]), "encoding: base64 encoding alphabet has non-ASCII symbol at byte 62"),
+        (dup, "encoding: base64 encoding alphabet has duplicate symbol 'A' at byte 1"),
+    ];
+
+    for (i, (text, expect)) in test_vector.into_iter().enumerate() {
+        let mut e = *base64::STD_ENCODING;
+        let err = e.unmarshal_text(text.as_bytes()).unwrap_err();
+        assert_eq!(expect, err.to_string(), "#{}", i);
+    }
+
+    let test_vector = vec![
+        (
+            format!("{}A", ALPHABET_32),
+            "encoding: base32 padding character 'A' contained in alphabet",
+        ),
+        (
+            format!("{}\r", ALPHABET_32),
+            "encoding: base32 padding character is a newline",
+        ),
+        (
+            format!("{}\n", &ALPHABET_32[1..]),
+            "encoding: base32 encoding alphabet has newline character at byte 31",
+        ),
+        (
+            format!("{}\u{e9}", &ALPHABET_32[2..]),
+            "encoding: base32 encoding alphabet has non-ASCII symbol at byte 30",
+        ),
+        (
+            format!("A{}", &ALPHABET_32[1..]).replace('B', "A"),
+            "encoding: base32 encoding alphabet has duplicate symbol 'A' at byte 1",
+        ),
+    ];
+
+    for (i, (text, expect)) in test_vector.into_iter().enumerate() {
+        let mut e = *base32::STD_ENCODING;
+        let err = e.unmarshal_text(text.as_bytes()).unwrap_err();
+        assert_eq!(expect, err.to_string(), "#{} base32", i);
+    }
+
+    let mut e = *base32::STD_ENCODING;
+    let err = e.unmarshal_binary(&[0xff; 32]).unwrap_err();
+    assert_eq!("encoding: base32 alphabet isn't UTF-8", err.to_string());
+    let err = e
+        .unmarshal_text(format!("{}\u{100}", ALPHABET_32).as_bytes())
+        .unwrap_err();
+    assert_eq!(
+        "encoding: base32 padding character above '\\xff'",
+        err.to_string()
+    );
+}
+
+#[test]
+fn primitives() {
+    assert_eq!(b"-12".to_vec(), (-12i32).marshal_text().unwrap());
+    assert_eq!(b"1.5".to_vec(), 1.5f64.marshal_text().unwrap());
+    assert_eq!(b"true".to_vec(), true.marshal_text().unwrap());
+    assert_eq!(b"hi".to_vec(), "hi".marshal_text().unwrap());
+
+    let mut v = 0u8;
+    v.unmarshal_text(b"255").unwrap();
+    assert_eq!(255, v);
+    let err = v.unmarshal_text(b"256").unwrap_err();
+    assert_eq!("encoding: invalid u8 text \"256\"", err.to_string());
+
+    let mut s = String::new();
+    s.unmarshal_text("caf\u{e9}".as_bytes()).unwrap();
+    assert_eq!("caf\u{e9}", s);
+    let err = s.unmarshal_text(b"\xff").unwrap_err();
+    assert_eq!("encoding: text isn't UTF-8", err.to_string());
+}
@@ -0,0 +1,214 @@
+use std::io::{self, Read, Write};
+
+use crate::{Codec, Error};
+
+/// Size of the chunks read from the underlying reader by [Decoder].
+const CHUNK_SIZE: usize = 1024;
+
+/// An Encoder encodes the data written to it with a [Codec], and writes the encoding to the
+/// wrapped writer.
+///
+/// Codecs operate in blocks, so a trailing partial block is only encoded once the encoder is
+/// flushed or dropped. Flushing ends the encoding: writing afterwards fails.
+///
+/// # Example
+/// ```
+#[doc = include_str!("../../examples/stream.rs")]
+/// ```
+pub struct Encoder<C, W>
+where
+    C: Codec,
+    W: Write,
+{
+    codec: C,
+    w: W,
+    /// Pending input of less than a block.
+    buf: Vec<u8>,
+    out: Vec<u8>,
+    /// Whether the encoder has been flushed, after which nothing may be written.
+    finished: bool,
+}
+
+/// A Decoder reads data encoded with a [Codec] from the wrapped reader, and decodes it.
+///
+/// Malformed input is reported by an [io::Error] of kind
+/// [InvalidData][io::ErrorKind::InvalidData] wrapping an [Error::Decode], whose offsets are
+/// those in the whole input and output.
+pub struct Decoder<C, R>
+where
+    C: Codec,
+    R: Read,
+{
+    codec: C,
+    r: R,
+    /// Input read but not decoded yet.
+    buf: Vec<u8>,
+    eof: bool,
+    /// Output decoded but not read yet, from `out_pos`.
+    out: Vec<u8>,
+    out_pos: usize,
+    /// Number of input bytes decoded before `buf`.
+    consumed: usize,
+    /// Number of bytes decoded so far.
+    written: usize,
+}
+
+impl<C, W> Encoder<C, W>
+where
+    C: Codec,
+    W: Write,
+{
+    /// Returns a new encoder writing the encoding by `codec` of the data written to it to `w`.
+    pub fn new(codec: C, w: W) -> Self {
+        Self {
+            codec,
+            w,
+            buf: vec![],
+            out: vec![],
+            finished: false,
+        }
+    }
+
+    /// Encodes `src` and writes the encoding to the wrapped writer.
+    fn encode(&mut self, src: &[u8]) -> io::Result<()> {
+        self.out.resize(self.codec.encoded_len(src.len()), 0);
+        let n = self.codec.encode(&mut self.out, src);
+        self.w.write_all(&self.out[..n])
+    }
+}
+
+impl<C, W> Drop for Encoder<C, W>
+where
+    C: Codec,
+    W: Write,
+{
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<C, W> Write for Encoder<C, W>
+where
+    C: Codec,
+    W: Write,
+{
+    fn write(&mut self, p: &[u8]) -> io::Result<usize> {
+        if self.finished {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "encoding: write after flush",
+            ));
+        }
+
+        let block_len = self.codec.block_len();
+        let mut p = p;
+        let n = p.len();
+
+        // Complete the pending block first.
+        if !self.buf.is_empty() {
+            let m = (block_len - self.buf.len()).min(p.len());
+            self.buf.extend_from_slice(&p[..m]);
+            p = &p[m..];
+            if self.buf.len() < block_len {
+                return Ok(n);
+            }
+
+            let buf = std::mem::take(&mut self.buf);
+            self.encode(&buf)?;
+        }
+
+        let m = p.len() - p.len() % block_len;
+        for chunk in p[..m].chunks(CHUNK_SIZE / block_len * block_len) {
+            self.encode(chunk)?;
+        }
+        self.buf.extend_from_slice(&p[m..]);
+
+        Ok(n)
+    }
+
+    /// Encodes the pending partial block, and flushes the wrapped writer. The encoder can't
+    /// be written to afterwards.
+    fn flush(&mut self) -> io::Result<()> {
+        self.finished = true;
+        if !self.buf.is_empty() {
+            let buf = std::mem::take(&mut self.buf);
+            self.encode(&buf)?;
+        }
+        self.w.flush()
+    }
+}
+
+impl<C, R> Decoder<C, R>
+where
+    C: Codec,
+    R: Read,
+{
+    /// Returns a new decoder reading the data encoded by `codec` from `r`.
+    pub fn new(codec: C, r: R) -> Self {
+        Self {
+            codec,
+            r,
+            buf: vec![],
+            eof: false,
+            out: vec![],
+            out_pos: 0,
+            consumed: 0,
+            written: 0,
+        }
+    }
+}
+
+impl<C, R> Read for Decoder<C, R>
+where
+    C: Codec,
+    R: Read,
+{
+    fn read(&mut self, p: &mut [u8]) -> io::Result<usize> {
+        while self.out_pos == self.out.len() {
+            if self.eof && self.buf.is_empty() {
+                return Ok(0);
+            }
+
+            if !self.eof {
+                let n = self.buf.len();
+                self.buf.resize(n + CHUNK_SIZE, 0);
+                let k = match self.r.read(&mut self.buf[n..]) {
+                    Ok(k) => k,
+                    Err(err) => {
+                        self.buf.truncate(n);
+                        return Err(err);
+                    }
+                };
+                self.buf.truncate(n + k);
+                self.eof = k == 0;
+            }
+
+            self.out.resize(self.codec.decoded_len(self.buf.len()), 0);
+            let (written, consumed) = match self.codec.decode(&mut self.out, &self.buf, self.eof) {
+                Ok(v) => v,
+                Err(Error::Decode(err)) => {
+                    let err = Error::Decode(err.offset_by(self.consumed, self.written));
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+                }
+                Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+            };
+            self.out.truncate(written);
+            self.out_pos = 0;
+            self.buf.drain(..consumed);
+            self.consumed += consumed;
+            self.written += written;
+            if self.eof {
+                // Whatever is left can't be decoded.
+                self.buf.clear();
+            }
+        }
+
+        let n = (self.out.len() - self.out_pos).min(p.len());
+        p[..n].copy_from_slice(&self.out[self.out_pos..self.out_pos + n]);
+        self.out_pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests;
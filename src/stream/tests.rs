@@ -0,0 +1,126 @@
+use std::io::{self, Read, Write};
+
+use crate::{Ascii85, Codec, DecodeError, Decoder, Encoder, Error, Hex};
+
+/// Reads at most `n` bytes at a time.
+struct ChunkReader<'a>(&'a [u8], usize);
+
+impl<'a> Read for ChunkReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.1.min(buf.len()).min(self.0.len());
+        buf[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        Ok(n)
+    }
+}
+
+fn codecs() -> Vec<Box<dyn Codec>> {
+    vec![
+        Box::new(Hex),
+        Box::new(*base32::STD_ENCODING),
+        Box::new(*base32::HEX_ENCODING),
+        Box::new(*base64::STD_ENCODING),
+        Box::new(*base64::RAW_URL_ENCODING),
+        Box::new(Ascii85),
+    ]
+}
+
+#[test]
+fn encoder() {
+    let data: Vec<u8> = (0..3000u32).map(|v| (v * 7 % 256) as u8).collect();
+
+    for (i, codec) in codecs().into_iter().enumerate() {
+        let expect = codec.encode_to_string(&data);
+
+        for chunk in [1, 2, 3, 7, 1000, 4000] {
+            let mut out = vec![];
+            {
+                let mut w = Encoder::new(&codec, &mut out);
+                for v in data.chunks(chunk) {
+                    w.write_all(v).unwrap();
+                }
+            }
+            assert_eq!(expect.as_bytes(), out.as_slice(), "#{} chunk={}", i, chunk);
+        }
+    }
+}
+
+#[test]
+fn encoder_write_after_flush() {
+    let mut out = vec![];
+    {
+        let mut w = Encoder::new(*base64::STD_ENCODING, &mut out);
+        w.write_all(b"foob").unwrap();
+        w.flush().unwrap();
+        assert!(w.write_all(b"ar").is_err());
+    }
+    assert_eq!(b"Zm9vYg==", out.as_slice());
+}
+
+#[test]
+fn decoder() {
+    let data: Vec<u8> = (0..3000u32).map(|v| (v * 7 % 256) as u8).collect();
+
+    for (i, codec) in codecs().into_iter().enumerate() {
+        let encoded = codec.encode_to_string(&data);
+
+        for chunk in [1, 3, 5, 1000, 4000] {
+            let mut got = vec![];
+            let mut r = Decoder::new(&codec, ChunkReader(encoded.as_bytes(), chunk));
+            r.read_to_end(&mut got).unwrap();
+            assert_eq!(data, got, "#{} chunk={}", i, chunk);
+        }
+    }
+}
+
+#[test]
+fn decoder_newlines() {
+    let mut got = vec![];
+    let mut r = Decoder::new(
+        *base64::STD_ENCODING,
+        ChunkReader(b"Zm9v\r\nYmFy\nYg==\n", 3),
+    );
+    r.read_to_end(&mut got).unwrap();
+    assert_eq!(b"foobarb", got.as_slice());
+}
+
+#[test]
+fn decoder_error() {
+    let mut r = Decoder::new(Hex, ChunkReader(b"666f6", 2));
+    let err = r.read_to_end(&mut vec![]).unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        "encoding: hex: odd length of hex string at input byte 4 after writing 2 bytes",
+        err.to_string()
+    );
+}
+
+/// Returns the offsets in the input and output of the error of a codec, if it has any.
+fn offsets(err: &Error) -> Option<(usize, usize)> {
+    match err {
+        Error::Decode(DecodeError::Hex { idx, written, .. }) => Some((*idx, *written)),
+        Error::Decode(DecodeError::Base32(err)) => Some((err.idx, err.written)),
+        Error::Decode(DecodeError::Base64(err)) => Some((err.idx, err.written)),
+        Error::Decode(DecodeError::Ascii85(err)) => Some((err.idx, err.written)),
+        _ => None,
+    }
+}
+
+#[test]
+fn decoder_error_offset() {
+    let data: Vec<u8> = (0..3000u32).map(|v| (v * 7 % 256) as u8).collect();
+
+    for (i, codec) in codecs().into_iter().enumerate() {
+        let mut encoded = codec.encode_to_string(&data).into_bytes();
+        encoded[2001] = b'~';
+        let expect = offsets(&codec.decode_to_vec(&encoded).unwrap_err());
+
+        for chunk in [1, 7, 1000] {
+            let mut r = Decoder::new(&codec, ChunkReader(&encoded, chunk));
+            let err = r.read_to_end(&mut vec![]).unwrap_err();
+            assert_eq!(io::ErrorKind::InvalidData, err.kind());
+            let err = err.into_inner().unwrap().downcast::<Error>().unwrap();
+            assert_eq!(expect, offsets(&err), "#{} chunk={}", i, chunk);
+        }
+    }
+}